@@ -1,118 +1,191 @@
 use anyhow::{anyhow, Context, Ok, Result};
 use chrono::prelude::{DateTime, Utc};
+use serde::Deserialize;
 use std::{
-    fs,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
     process::{Command, ExitStatus, Stdio},
 };
 
 use crate::{
     filesystem::FileType,
+    glob::glob_match,
     logs::log,
+    shell::{command_template_args, render_command_template, shell_command},
     tree::{TreeNode, TreeNodeType},
     tui::Tui,
 };
 
 #[derive(Debug, Clone)]
 pub struct MenuAction {
-    pub name: &'static str,
+    pub name: String,
     pub operation: Operation,
+    pub filter: ActionFilter,
 }
 
 #[derive(Debug, Clone)]
 pub enum Operation {
-    ShellCommand { template: &'static str },
-    InteractiveShellCommand { template: &'static str },
+    ShellCommand { template: String },
+    InteractiveShellCommand { template: String },
     PickAbsolutePath,
     PickRelativePath,
     Rename,
     CreateFile,
     CreateDir,
-    Delete,
+    Delete, // moves to trash
+    DeletePermanently,
+    ShowTrash,
     CopyToClipboard { is_relative_path: bool },
     FileDetails,
     CustomCommand,
     CustomInteractiveCommand,
     ViewContent,
+    AddBookmark,
+    Yank,
+    Cut,
+    Paste,
+    ResolvePasteConflict,
+    Undo,
 }
 
-pub fn generate_known_actions() -> Vec<MenuAction> {
-    vec![
-        MenuAction {
-            name: "Pick absolute path",
-            operation: Operation::PickAbsolutePath,
-        },
-        MenuAction {
-            name: "Pick relative path",
-            operation: Operation::PickRelativePath,
-        },
-        MenuAction {
-            name: "View",
-            operation: Operation::ViewContent,
-        },
-        MenuAction {
-            name: "Rename",
-            operation: Operation::Rename,
-        },
+/// Decides which entries an action can be run on.
+#[derive(Debug, Clone, Default)]
+pub struct ActionFilter {
+    pub target: ActionTarget,
+    pub name_glob: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionTarget {
+    #[default]
+    All,
+    Files,
+    Directories,
+}
+
+/// Action defined by the user in the `[[actions]]` section of the config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserActionConfig {
+    pub name: String,
+    pub command: String, // placeholders like `{}` are passed as arguments referring to the selected entry
+    #[serde(default)]
+    pub interactive: bool,
+    #[serde(default)]
+    pub applies_to: ActionTarget,
+    pub glob: Option<String>,
+}
+
+impl ActionFilter {
+    pub fn accepts(&self, name: &str, is_directory: bool) -> bool {
+        let target_matches = match self.target {
+            ActionTarget::All => true,
+            ActionTarget::Files => !is_directory,
+            ActionTarget::Directories => is_directory,
+        };
+        let name_matches = match &self.name_glob {
+            Some(name_glob) => glob_match(name_glob, name),
+            None => true,
+        };
+        target_matches && name_matches
+    }
+}
+
+impl MenuAction {
+    fn builtin(name: &str, operation: Operation) -> Self {
         MenuAction {
-            name: "Delete",
-            operation: Operation::Delete,
+            name: name.to_string(),
+            operation,
+            filter: ActionFilter::default(),
+        }
+    }
+}
+
+pub fn build_user_action(action_config: &UserActionConfig) -> Result<MenuAction> {
+    if action_config.name.trim().is_empty() {
+        return Err(anyhow!("action name must not be empty"));
+    }
+    if action_config.command.trim().is_empty() {
+        return Err(anyhow!(
+            "command of action '{}' must not be empty",
+            action_config.name
+        ));
+    }
+    let template = action_config.command.clone();
+    let operation = match action_config.interactive {
+        true => Operation::InteractiveShellCommand { template },
+        false => Operation::ShellCommand { template },
+    };
+    Ok(MenuAction {
+        name: action_config.name.clone(),
+        operation,
+        filter: ActionFilter {
+            target: action_config.applies_to,
+            name_glob: action_config.glob.clone(),
         },
-        MenuAction {
-            name: "View in less",
-            operation: Operation::InteractiveShellCommand {
-                template: "less -Src \"{}\"",
+    })
+}
+
+pub fn generate_known_actions() -> Vec<MenuAction> {
+    vec![
+        MenuAction::builtin("Pick absolute path", Operation::PickAbsolutePath),
+        MenuAction::builtin("Pick relative path", Operation::PickRelativePath),
+        MenuAction::builtin("View", Operation::ViewContent),
+        MenuAction::builtin("Rename", Operation::Rename),
+        MenuAction::builtin("Move to trash", Operation::Delete),
+        MenuAction::builtin("Delete permanently", Operation::DeletePermanently),
+        MenuAction::builtin("Yank (copy)", Operation::Yank),
+        MenuAction::builtin("Cut", Operation::Cut),
+        MenuAction::builtin("Paste here", Operation::Paste),
+        MenuAction::builtin("Undo last change", Operation::Undo),
+        MenuAction::builtin(
+            "View in less",
+            Operation::InteractiveShellCommand {
+                template: "less -Src {}".to_string(),
             },
-        },
-        MenuAction {
-            name: "Edit in vim",
-            operation: Operation::InteractiveShellCommand {
-                template: "vim \"{}\"",
+        ),
+        MenuAction::builtin(
+            "Edit in vim",
+            Operation::InteractiveShellCommand {
+                template: "vim {}".to_string(),
             },
-        },
-        MenuAction {
-            name: "Open with default app",
-            operation: Operation::ShellCommand {
-                template: "xdg-open \"{}\"",
+        ),
+        MenuAction::builtin(
+            "Open with default app",
+            Operation::ShellCommand {
+                template: "xdg-open {}".to_string(),
             },
-        },
-        MenuAction {
-            name: "Details",
-            operation: Operation::FileDetails,
-        },
-        MenuAction {
-            name: "Create file",
-            operation: Operation::CreateFile,
-        },
-        MenuAction {
-            name: "Create directory",
-            operation: Operation::CreateDir,
-        },
-        MenuAction {
-            name: "Copy absolute path to clipboard",
-            operation: Operation::CopyToClipboard {
+        ),
+        MenuAction::builtin("Details", Operation::FileDetails),
+        MenuAction::builtin("Create file", Operation::CreateFile),
+        MenuAction::builtin("Create directory", Operation::CreateDir),
+        MenuAction::builtin("Bookmark current directory", Operation::AddBookmark),
+        MenuAction::builtin("Show trash", Operation::ShowTrash),
+        MenuAction::builtin(
+            "Copy absolute path to clipboard",
+            Operation::CopyToClipboard {
                 is_relative_path: false,
             },
-        },
-        MenuAction {
-            name: "Copy relative path to clipboard",
-            operation: Operation::CopyToClipboard {
+        ),
+        MenuAction::builtin(
+            "Copy relative path to clipboard",
+            Operation::CopyToClipboard {
                 is_relative_path: true,
             },
-        },
-        MenuAction {
-            name: "Run interactive, external command",
-            operation: Operation::CustomInteractiveCommand,
-        },
-        MenuAction {
-            name: "Run command",
-            operation: Operation::CustomCommand,
-        },
+        ),
+        MenuAction::builtin(
+            "Run interactive, external command",
+            Operation::CustomInteractiveCommand,
+        ),
+        MenuAction::builtin("Run command", Operation::CustomCommand),
     ]
 }
 
 pub fn execute_shell_operation(path: &String, command_template: &str) -> Result<()> {
-    let cmd = String::from(command_template).replace("{}", path);
-    execute_shell(cmd.clone())
+    let cmd = render_command_template(command_template);
+    execute_shell(cmd, &command_template_args(path))
 }
 
 pub fn execute_interactive_shell_operation(
@@ -120,12 +193,10 @@ pub fn execute_interactive_shell_operation(
     command_template: &str,
     tui: &mut Tui,
 ) -> Result<()> {
-    let cmd = String::from(command_template).replace("{}", path);
-    log(format!("Executing command: {}", cmd).as_str());
+    let cmd = render_command_template(command_template);
+    log(format!("Executing command: {} with {}", cmd, path).as_str());
     tui.exit().context("failed to exit TUI mode")?;
-    let mut output = Command::new("sh")
-        .arg("-c")
-        .arg(cmd.clone())
+    let mut output = shell_command(&cmd, &command_template_args(path))
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -145,11 +216,10 @@ pub fn execute_interactive_shell_operation(
     Ok(())
 }
 
-pub fn execute_shell(cmd: String) -> Result<()> {
-    log(format!("Executing command: {}", cmd).as_str());
-    let c = Command::new("sh")
-        .arg("-c")
-        .arg(cmd.clone())
+/// Runs the script with positional arguments.
+pub fn execute_shell(cmd: String, args: &[String]) -> Result<()> {
+    log(format!("Executing command: {} with {:?}", cmd, args).as_str());
+    let c = shell_command(&cmd, args)
         .stdin(Stdio::inherit())
         .stderr(Stdio::piped())
         .stdout(Stdio::piped())
@@ -173,43 +243,82 @@ pub fn execute_shell(cmd: String) -> Result<()> {
     Ok(())
 }
 
-pub fn rename_file(abs_path: &String, new_name: &String) -> Result<()> {
-    let path_parts = abs_path.split('/').collect::<Vec<&str>>();
-    let folder_abs_path: String = path_parts[..path_parts.len() - 1].join("/");
-    let cmd = format!("mv \"{}\" \"{}/{}\"", abs_path, folder_abs_path, new_name);
-    execute_shell(cmd)
+/// Renames the file within its directory, returning the new path.
+pub fn rename_file(abs_path: &String, new_name: &String) -> Result<String> {
+    let source = Path::new(abs_path);
+    let folder_path: &Path = source.parent().unwrap_or(Path::new("/"));
+    let target: PathBuf = folder_path.join(new_name);
+    if target.symlink_metadata().is_ok() {
+        return Err(anyhow!("'{}' already exists", target.to_string_lossy()));
+    }
+    log(format!("Renaming {} to {}", abs_path, target.to_string_lossy()).as_str());
+    fs::rename(source, &target).with_context(|| {
+        format!(
+            "failed to rename '{}' to '{}'",
+            abs_path,
+            target.to_string_lossy()
+        )
+    })?;
+    Ok(target.to_string_lossy().to_string())
 }
 
 pub fn create_file(abs_path: &String) -> Result<()> {
-    let cmd = format!("touch \"{}\"", abs_path);
-    execute_shell(cmd)
+    log(format!("Creating file {}", abs_path).as_str());
+    if let Some(parent) = Path::new(abs_path).parent() {
+        fs::create_dir_all(parent).with_context(|| {
+            format!("failed to create directory '{}'", parent.to_string_lossy())
+        })?;
+    }
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(abs_path)
+        .with_context(|| format!("failed to create file '{}'", abs_path))?;
+    Ok(())
 }
 
 pub fn create_directory(abs_path: &String) -> Result<()> {
-    let cmd = format!("mkdir -p \"{}\"", abs_path);
-    execute_shell(cmd)
+    log(format!("Creating directory {}", abs_path).as_str());
+    fs::create_dir_all(abs_path)
+        .with_context(|| format!("failed to create directory '{}'", abs_path))
 }
 
 pub fn delete_tree_node(tree_node: &TreeNode, abs_path: &String) -> Result<()> {
-    let cmd: String = match &tree_node.kind {
-        TreeNodeType::SelfReference => {
-            format!("rm -rf \"{}\"", abs_path)
+    let is_directory = match &tree_node.kind {
+        TreeNodeType::SelfReference => true,
+        TreeNodeType::FileNode(file_node) => {
+            file_node.file_type == FileType::Directory && !file_node.is_symlink
+        }
+        TreeNodeType::SubtreeNode(entry) => {
+            entry.file_node().file_type == FileType::Directory && !entry.file_node().is_symlink
         }
-        TreeNodeType::FileNode(file_node) => match file_node.file_type {
-            FileType::Directory => {
-                format!("rm -rf \"{}\"", abs_path)
-            }
-            _ => {
-                format!("rm \"{}\"", abs_path)
-            }
-        },
     };
-    execute_shell(cmd)
+    delete_path(abs_path, is_directory)
+}
+
+pub fn delete_path(abs_path: &String, is_directory: bool) -> Result<()> {
+    log(format!("Deleting {}", abs_path).as_str());
+    match is_directory {
+        true => fs::remove_dir_all(abs_path),
+        false => fs::remove_file(abs_path),
+    }
+    .with_context(|| format!("failed to delete '{}'", abs_path))
 }
 
 pub fn copy_path_to_clipboard(path: &String) -> Result<()> {
-    let cmd = format!("printf \"%s\" \"{}\" | xclip -selection clipboard", path);
-    Command::new("sh").arg("-c").arg(cmd).spawn()?.wait()?;
+    let mut child = Command::new("xclip")
+        .arg("-selection")
+        .arg("clipboard")
+        .stdin(Stdio::piped())
+        .spawn()
+        .context("failed to start xclip")?;
+    child
+        .stdin
+        .take()
+        .context("failed to open xclip input")?
+        .write_all(path.as_bytes())
+        .context("failed to pass the path to xclip")?;
+    child.wait()?;
     Ok(())
 }
 
@@ -239,6 +348,14 @@ pub fn human_readable_size(size_bytes: u64) -> String {
     if size_bytes < 1024 {
         return format!("{} bytes", size_bytes);
     }
+    format!("{} ({} bytes)", short_readable_size(size_bytes), size_bytes)
+}
+
+/// Size in the largest fitting unit, e.g. `1.23 MB`.
+pub fn short_readable_size(size_bytes: u64) -> String {
+    if size_bytes < 1000 {
+        return format!("{} B", size_bytes);
+    }
     let mut size = size_bytes as f64;
     let units = ["B", "kB", "MB", "GB", "TB"];
     let mut i = 0;
@@ -246,7 +363,7 @@ pub fn human_readable_size(size_bytes: u64) -> String {
         size /= 1000.0;
         i += 1;
     }
-    format!("{:.2} {} ({} bytes)", size, units[i], size_bytes)
+    format!("{:.2} {}", size, units[i])
 }
 
 pub fn run_custom_command(workdir: String, cmd: &String) -> Result<String> {
@@ -316,8 +433,3 @@ pub fn run_custom_interactive_command(
     }
     Ok(format!("Command \"{}\" executed successfully.", cmd))
 }
-
-pub fn read_file_content(abs_path: &String) -> Result<String> {
-    let content: String = fs::read_to_string(abs_path).context("Unable to read file")?;
-    Ok(content)
-}
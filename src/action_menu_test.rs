@@ -0,0 +1,131 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use crate::action_menu::{
+        build_user_action, create_directory, create_file, delete_path, execute_shell_operation,
+        rename_file, ActionFilter, ActionTarget, Operation, UserActionConfig,
+    };
+    use crate::testing::{path_string, TempDir};
+
+    const HOSTILE_NAMES: [&str; 8] = [
+        "with space",
+        "double\"quote",
+        "single'quote",
+        "$(touch pwned)",
+        "`touch pwned`",
+        "semi;colon",
+        "new\nline",
+        "-rf",
+    ];
+
+    fn assert_no_side_effects(dir: &Path) {
+        assert!(!dir.join("pwned").exists());
+        assert!(!Path::new("pwned").exists());
+    }
+
+    #[test]
+    fn test_create_rename_delete_hostile_names() {
+        let dir = TempDir::new("fs-ops");
+        for name in HOSTILE_NAMES {
+            let file_path = path_string(&dir.join(name));
+            create_file(&file_path).unwrap();
+            assert!(dir.join(name).is_file());
+            assert!(create_file(&file_path).is_err());
+
+            let renamed = format!("{} renamed", name);
+            rename_file(&file_path, &renamed).unwrap();
+            assert!(!dir.join(name).exists());
+            assert!(dir.join(&renamed).is_file());
+
+            delete_path(&path_string(&dir.join(&renamed)), false).unwrap();
+            assert!(!dir.join(&renamed).exists());
+
+            let dir_path = path_string(&dir.join(name).join("nested"));
+            create_directory(&dir_path).unwrap();
+            assert!(dir.join(name).join("nested").is_dir());
+            delete_path(&path_string(&dir.join(name)), true).unwrap();
+            assert!(!dir.join(name).exists());
+        }
+        assert_no_side_effects(&dir);
+    }
+
+    #[test]
+    fn test_rename_does_not_overwrite() {
+        let dir = TempDir::new("rename-overwrite");
+        fs::write(dir.join("a"), "a").unwrap();
+        fs::write(dir.join("b"), "b").unwrap();
+        assert!(rename_file(&path_string(&dir.join("a")), &"b".to_string()).is_err());
+        assert_eq!(fs::read_to_string(dir.join("b")).unwrap(), "b");
+    }
+
+    #[test]
+    fn test_shell_operation_hostile_names() {
+        let dir = TempDir::new("shell-ops");
+        for name in HOSTILE_NAMES {
+            let file_path = path_string(&dir.join(name));
+            fs::write(&file_path, "content").unwrap();
+            execute_shell_operation(&file_path, "cp {} {}.copy").unwrap();
+            assert_eq!(
+                fs::read_to_string(format!("{}.copy", file_path)).unwrap(),
+                "content"
+            );
+        }
+        assert_no_side_effects(&dir);
+    }
+
+    fn user_action(name: &str, command: &str, interactive: bool) -> UserActionConfig {
+        UserActionConfig {
+            name: name.to_string(),
+            command: command.to_string(),
+            interactive,
+            applies_to: ActionTarget::Files,
+            glob: Some("*.rs".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_action_filter() {
+        let all = ActionFilter::default();
+        assert!(all.accepts("main.rs", false));
+        assert!(all.accepts("src", true));
+
+        let files = ActionFilter {
+            target: ActionTarget::Files,
+            name_glob: None,
+        };
+        assert!(files.accepts("main.rs", false));
+        assert!(!files.accepts("src", true));
+
+        let directories = ActionFilter {
+            target: ActionTarget::Directories,
+            name_glob: Some("s*".to_string()),
+        };
+        assert!(directories.accepts("src", true));
+        assert!(!directories.accepts("target", true));
+        assert!(!directories.accepts("src", false));
+    }
+
+    #[test]
+    fn test_build_user_action() {
+        let action = build_user_action(&user_action("Edit", "vim {}", false)).unwrap();
+        assert_eq!(action.name, "Edit");
+        assert!(matches!(
+            action.operation,
+            Operation::ShellCommand { ref template } if template == "vim {}"
+        ));
+        assert!(action.filter.accepts("main.rs", false));
+        assert!(!action.filter.accepts("main.py", false));
+        assert!(!action.filter.accepts("src.rs", true));
+
+        let action = build_user_action(&user_action("Edit", "vim {}", true)).unwrap();
+        assert!(matches!(
+            action.operation,
+            Operation::InteractiveShellCommand { ref template } if template == "vim {}"
+        ));
+
+        assert!(build_user_action(&user_action(" ", "vim {}", false)).is_err());
+        assert!(build_user_action(&user_action("Edit", "", true)).is_err());
+    }
+}
@@ -1,16 +1,36 @@
 use anyhow::Result;
 use ratatui::widgets::ListState;
 use signal_hook::{consts::SIGINT, consts::SIGTERM, iterator::Signals};
-use std::sync::mpsc;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 
 use crate::action_menu::{generate_known_actions, MenuAction, Operation};
-use crate::appdata::WindowFocus;
+use crate::appdata::{DirectoryState, NavigationEntry, WindowFocus};
 use crate::background::BackgroundEvent;
-use crate::filesystem::FileNode;
+use crate::bookmarks::{Bookmark, BookmarkMatch};
+use crate::breadcrumbs::BreadcrumbArea;
+use crate::config::Theme;
+use crate::event::Event;
+use crate::filesystem::{FileNode, SubtreeEntry};
+use crate::fuzzy::MatchMode;
+use crate::help::{HelpEntry, HelpMatch};
+use crate::highlight::HighlightSettings;
+use crate::history::{HistoryEntry, HistoryMatch};
+use crate::ignore::EntryFilter;
+use crate::keymap::Keymap;
 use crate::logs::print_logs;
+use crate::mouse::{Click, ListArea};
+use crate::preview::{Preview, PreviewRequest, DEFAULT_PREVIEW_MAX_LINES, DEFAULT_VIEW_MAX_BYTES};
+use crate::sorting::{SortMode, SortOrder};
+use crate::transfer::{PendingTransfer, TransferMode, TransferProgress, TransferRegister};
+use crate::trash::TrashEntry;
 use crate::tree::TreeNode;
 use crate::tui::Tui;
+use crate::undo::UndoRecord;
+use crate::viewer::FileViewer;
+use crate::vim::VimState;
 
 #[derive(Debug, Default)]
 pub struct App {
@@ -19,48 +39,142 @@ pub struct App {
     pub relative_path: bool,
     pub absolute_path: bool,
     pub print_stderr: bool,
+    pub config_path: Option<String>,
+    pub theme: Theme,
+    pub tick_rate: u64,
+    pub multi_select: bool,
+    pub null_separator: bool,
     pub parent_file_nodes: Vec<FileNode>, // nodes leading to the current directory
     pub starting_dir_nodes: Vec<FileNode>, // nodes leading to the starting directory
     pub child_file_nodes: Vec<FileNode>,  // nodes in the current directory
     pub child_tree_nodes: Vec<TreeNode>,  // nodes of filesystem tree to display
     pub dir_cursor: usize,
+    pub back_stack: Vec<NavigationEntry>, // previously visited locations, the latest last
+    pub forward_stack: Vec<NavigationEntry>, // locations left by going back
+    pub directory_states: HashMap<String, DirectoryState>, // last position in visited directories by path
     pub filter_text: String,
+    pub path_candidates: Vec<FileNode>, // entries completing the typed path
+    pub path_candidates_dir: String,
+    pub path_candidates_prefix: String, // beginning of the entry name typed so far
+    pub match_mode: MatchMode,
+    pub sort_order: SortOrder,
+    pub cli_sort_mode: Option<SortMode>, // given in the command line, over the config
+    pub long_listing: bool,              // show detail columns beside names
     pub file_tree_state: ListState,
-    pub picked_path: Option<String>,
+    pub tree_list_area: ListArea, // where the tree was last rendered, for mouse clicks
+    pub breadcrumb_areas: Vec<BreadcrumbArea>,
+    pub breadcrumb_cursor: usize, // depth of the ancestor selected in the path bar
+    pub action_menu_list_area: ListArea,
+    pub last_click: Option<Click>,
+    pub marked_routes: Vec<Vec<FileNode>>, // routes to the marked nodes in multi-select mode
+    pub picked_paths: Vec<String>,
     pub exit_code: i32,
     pub error_message: Option<String>,
     pub info_message: Option<String>,
     pub info_message_scroll: usize,
     pub window_focus: WindowFocus,
+    pub keymap: Keymap,
+    pub vim_mode: bool, // modal navigation in the tree window
+    pub vim: VimState,
     pub known_menu_actions: Vec<MenuAction>,
+    pub menu_actions: Vec<MenuAction>, // actions applicable to the selected entry
     pub action_menu_cursor_y: usize,
     pub action_menu_cursor_x: usize,
     pub action_menu_operation: Option<Operation>,
     pub action_menu_title: String,
     pub action_menu_buffer: String,
     pub background_event_channel: BackgroundEventChannel,
+    pub recursive_search: bool,
+    pub subtree_entries: Vec<SubtreeEntry>, // entries found below the current directory
+    pub subtree_search_generation: u64,
+    pub subtree_search_running: bool,
+    pub subtree_search_cancelled: Arc<AtomicBool>,
+    pub preview_enabled: bool,
+    pub preview_max_lines: usize,
+    pub preview: Option<Preview>, // preview of the selected entry, possibly outdated until the new one is loaded
+    pub preview_requested_path: Option<String>,
+    pub preview_request_tx: Option<mpsc::Sender<PreviewRequest>>, // started with the first preview
+    pub highlight_settings: HighlightSettings,
+    pub view_max_bytes: u64, // files opened in the viewer are cut off after this size
+    pub viewer: Option<FileViewer>,
+    pub transfer_register: Option<TransferRegister>, // yanked or cut paths
+    pub pending_transfer: Option<PendingTransfer>,
+    pub trash_entries: Vec<TrashEntry>, // listed in the trash popup
+    pub trash_cursor: usize,
+    pub transfer_status: Option<(TransferMode, TransferProgress)>, // progress of the running paste
+    pub undo_journal: Vec<UndoRecord>, // reversible filesystem changes, the latest last
+    pub viewer_generation: u64,        // identifies chunks read for the currently open viewer
+    pub bookmarks: Vec<Bookmark>,
+    pub bookmark_matches: Vec<BookmarkMatch>, // bookmarks matching the filter in the bookmarks popup
+    pub bookmarks_filter: String,
+    pub bookmarks_cursor: usize,
+    pub record_history: bool,
+    pub history_visits: Vec<String>, // paths visited in this session, not saved yet
+    pub history_entries: Vec<HistoryEntry>,
+    pub history_matches: Vec<HistoryMatch>, // entries matching the filter in the history popup
+    pub history_filter: String,
+    pub history_cursor: usize,
+    pub help_focus: WindowFocus, // window whose bindings are listed in the help popup
+    pub help_entries: Vec<HelpEntry>,
+    pub help_matches: Vec<HelpMatch>, // entries matching the filter in the help popup
+    pub help_filter: String,
+    pub help_cursor: usize,
+    pub start_in_history: bool,
+    pub hide_dotfiles: bool,
+    pub respect_ignore_files: bool,
+    pub entry_filter: EntryFilter, // filter applied to the current directory listing
+    pub hidden_entries_count: usize,
 }
 
 #[derive(Debug)]
 pub struct BackgroundEventChannel {
-    pub tx: mpsc::Sender<BackgroundEvent>,
+    pub tx: BackgroundEventSender,
     pub rx: mpsc::Receiver<BackgroundEvent>,
 }
 
+/// Sends events from background threads and wakes up the UI loop to handle them right away.
+#[derive(Debug, Clone)]
+pub struct BackgroundEventSender {
+    tx: mpsc::Sender<BackgroundEvent>,
+    waker: Arc<Mutex<Option<mpsc::Sender<Event>>>>,
+}
+
 impl Default for BackgroundEventChannel {
     fn default() -> Self {
         let (background_events_tx, background_events_rx) = mpsc::channel();
         Self {
-            tx: background_events_tx,
+            tx: BackgroundEventSender {
+                tx: background_events_tx,
+                waker: Arc::new(Mutex::new(None)),
+            },
             rx: background_events_rx,
         }
     }
 }
 
+impl BackgroundEventChannel {
+    pub fn set_waker(&self, waker: mpsc::Sender<Event>) {
+        *self.tx.waker.lock().unwrap() = Some(waker);
+    }
+}
+
+impl BackgroundEventSender {
+    pub fn send(&self, event: BackgroundEvent) -> Result<(), mpsc::SendError<BackgroundEvent>> {
+        self.tx.send(event)?;
+        if let Some(waker) = self.waker.lock().unwrap().as_ref() {
+            waker.send(Event::Background).ok();
+        }
+        Ok(())
+    }
+}
+
 impl App {
     pub fn new() -> Self {
         Self {
             known_menu_actions: generate_known_actions(),
+            preview_max_lines: DEFAULT_PREVIEW_MAX_LINES,
+            view_max_bytes: DEFAULT_VIEW_MAX_BYTES,
+            record_history: true,
             ..Default::default()
         }
     }
@@ -69,7 +183,9 @@ impl App {
         self.pre_init()?;
         let signal_rx = self.handle_signals();
         self.init_catch();
-        let mut tui: Tui = Tui::new();
+        let mut tui: Tui = Tui::new(self.tick_rate);
+        self.background_event_channel
+            .set_waker(tui.event_handler.waker());
         tui.enter()?;
 
         while !self.should_quit {
@@ -83,6 +199,7 @@ impl App {
 
         tui.exit()?;
         self.post_exit();
+        self.save_history_visits();
         print_logs();
         Ok(())
     }
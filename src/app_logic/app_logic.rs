@@ -2,21 +2,28 @@ use anyhow::{anyhow, Context, Result};
 use crossterm::tty::IsTty;
 use relative_path::{PathExt, RelativePathBuf, RelativeToError};
 use std::fs;
-use std::io::stdout;
+use std::io::{stdout, Write};
 use std::path::{Path, PathBuf};
 
+use crate::action_menu::build_user_action;
 use crate::app::App;
 use crate::appdata::WindowFocus;
+use crate::config::{default_keymap_path, load_config, Config, PathMode, DEFAULT_TICK_RATE_MS};
 use crate::errors::contextualized_error;
 use crate::filesystem::{
     get_path_file_nodes, get_string_abs_path, list_files, nodes_start_with, trim_end_slash,
     FileNode, FileType,
 };
+use crate::fuzzy::MatchMode;
+use crate::highlight::{is_known_theme, theme_names};
+use crate::keymap::load_keymap;
 use crate::numbers::{ClampNumExt, MyIntExt};
+use crate::sorting::{sort_file_nodes, SortMode};
 use crate::tree::{render_tree_nodes, TreeNode, TreeNodeType};
 
 const HELP_TEXT: &str = "fpick - interactive file picker. 
 Navigate with arrow keys and enter. It returns the selected path to standard output.
+Press F1 or ? inside the picker to list the key bindings of the current window.
 
 Usage:
   `fpick [OPTIONS]` to select a file in a current directory and return its path
@@ -25,6 +32,19 @@ Usage:
 Options:
     --relative, --rel, -r  Always print chosen path as relative to the starting directory
     --absolute, --abs, -a  Always print chosen path as absolute directory
+    --multi, -m            Allow marking multiple paths with Ctrl+T and print all of them
+    --null, -0             Separate printed paths with NUL character instead of newline
+    --recursive, -R        Start in recursive search mode, matching paths in the whole subtree
+    --exact, -e            Filter names by exact substrings instead of fuzzy matching
+    --preview, -p          Show a preview of the selected file or directory beside the list
+    --sort <MODE>          Sort entries by: name, natural, size, mtime or ext
+    --reverse              Reverse the sort order
+    --long, -l             Show size, modification time, permissions and owner of entries
+    --recent               Start with the list of most frequently and recently visited paths
+    --no-hidden            Hide dotfiles
+    --gitignore            Hide entries matched by .gitignore and .ignore files
+    --vim                  Navigate with vim-style keys in normal mode, press i or / to search
+    --config <FILE>        Read settings from a given file instead of $XDG_CONFIG_HOME/fpick/config.toml
     --version              Print version
     --help, -h             Print usage
 ";
@@ -53,6 +73,58 @@ impl App {
                 "--stderr" => {
                     self.print_stderr = true;
                 }
+                "--multi" | "-m" => {
+                    self.multi_select = true;
+                }
+                "--null" | "-0" => {
+                    self.null_separator = true;
+                }
+                "--recursive" | "-R" => {
+                    self.recursive_search = true;
+                }
+                "--exact" | "-e" => {
+                    self.match_mode = MatchMode::Substring;
+                }
+                "--preview" | "-p" => {
+                    self.preview_enabled = true;
+                }
+                "--sort" => match args.pop() {
+                    Some(mode) => match SortMode::parse(&mode) {
+                        Some(mode) => {
+                            self.sort_order.mode = mode;
+                            self.cli_sort_mode = Some(mode);
+                        }
+                        None => {
+                            return Err(anyhow!(
+                                "unknown sort mode '{}'. Use --help for usage",
+                                mode
+                            ))
+                        }
+                    },
+                    None => return Err(anyhow!("missing value for --sort option")),
+                },
+                "--reverse" => {
+                    self.sort_order.reverse = true;
+                }
+                "--long" | "-l" => {
+                    self.long_listing = true;
+                }
+                "--recent" => {
+                    self.start_in_history = true;
+                }
+                "--no-hidden" => {
+                    self.hide_dotfiles = true;
+                }
+                "--gitignore" => {
+                    self.respect_ignore_files = true;
+                }
+                "--vim" => {
+                    self.vim_mode = true;
+                }
+                "--config" => match args.pop() {
+                    Some(path) => self.config_path = Some(path),
+                    None => return Err(anyhow!("missing value for --config option")),
+                },
                 _ => {
                     if !self.starting_dir.is_empty() {
                         return Err(anyhow!(
@@ -63,9 +135,108 @@ impl App {
                 }
             }
         }
+        match self.apply_config() {
+            Ok(errors) if !errors.is_empty() => {
+                self.error_message = Some(format!(
+                    "Invalid settings in config:\n{}",
+                    errors.join("\n")
+                ))
+            }
+            Ok(_) => {}
+            Err(e) => self.error_message = Some(contextualized_error(&e)),
+        }
+        self.apply_keymap();
         Ok(())
     }
 
+    /// Fills in the settings from the config file that were not given in command-line arguments.
+    /// An unreadable file fails as a whole, while invalid settings are skipped and returned,
+    /// so that the valid ones still apply.
+    pub fn apply_config(&mut self) -> Result<Vec<String>> {
+        self.tick_rate = DEFAULT_TICK_RATE_MS;
+        let config: Config = load_config(self.config_path.as_ref())?;
+        let mut errors: Vec<String> = Vec::new();
+        self.theme = config.theme.resolve(&mut errors);
+        if let Some(tick_rate) = config.tick_rate_ms {
+            self.tick_rate = tick_rate.clamp_min(10);
+        }
+        if !self.relative_path && !self.absolute_path {
+            match config.path_mode {
+                PathMode::Relative => self.relative_path = true,
+                PathMode::Absolute => self.absolute_path = true,
+                PathMode::Auto => {}
+            }
+        }
+        self.sort_order.mode = self.cli_sort_mode.or(config.sort).unwrap_or_default();
+        self.sort_order.reverse |= config.sort_reverse;
+        if self.match_mode == MatchMode::Fuzzy {
+            self.match_mode = config.match_mode.unwrap_or_default();
+        }
+        for action_config in &config.actions {
+            match build_user_action(action_config) {
+                Ok(action) => self.known_menu_actions.push(action),
+                Err(e) => errors.push(format!("invalid action: {}", contextualized_error(&e))),
+            }
+        }
+        self.multi_select |= config.multi;
+        self.null_separator |= config.null_separator;
+        self.recursive_search |= config.recursive;
+        self.preview_enabled |= config.preview;
+        self.long_listing |= config.long_listing;
+        if let Some(record_history) = config.record_history {
+            self.record_history = record_history;
+        }
+        self.hide_dotfiles |= config.hide_dotfiles;
+        self.respect_ignore_files |= config.respect_ignore_files;
+        self.vim_mode |= config.vim_mode;
+        if let Some(preview_max_lines) = config.preview_max_lines {
+            self.preview_max_lines = preview_max_lines;
+        }
+        if let Some(view_max_bytes) = config.view_max_bytes {
+            self.view_max_bytes = view_max_bytes;
+        }
+        if let Some(syntax_highlighting) = config.syntax_highlighting {
+            self.highlight_settings.enabled = syntax_highlighting;
+        }
+        match config.syntax_theme {
+            Some(syntax_theme) if !is_known_theme(&syntax_theme) => errors.push(format!(
+                "unknown syntax theme '{}', available themes: {}",
+                syntax_theme,
+                theme_names().join(", ")
+            )),
+            Some(syntax_theme) => self.highlight_settings.theme = syntax_theme,
+            None => {}
+        }
+        Ok(errors)
+    }
+
+    /// Loads the user keymap on top of the default one.
+    /// Conflicting bindings are reported, but the keymap is used anyway.
+    pub fn apply_keymap(&mut self) {
+        let path: PathBuf = match default_keymap_path() {
+            Some(path) => path,
+            None => return,
+        };
+        let message = match load_keymap(&path) {
+            Ok((keymap, conflicts)) => {
+                self.keymap = keymap;
+                if conflicts.is_empty() {
+                    return;
+                }
+                format!(
+                    "Conflicting key bindings in '{}':\n{}",
+                    path.to_string_lossy(),
+                    conflicts.join("\n")
+                )
+            }
+            Err(e) => contextualized_error(&e),
+        };
+        self.error_message = match self.error_message.take() {
+            Some(error_message) => Some(format!("{}\n{}", error_message, message)),
+            None => Some(message),
+        };
+    }
+
     pub fn init_catch(&mut self) {
         if let Err(e) = self.init() {
             self.error_message = Some(contextualized_error(&e));
@@ -78,24 +249,43 @@ impl App {
         self.starting_dir_nodes = self.parent_file_nodes.clone();
         self.populate_current_child_nodes();
         self.set_dir_cursor(0);
+        if self.start_in_history {
+            self.open_history_dialog();
+        }
         Ok(())
     }
 
     pub fn render_tree_nodes(&mut self) {
-        self.child_tree_nodes = render_tree_nodes(&self.child_file_nodes, &self.filter_text);
+        self.child_tree_nodes = match self.recursive_search {
+            true => self.render_subtree_tree_nodes(),
+            false => render_tree_nodes(
+                &self.child_file_nodes,
+                self.get_name_filter(),
+                self.match_mode,
+            ),
+        };
+        self.refresh_marked_nodes();
         self.reset_cursor_offset();
         self.move_cursor(0); // validate cursor position
     }
 
     pub fn post_exit(&mut self) {
-        if let Some(picked_path) = &self.picked_path {
-            println!("{}", picked_path);
-            if !stdout().is_tty() && self.print_stderr {
+        if self.picked_paths.is_empty() {
+            self.exit_code = 1;
+            return;
+        }
+        let separator = match self.null_separator {
+            true => "\0",
+            false => "\n",
+        };
+        let mut out = stdout();
+        for picked_path in &self.picked_paths {
+            print!("{}{}", picked_path, separator);
+            if !out.is_tty() && self.print_stderr {
                 eprintln!("{}", picked_path);
             }
-        } else {
-            self.exit_code = 1;
         }
+        out.flush().ok();
     }
 
     pub fn set_dir_cursor(&mut self, cursor: usize) {
@@ -107,6 +297,7 @@ impl App {
         } else {
             self.file_tree_state.select(Some(self.dir_cursor));
         }
+        self.request_preview();
     }
 
     pub fn reset_cursor_offset(&mut self) {
@@ -135,13 +326,55 @@ impl App {
                 let new_cursor = match delta.abs() {
                     1 => self
                         .action_menu_cursor_y
-                        .move_rotating(delta, self.known_menu_actions.len()),
+                        .move_rotating(delta, self.menu_actions.len()),
                     _ => self
                         .action_menu_cursor_y
-                        .move_bound(delta, self.known_menu_actions.len()),
+                        .move_bound(delta, self.menu_actions.len()),
                 };
                 self.action_menu_cursor_y = new_cursor;
             }
+            WindowFocus::History => {
+                let new_cursor = match delta.abs() {
+                    1 => self
+                        .history_cursor
+                        .move_rotating(delta, self.history_matches.len()),
+                    _ => self
+                        .history_cursor
+                        .move_bound(delta, self.history_matches.len()),
+                };
+                self.history_cursor = new_cursor;
+            }
+            WindowFocus::Bookmarks => {
+                let new_cursor = match delta.abs() {
+                    1 => self
+                        .bookmarks_cursor
+                        .move_rotating(delta, self.bookmark_matches.len()),
+                    _ => self
+                        .bookmarks_cursor
+                        .move_bound(delta, self.bookmark_matches.len()),
+                };
+                self.bookmarks_cursor = new_cursor;
+            }
+            WindowFocus::Trash => {
+                let new_cursor = match delta.abs() {
+                    1 => self
+                        .trash_cursor
+                        .move_rotating(delta, self.trash_entries.len()),
+                    _ => self
+                        .trash_cursor
+                        .move_bound(delta, self.trash_entries.len()),
+                };
+                self.trash_cursor = new_cursor;
+            }
+            WindowFocus::Help => {
+                let new_cursor = match delta.abs() {
+                    1 => self
+                        .help_cursor
+                        .move_rotating(delta, self.help_matches.len()),
+                    _ => self.help_cursor.move_bound(delta, self.help_matches.len()),
+                };
+                self.help_cursor = new_cursor;
+            }
             _ => {}
         }
     }
@@ -152,7 +385,8 @@ impl App {
 
     pub fn populate_current_child_nodes(&mut self) {
         let path = self.get_current_string_path();
-        let nodes_result = list_files(std::path::Path::new(&path));
+        let with_details = self.long_listing || self.sort_order.mode.needs_details();
+        let nodes_result = list_files(std::path::Path::new(&path), with_details);
         if nodes_result.is_err() {
             self.error_message = Some(contextualized_error(&nodes_result.unwrap_err()));
             self.child_file_nodes = vec![];
@@ -160,40 +394,52 @@ impl App {
             return;
         }
         let mut nodes = nodes_result.unwrap();
-        nodes.sort_by(|a, b| {
-            if a.file_type == FileType::Directory && b.file_type != FileType::Directory {
-                std::cmp::Ordering::Less
-            } else if a.file_type != FileType::Directory && b.file_type == FileType::Directory {
-                std::cmp::Ordering::Greater
-            } else {
-                a.lowercase_name.cmp(&b.lowercase_name)
-            }
-        });
+        self.entry_filter = self.build_entry_filter();
+        let total_count = nodes.len();
+        nodes.retain(|node| self.entry_filter.accepts(&path, node));
+        self.hidden_entries_count = total_count - nodes.len();
+        sort_file_nodes(&mut nodes, self.sort_order);
         self.child_file_nodes = nodes;
+        self.preview_requested_path = None;
+        if self.recursive_search {
+            self.start_subtree_search();
+        }
         self.render_tree_nodes();
     }
 
+    /// Reloads what's listed after a background command,
+    /// without starting the recursive search over again.
+    pub fn refresh_visible_nodes(&mut self) {
+        match self.recursive_search {
+            true => self.render_tree_nodes(),
+            false => self.populate_current_child_nodes(),
+        }
+    }
+
     pub fn go_up(&mut self) {
         if self.parent_file_nodes.is_empty() {
             return;
         }
-        self.filter_text.clear();
-        let parent: FileNode = self.parent_file_nodes.pop().unwrap();
-        self.populate_current_child_nodes();
+        self.go_to_ancestor(self.parent_file_nodes.len() - 1);
+    }
+
+    /// Goes to the ancestor made of the first `depth` path nodes (0 is the root),
+    /// selecting the directory leading back to where we were.
+    pub fn go_to_ancestor(&mut self, depth: usize) {
+        if depth >= self.parent_file_nodes.len() {
+            return;
+        }
+        self.remember_location();
+        let child: FileNode = self.parent_file_nodes[depth].clone();
+        self.parent_file_nodes.truncate(depth);
+        self.restore_directory_state();
         let new_cursor = self
             .child_tree_nodes
             .iter()
-            .position(|node| node.name() == parent.name);
-        match new_cursor {
-            Some(idx) => {
-                self.dir_cursor = idx;
-            }
-            None => {
-                self.dir_cursor = 0;
-            }
+            .position(|node| node.name() == child.name);
+        if let Some(idx) = new_cursor {
+            self.set_dir_cursor(idx);
         }
-        self.reset_cursor_offset();
-        self.set_dir_cursor(self.dir_cursor);
     }
 
     pub fn go_into(&mut self) {
@@ -203,56 +449,58 @@ impl App {
         }
         match selected_node_o.unwrap().kind {
             TreeNodeType::SelfReference => return,
+            TreeNodeType::SubtreeNode(entry) => self.go_into_subtree_entry(&entry),
             TreeNodeType::FileNode(file_node) => {
                 if file_node.file_type != FileType::Directory {
                     return;
                 }
+                self.remember_location();
                 self.parent_file_nodes.push(file_node.clone());
-                self.filter_text.clear();
-                self.populate_current_child_nodes();
-                self.reset_cursor_offset();
-                self.set_dir_cursor(0);
+                self.record_history_visit(self.get_current_string_path());
+                self.restore_directory_state();
             }
         }
     }
 
     pub fn go_to_root(&mut self) {
+        self.remember_location();
         self.parent_file_nodes = vec![];
-        self.filter_text.clear();
-        self.populate_current_child_nodes();
-        self.reset_cursor_offset();
-        self.set_dir_cursor(0);
+        self.restore_directory_state();
     }
 
     pub fn go_to_home(&mut self) {
         match std::env::var("HOME") {
-            Ok(home_path) => match get_path_file_nodes(&home_path) {
-                Ok(nodes) => {
-                    self.parent_file_nodes = nodes;
-                    self.filter_text.clear();
-                    self.populate_current_child_nodes();
-                    self.reset_cursor_offset();
-                    self.set_dir_cursor(0);
-                }
-                Err(e) => self.show_error(contextualized_error(&e)),
-            },
+            Ok(home_path) => self.go_to_path(&home_path),
             Err(_) => self.show_error("HOME environment variable not set".to_string()),
         }
     }
 
+    pub fn go_to_path(&mut self, path: &String) {
+        match get_path_file_nodes(path) {
+            Ok(nodes) => {
+                self.remember_location();
+                self.parent_file_nodes = nodes;
+                self.restore_directory_state();
+            }
+            Err(e) => self.show_error(contextualized_error(&e)),
+        }
+    }
+
     pub fn enter_selected_node(&mut self) {
         let selected_node_o: Option<TreeNode> = self.get_selected_tree_node();
         if selected_node_o.is_none() {
             return;
         }
         match selected_node_o.unwrap().kind {
-            TreeNodeType::SelfReference => {
-                self.pick_current_dir();
+            TreeNodeType::FileNode(file_node) if file_node.file_type == FileType::Directory => {
+                self.go_into()
             }
-            TreeNodeType::FileNode(file_node) => match file_node.file_type {
-                FileType::Directory => self.go_into(),
-                _ => self.pick_selected_node(None),
-            },
+            TreeNodeType::SubtreeNode(entry) if entry.file_node().is_directory => {
+                self.go_into_subtree_entry(&entry)
+            }
+            _ if !self.marked_routes.is_empty() => self.pick_marked_nodes(None),
+            TreeNodeType::SelfReference => self.pick_current_dir(),
+            _ => self.pick_selected_node(None),
         }
     }
 
@@ -260,11 +508,17 @@ impl App {
         let current_dir_node = TreeNode {
             relevance: 0,
             kind: TreeNodeType::SelfReference,
+            marked: false,
+            matched_indices: vec![],
         };
         self.pick_tree_node(&current_dir_node, None);
     }
 
     pub fn pick_selected_node(&mut self, relative_mode: Option<bool>) {
+        if !self.marked_routes.is_empty() {
+            self.pick_marked_nodes(relative_mode);
+            return;
+        }
         let selected_node_o: Option<TreeNode> = self.get_selected_tree_node();
         if selected_node_o.is_none() {
             return;
@@ -274,18 +528,75 @@ impl App {
     }
 
     pub fn pick_tree_node(&mut self, tree_node: &TreeNode, relative_mode_o: Option<bool>) {
-        let chosen_path = self.build_tree_node_path(tree_node);
         let chosen_nodes = self.build_tree_nodes_route(tree_node);
+        if let Some(picked_path) = self.resolve_picked_path(&chosen_nodes, relative_mode_o) {
+            self.picked_paths = vec![picked_path];
+            self.quit();
+        }
+    }
+
+    pub fn pick_marked_nodes(&mut self, relative_mode_o: Option<bool>) {
+        let mut picked_paths: Vec<String> = Vec::new();
+        for chosen_nodes in self.marked_routes.clone() {
+            match self.resolve_picked_path(&chosen_nodes, relative_mode_o) {
+                Some(picked_path) => picked_paths.push(picked_path),
+                None => return,
+            }
+        }
+        self.picked_paths = picked_paths;
+        self.quit();
+    }
+
+    fn resolve_picked_path(
+        &mut self,
+        chosen_nodes: &Vec<FileNode>,
+        relative_mode_o: Option<bool>,
+    ) -> Option<String> {
+        let chosen_path = get_string_abs_path(chosen_nodes);
+        self.record_history_visit(chosen_path.clone());
         let relative_mode: bool = match relative_mode_o {
             Some(b) => b,
-            None => self.determine_relative_mode(&chosen_nodes),
+            None => self.determine_relative_mode(chosen_nodes),
         };
-        self.picked_path = match relative_mode {
+        match relative_mode {
             true => self.make_relative_path(&chosen_path),
             false => Some(chosen_path),
+        }
+    }
+
+    pub fn toggle_mark_selected_node(&mut self) {
+        if !self.multi_select {
+            return;
+        }
+        let selected_node: TreeNode = match self.get_selected_tree_node() {
+            Some(selected_node) => selected_node,
+            None => return,
         };
-        if !self.picked_path.is_none() {
-            self.quit();
+        let route = self.build_tree_nodes_route(&selected_node);
+        let route_path = get_string_abs_path(&route);
+        let marked_position = self
+            .marked_routes
+            .iter()
+            .position(|it| get_string_abs_path(it) == route_path);
+        match marked_position {
+            Some(idx) => {
+                self.marked_routes.remove(idx);
+            }
+            None => self.marked_routes.push(route),
+        }
+        self.refresh_marked_nodes();
+        self.move_cursor(1);
+    }
+
+    pub fn refresh_marked_nodes(&mut self) {
+        if !self.multi_select {
+            return;
+        }
+        let marked_paths: Vec<String> =
+            self.marked_routes.iter().map(get_string_abs_path).collect();
+        for idx in 0..self.child_tree_nodes.len() {
+            let node_path = self.build_tree_node_path(&self.child_tree_nodes[idx]);
+            self.child_tree_nodes[idx].marked = marked_paths.contains(&node_path);
         }
     }
 
@@ -305,6 +616,11 @@ impl App {
                 chosen_nodes.push(file_node.clone());
                 get_string_abs_path(&chosen_nodes)
             }
+            TreeNodeType::SubtreeNode(entry) => {
+                let mut chosen_nodes: Vec<FileNode> = self.parent_file_nodes.clone();
+                chosen_nodes.extend(entry.route.clone());
+                get_string_abs_path(&chosen_nodes)
+            }
         }
     }
 
@@ -316,6 +632,11 @@ impl App {
                 chosen_nodes.push(file_node.clone());
                 chosen_nodes
             }
+            TreeNodeType::SubtreeNode(entry) => {
+                let mut chosen_nodes: Vec<FileNode> = self.parent_file_nodes.clone();
+                chosen_nodes.extend(entry.route.clone());
+                chosen_nodes
+            }
         }
     }
 
@@ -332,6 +653,8 @@ impl App {
         let current_dir_node = TreeNode {
             relevance: 0,
             kind: TreeNodeType::SelfReference,
+            marked: false,
+            matched_indices: vec![],
         };
         self.build_tree_node_path(&current_dir_node)
     }
@@ -378,22 +701,74 @@ impl App {
                 FileType::Directory => true,
                 _ => false,
             },
+            TreeNodeType::SubtreeNode(entry) => entry.file_node().is_directory,
         }
     }
 
     pub fn type_search_text(&mut self, c: char) {
         self.filter_text.push(c);
+        self.refresh_path_candidates();
         self.render_tree_nodes();
         self.set_dir_cursor(0);
     }
 
+    pub fn toggle_match_mode(&mut self) {
+        self.match_mode = self.match_mode.toggled();
+        self.render_tree_nodes();
+        self.set_dir_cursor(0);
+    }
+
+    pub fn cycle_sort_mode(&mut self) {
+        self.sort_order.mode = self.sort_order.mode.next();
+        match self.sort_order.mode.needs_details() {
+            true => self.reload_child_nodes(),
+            false => self.resort_child_nodes(),
+        }
+    }
+
+    pub fn toggle_long_listing(&mut self) {
+        self.long_listing = !self.long_listing;
+        self.reload_child_nodes();
+    }
+
+    /// Lists the current directory again, keeping the selected entry if it's still visible.
+    pub fn reload_child_nodes(&mut self) {
+        let selected_path: Option<String> = self.get_selected_abs_path();
+        self.populate_current_child_nodes();
+        self.select_path(selected_path);
+    }
+
+    pub fn toggle_sort_reverse(&mut self) {
+        self.sort_order.reverse = !self.sort_order.reverse;
+        self.resort_child_nodes();
+    }
+
+    fn resort_child_nodes(&mut self) {
+        let selected_path: Option<String> = self.get_selected_abs_path();
+        sort_file_nodes(&mut self.child_file_nodes, self.sort_order);
+        self.render_tree_nodes();
+        self.select_path(selected_path);
+    }
+
+    /// Moves the cursor to the entry with the given path, or to the top if it's not listed.
+    pub fn select_path(&mut self, path: Option<String>) {
+        let new_cursor: Option<usize> = path.and_then(|path| {
+            self.child_tree_nodes
+                .iter()
+                .position(|node| self.build_tree_node_path(node) == path)
+        });
+        self.set_dir_cursor(new_cursor.unwrap_or(0));
+    }
+
     pub fn backspace_search_text(&mut self) {
         self.filter_text.pop();
+        self.refresh_path_candidates();
         self.render_tree_nodes();
     }
 
     pub fn clear_search_text(&mut self) {
         self.filter_text.clear();
+        self.refresh_path_candidates();
         self.render_tree_nodes();
     }
 
@@ -2,19 +2,33 @@ use std::str::Chars;
 
 use crate::action_menu::{
     copy_path_to_clipboard, create_directory, create_file, delete_tree_node,
-    execute_interactive_shell_operation, execute_shell_operation, get_file_details,
-    read_file_content, rename_file, run_custom_command, run_custom_interactive_command, MenuAction,
-    Operation,
+    execute_interactive_shell_operation, execute_shell_operation, get_file_details, rename_file,
+    run_custom_command, run_custom_interactive_command, MenuAction, Operation,
 };
 use crate::app::App;
 use crate::appdata::WindowFocus;
 use crate::background::BackgroundEvent;
+use crate::errors::contextualized_error;
+use crate::shell::shell_quote;
+use crate::transfer::TransferMode;
 use crate::tree::TreeNode;
 use crate::tui::Tui;
+use crate::undo::{first_missing_ancestor, UndoRecord};
 
 impl App {
     pub fn open_action_dialog(&mut self) {
-        if self.child_tree_nodes.is_empty() {
+        let tree_node: TreeNode = match self.get_selected_tree_node() {
+            Some(tree_node) => tree_node,
+            None => return,
+        };
+        let is_directory = App::is_tree_node_directory(&tree_node);
+        self.menu_actions = self
+            .known_menu_actions
+            .iter()
+            .filter(|action| action.filter.accepts(tree_node.name(), is_directory))
+            .cloned()
+            .collect();
+        if self.menu_actions.is_empty() {
             return;
         }
         self.window_focus = WindowFocus::ActionMenu;
@@ -38,19 +52,22 @@ impl App {
         let is_directory = App::is_tree_node_directory(&tree_node);
         let current_dir_path: String = self.get_current_dir_abs_path();
 
-        let action: &MenuAction = &self.known_menu_actions[self.action_menu_cursor_y];
+        let action: MenuAction = match self.menu_actions.get(self.action_menu_cursor_y) {
+            Some(action) => action.clone(),
+            None => return,
+        };
         self.window_focus = WindowFocus::Tree;
         self.action_menu_operation = Some(action.operation.clone());
         match action.operation {
             Operation::ShellCommand { template } => {
-                let result = execute_shell_operation(&abs_path, template);
+                let result = execute_shell_operation(&abs_path, &template);
                 match result {
                     Err(err) => self.show_error(err.to_string()),
                     _ => {}
                 }
             }
             Operation::InteractiveShellCommand { template } => {
-                let result = execute_interactive_shell_operation(&abs_path, template, tui);
+                let result = execute_interactive_shell_operation(&abs_path, &template, tui);
                 match result {
                     Err(err) => self.show_error(err.to_string()),
                     _ => {}
@@ -81,21 +98,23 @@ impl App {
             Operation::CustomCommand => {
                 self.open_action_menu_step2(
                     format!("Run command at {}", current_dir_path),
-                    format!("\"{}\"", abs_path),
+                    shell_quote(&abs_path),
                 );
             }
             Operation::CustomInteractiveCommand => {
                 self.open_action_menu_step2(
                     format!("Run interactive, external command at {}", current_dir_path),
-                    format!("\"{}\"", abs_path),
+                    shell_quote(&abs_path),
                 );
             }
             Operation::Delete => {
-                let result = delete_tree_node(&tree_node, &abs_path);
-                match result {
-                    Err(err) => self.show_error(err.to_string()),
-                    _ => {}
-                }
+                self.trash_selected_node();
+            }
+            Operation::DeletePermanently => {
+                self.delete_selected_node_confirm();
+            }
+            Operation::ShowTrash => {
+                self.open_trash_dialog();
             }
             Operation::CopyToClipboard { is_relative_path } => {
                 let result = match is_relative_path {
@@ -107,6 +126,14 @@ impl App {
                     _ => {}
                 }
             }
+            Operation::AddBookmark => {
+                self.add_bookmark_prompt();
+            }
+            Operation::Yank => self.register_transfer(TransferMode::Copy),
+            Operation::Cut => self.register_transfer(TransferMode::Move),
+            Operation::Paste => self.paste_transfer_register(),
+            Operation::Undo => self.undo_last_change(),
+            Operation::ResolvePasteConflict => {}
             Operation::FileDetails => {
                 let result = get_file_details(&abs_path, is_directory);
                 match result {
@@ -116,18 +143,14 @@ impl App {
             }
             Operation::ViewContent => {
                 if !is_directory {
-                    let result = read_file_content(&abs_path);
-                    match result {
-                        Ok(info) => self.show_info(info),
-                        Err(err) => self.show_error(err.to_string()),
-                    }
+                    self.open_viewer(abs_path.clone());
                 }
             }
         }
         self.populate_current_child_nodes();
     }
 
-    fn open_action_menu_step2(&mut self, title: String, buffer: String) {
+    pub fn open_action_menu_step2(&mut self, title: String, buffer: String) {
         self.window_focus = WindowFocus::ActionMenuStep2;
         self.action_menu_title = title;
         self.action_menu_buffer = buffer;
@@ -135,6 +158,17 @@ impl App {
     }
 
     pub fn execute_dialog_action_step2(&mut self, tui: &mut Tui) {
+        if let Some(Operation::AddBookmark) = self.action_menu_operation {
+            self.window_focus = WindowFocus::Tree;
+            if let Err(e) = self.add_bookmark(self.action_menu_buffer.trim().to_string()) {
+                self.show_error(contextualized_error(&e));
+            }
+            return;
+        }
+        if let Some(Operation::ResolvePasteConflict) = self.action_menu_operation {
+            self.resolve_paste_conflict(self.action_menu_buffer.trim().to_string());
+            return;
+        }
         let abs_path: String = match self.get_selected_abs_path() {
             Some(abs_path) => abs_path,
             None => return,
@@ -154,10 +188,10 @@ impl App {
                 let result = rename_file(&abs_path, &self.action_menu_buffer);
                 match result {
                     Err(err) => self.show_error(err.to_string()),
-                    _ => {}
+                    Ok(to) => self.record_undo(UndoRecord::Rename { from: abs_path, to }),
                 }
             }
-            Some(Operation::Delete) => {
+            Some(Operation::DeletePermanently) => {
                 if &self.action_menu_buffer != "yes" {
                     self.show_error("Operation aborted".to_string());
                     return;
@@ -170,18 +204,28 @@ impl App {
             }
             Some(Operation::CreateFile) => {
                 let full_path = format!("{}/{}", current_dir_path, &self.action_menu_buffer);
+                let created_path = first_missing_ancestor(&full_path);
                 let result = create_file(&full_path);
                 match result {
                     Err(err) => self.show_error(err.to_string()),
-                    _ => {}
+                    _ => {
+                        if let Some(path) = created_path {
+                            self.record_undo(UndoRecord::Create { path });
+                        }
+                    }
                 }
             }
             Some(Operation::CreateDir) => {
                 let full_path = format!("{}/{}", current_dir_path, &self.action_menu_buffer);
+                let created_path = first_missing_ancestor(&full_path);
                 let result = create_directory(&full_path);
                 match result {
                     Err(err) => self.show_error(err.to_string()),
-                    _ => {}
+                    _ => {
+                        if let Some(path) = created_path {
+                            self.record_undo(UndoRecord::Create { path });
+                        }
+                    }
                 }
             }
             Some(Operation::CustomCommand) => {
@@ -227,7 +271,7 @@ impl App {
             None => return,
         };
         let filename = abs_path.split('/').last().unwrap().to_string();
-        self.action_menu_operation = Some(Operation::Delete);
+        self.action_menu_operation = Some(Operation::DeletePermanently);
         self.open_action_menu_step2(
             format!(
                 "Are you sure you want to permanently delete \"{}\"?",
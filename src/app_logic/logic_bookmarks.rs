@@ -0,0 +1,111 @@
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+
+use crate::action_menu::Operation;
+use crate::app::App;
+use crate::appdata::WindowFocus;
+use crate::bookmarks::{
+    default_bookmarks_path, filter_bookmarks, load_bookmarks, save_bookmarks, upsert_bookmark,
+    Bookmark,
+};
+use crate::errors::contextualized_error;
+
+impl App {
+    pub fn open_bookmarks_dialog(&mut self) {
+        if let Err(e) = self.load_bookmarks() {
+            self.show_error(contextualized_error(&e));
+            return;
+        }
+        self.bookmarks_filter.clear();
+        self.refresh_bookmark_matches();
+        self.window_focus = WindowFocus::Bookmarks;
+    }
+
+    pub fn close_bookmarks_dialog(&mut self) {
+        self.window_focus = WindowFocus::Tree;
+    }
+
+    fn bookmarks_path(&self) -> Result<PathBuf> {
+        default_bookmarks_path().ok_or_else(|| {
+            anyhow!("can't locate bookmarks file: neither XDG_DATA_HOME nor HOME is set")
+        })
+    }
+
+    fn load_bookmarks(&mut self) -> Result<()> {
+        self.bookmarks = load_bookmarks(&self.bookmarks_path()?)?;
+        Ok(())
+    }
+
+    pub fn refresh_bookmark_matches(&mut self) {
+        self.bookmark_matches =
+            filter_bookmarks(&self.bookmarks, &self.bookmarks_filter, self.match_mode);
+        self.bookmarks_cursor = 0;
+    }
+
+    pub fn type_bookmarks_filter(&mut self, c: char) {
+        self.bookmarks_filter.push(c);
+        self.refresh_bookmark_matches();
+    }
+
+    pub fn backspace_bookmarks_filter(&mut self) {
+        self.bookmarks_filter.pop();
+        self.refresh_bookmark_matches();
+    }
+
+    pub fn get_selected_bookmark(&self) -> Option<&Bookmark> {
+        let bookmark_match = self.bookmark_matches.get(self.bookmarks_cursor)?;
+        self.bookmarks.get(bookmark_match.index)
+    }
+
+    pub fn jump_to_selected_bookmark(&mut self) {
+        let path: String = match self.get_selected_bookmark() {
+            Some(bookmark) => bookmark.path.clone(),
+            None => return,
+        };
+        self.window_focus = WindowFocus::Tree;
+        self.go_to_path(&path);
+    }
+
+    pub fn delete_selected_bookmark(&mut self) {
+        let index: usize = match self.bookmark_matches.get(self.bookmarks_cursor) {
+            Some(bookmark_match) => bookmark_match.index,
+            None => return,
+        };
+        let removed = self.bookmarks.remove(index);
+        if let Err(e) = self.store_bookmarks() {
+            self.bookmarks.insert(index, removed);
+            self.show_error(contextualized_error(&e));
+            return;
+        }
+        let cursor = self.bookmarks_cursor;
+        self.refresh_bookmark_matches();
+        self.bookmarks_cursor = cursor.min(self.bookmark_matches.len().saturating_sub(1));
+    }
+
+    fn store_bookmarks(&self) -> Result<()> {
+        save_bookmarks(&self.bookmarks_path()?, &self.bookmarks)
+    }
+
+    /// Asks for a name of the bookmark for the current directory.
+    pub fn add_bookmark_prompt(&mut self) {
+        let current_dir_path: String = self.get_current_dir_abs_path();
+        let default_name: String = match current_dir_path.rsplit('/').next() {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => "root".to_string(),
+        };
+        self.action_menu_operation = Some(Operation::AddBookmark);
+        self.open_action_menu_step2(format!("Bookmark {} as", current_dir_path), default_name);
+    }
+
+    pub fn add_bookmark(&mut self, name: String) -> Result<()> {
+        self.load_bookmarks()?;
+        let bookmark = Bookmark {
+            name: name.clone(),
+            path: self.get_current_dir_abs_path(),
+        };
+        upsert_bookmark(&mut self.bookmarks, bookmark);
+        self.store_bookmarks()?;
+        self.show_info(format!("Bookmark '{}' saved", name));
+        Ok(())
+    }
+}
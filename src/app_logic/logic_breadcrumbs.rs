@@ -0,0 +1,58 @@
+use crate::app::App;
+use crate::appdata::WindowFocus;
+use crate::breadcrumbs::{breadcrumb_at, layout_breadcrumbs, BreadcrumbPart};
+
+impl App {
+    /// Focuses the path bar, starting at the parent directory.
+    pub fn open_breadcrumbs(&mut self) {
+        self.breadcrumb_cursor = self.parent_file_nodes.len().saturating_sub(1);
+        self.window_focus = WindowFocus::Breadcrumbs;
+    }
+
+    pub fn close_breadcrumbs(&mut self) {
+        self.window_focus = WindowFocus::Tree;
+    }
+
+    pub fn move_breadcrumb_cursor(&mut self, delta: i32) {
+        let depth = self.breadcrumb_cursor as i32 + delta;
+        self.select_breadcrumb(depth.max(0) as usize);
+    }
+
+    pub fn select_breadcrumb(&mut self, depth: usize) {
+        self.breadcrumb_cursor = depth.min(self.parent_file_nodes.len());
+    }
+
+    pub fn jump_to_selected_breadcrumb(&mut self) {
+        self.window_focus = WindowFocus::Tree;
+        self.go_to_ancestor(self.breadcrumb_cursor);
+    }
+
+    pub fn get_breadcrumbs(&self, max_width: usize) -> Vec<BreadcrumbPart> {
+        let names: Vec<String> = self
+            .parent_file_nodes
+            .iter()
+            .map(|node| node.name.to_string())
+            .collect();
+        layout_breadcrumbs(&names, self.get_selected_breadcrumb(), max_width)
+    }
+
+    /// Depth of the segment to highlight in the path bar.
+    pub fn get_selected_breadcrumb(&self) -> Option<usize> {
+        match self.window_focus {
+            WindowFocus::Breadcrumbs => Some(self.breadcrumb_cursor),
+            _ => None,
+        }
+    }
+
+    /// Jumps to the clicked segment of the path bar. Returns false when no segment was clicked.
+    pub fn click_breadcrumb(&mut self, column: u16, row: u16) -> bool {
+        match breadcrumb_at(&self.breadcrumb_areas, column, row) {
+            Some(depth) => {
+                self.window_focus = WindowFocus::Tree;
+                self.go_to_ancestor(depth);
+                true
+            }
+            None => false,
+        }
+    }
+}
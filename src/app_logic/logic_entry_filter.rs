@@ -0,0 +1,43 @@
+use crate::app::App;
+use crate::filesystem::{get_string_abs_path, FileNode};
+use crate::ignore::{EntryFilter, IgnoreRules};
+
+impl App {
+    pub fn toggle_hide_dotfiles(&mut self) {
+        self.hide_dotfiles = !self.hide_dotfiles;
+        self.reload_child_nodes();
+    }
+
+    pub fn toggle_respect_ignore_files(&mut self) {
+        self.respect_ignore_files = !self.respect_ignore_files;
+        self.reload_child_nodes();
+    }
+
+    /// Builds the filter for the current directory,
+    /// reading ignore files from all directories leading to it.
+    pub fn build_entry_filter(&self) -> EntryFilter {
+        let ignore_rules: Option<IgnoreRules> = match self.respect_ignore_files {
+            true => {
+                let dir_paths: Vec<String> = (0..=self.parent_file_nodes.len())
+                    .map(|depth| {
+                        let nodes: Vec<FileNode> = self.parent_file_nodes[..depth].to_vec();
+                        get_string_abs_path(&nodes)
+                    })
+                    .collect();
+                Some(IgnoreRules::load_along(&dir_paths))
+            }
+            false => None,
+        };
+        EntryFilter {
+            hide_dotfiles: self.hide_dotfiles,
+            ignore_rules,
+        }
+    }
+
+    pub fn get_hidden_entries_status(&self) -> Option<String> {
+        if !self.entry_filter.is_active() {
+            return None;
+        }
+        Some(format!("{} hidden", self.hidden_entries_count))
+    }
+}
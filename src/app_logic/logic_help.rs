@@ -0,0 +1,38 @@
+use crate::app::App;
+use crate::appdata::WindowFocus;
+use crate::help::{filter_help_entries, help_entries};
+
+impl App {
+    /// Lists the bindings of the focused window, as they're dispatched by the keymap.
+    pub fn open_help(&mut self) {
+        if self.window_focus == WindowFocus::Help {
+            return;
+        }
+        self.help_focus = self.window_focus;
+        let vim_normal_mode = self.is_vim_normal_mode();
+        self.help_entries = help_entries(&self.keymap, self.help_focus, vim_normal_mode);
+        self.help_filter.clear();
+        self.refresh_help_matches();
+        self.window_focus = WindowFocus::Help;
+    }
+
+    pub fn close_help(&mut self) {
+        self.window_focus = self.help_focus;
+    }
+
+    pub fn refresh_help_matches(&mut self) {
+        self.help_matches =
+            filter_help_entries(&self.help_entries, &self.help_filter, self.match_mode);
+        self.help_cursor = 0;
+    }
+
+    pub fn type_help_filter(&mut self, c: char) {
+        self.help_filter.push(c);
+        self.refresh_help_matches();
+    }
+
+    pub fn backspace_help_filter(&mut self) {
+        self.help_filter.pop();
+        self.refresh_help_matches();
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::{anyhow, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app::App;
+use crate::appdata::WindowFocus;
+use crate::errors::contextualized_error;
+use crate::history::{
+    default_history_path, load_history, now_timestamp, rank_history, record_visit, save_history,
+    HistoryEntry,
+};
+use crate::logs::log;
+
+impl App {
+    /// Remembers a visited path, the history file is updated once on exit.
+    pub fn record_history_visit(&mut self, path: String) {
+        if self.record_history {
+            self.history_visits.push(path);
+        }
+    }
+
+    fn history_path(&self) -> Result<PathBuf> {
+        default_history_path().ok_or_else(|| {
+            anyhow!("can't locate history file: neither XDG_DATA_HOME nor HOME is set")
+        })
+    }
+
+    pub fn save_history_visits(&mut self) {
+        if self.history_visits.is_empty() {
+            return;
+        }
+        let result = self.history_path().and_then(|path| {
+            let mut entries: Vec<HistoryEntry> = load_history(&path)?;
+            let now = now_timestamp();
+            for visit in &self.history_visits {
+                record_visit(&mut entries, visit, now);
+            }
+            save_history(&path, &entries)
+        });
+        if let Err(e) = result {
+            log(format!("Failed to save history: {}", contextualized_error(&e)).as_str());
+        }
+    }
+
+    pub fn open_history_dialog(&mut self) {
+        let mut entries: Vec<HistoryEntry> =
+            match self.history_path().and_then(|p| load_history(&p)) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    self.show_error(contextualized_error(&e));
+                    return;
+                }
+            };
+        let now = now_timestamp();
+        for visit in &self.history_visits {
+            record_visit(&mut entries, visit, now);
+        }
+        entries.retain_mut(|entry| match fs::metadata(&entry.path) {
+            Ok(metadata) => {
+                entry.is_directory = metadata.is_dir();
+                true
+            }
+            Err(_) => false,
+        });
+        self.history_entries = entries;
+        self.history_filter.clear();
+        self.refresh_history_matches();
+        self.window_focus = WindowFocus::History;
+    }
+
+    pub fn close_history_dialog(&mut self) {
+        self.window_focus = WindowFocus::Tree;
+    }
+
+    pub fn refresh_history_matches(&mut self) {
+        self.history_matches = rank_history(
+            &self.history_entries,
+            &self.history_filter,
+            self.match_mode,
+            now_timestamp(),
+        );
+        self.history_cursor = 0;
+    }
+
+    pub fn type_history_filter(&mut self, c: char) {
+        self.history_filter.push(c);
+        self.refresh_history_matches();
+    }
+
+    pub fn backspace_history_filter(&mut self) {
+        self.history_filter.pop();
+        self.refresh_history_matches();
+    }
+
+    /// Opens the selected directory, or the directory of the selected file with the file highlighted.
+    pub fn jump_to_selected_history_entry(&mut self) {
+        let (path, is_directory): (String, bool) = match self
+            .history_matches
+            .get(self.history_cursor)
+            .and_then(|it| self.history_entries.get(it.index))
+        {
+            Some(entry) => (entry.path.clone(), entry.is_directory),
+            None => return,
+        };
+        self.window_focus = WindowFocus::Tree;
+        if is_directory {
+            self.go_to_path(&path);
+            return;
+        }
+        let parent: String = match Path::new(&path).parent() {
+            Some(parent) => parent.to_string_lossy().to_string(),
+            None => return,
+        };
+        self.go_to_path(&parent);
+        self.select_path(Some(path));
+    }
+}
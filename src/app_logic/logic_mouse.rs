@@ -0,0 +1,34 @@
+use std::time::Instant;
+
+use crate::app::App;
+use crate::mouse::Click;
+
+impl App {
+    /// Remembers the click and tells whether it completes a double click.
+    pub fn register_click(&mut self, column: u16, row: u16) -> bool {
+        let click = Click {
+            column,
+            row,
+            time: Instant::now(),
+        };
+        let double_click = self
+            .last_click
+            .is_some_and(|last_click| last_click.is_repeated_by(&click));
+        self.last_click = match double_click {
+            true => None, // a third click starts over
+            false => Some(click),
+        };
+        double_click
+    }
+
+    /// Selects the clicked entry, double click enters a directory or picks a file.
+    pub fn click_tree_node(&mut self, index: usize, double_click: bool) {
+        if index >= self.child_tree_nodes.len() {
+            return;
+        }
+        self.set_dir_cursor(index);
+        if double_click {
+            self.enter_selected_node();
+        }
+    }
+}
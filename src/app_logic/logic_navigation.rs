@@ -0,0 +1,82 @@
+use crate::app::App;
+use crate::appdata::{DirectoryState, NavigationEntry};
+
+const MAX_NAVIGATION_HISTORY: usize = 100;
+
+impl App {
+    fn current_navigation_entry(&self) -> NavigationEntry {
+        NavigationEntry {
+            parent_file_nodes: self.parent_file_nodes.clone(),
+            dir_cursor: self.dir_cursor,
+            filter_text: self.filter_text.clone(),
+        }
+    }
+
+    /// Saves the current location before moving to another directory.
+    pub fn remember_location(&mut self) {
+        self.save_directory_state();
+        let entry = self.current_navigation_entry();
+        self.back_stack.push(entry);
+        if self.back_stack.len() > MAX_NAVIGATION_HISTORY {
+            self.back_stack.remove(0);
+        }
+        self.forward_stack.clear();
+    }
+
+    pub fn go_back(&mut self) {
+        let entry: NavigationEntry = match self.back_stack.pop() {
+            Some(entry) => entry,
+            None => return,
+        };
+        self.save_directory_state();
+        let current = self.current_navigation_entry();
+        self.forward_stack.push(current);
+        self.restore_location(entry);
+    }
+
+    pub fn go_forward(&mut self) {
+        let entry: NavigationEntry = match self.forward_stack.pop() {
+            Some(entry) => entry,
+            None => return,
+        };
+        self.save_directory_state();
+        let current = self.current_navigation_entry();
+        self.back_stack.push(current);
+        self.restore_location(entry);
+    }
+
+    pub fn save_directory_state(&mut self) {
+        let state = DirectoryState {
+            dir_cursor: self.dir_cursor,
+            scroll_offset: self.file_tree_state.offset(),
+            filter_text: self.filter_text.clone(),
+        };
+        self.directory_states
+            .insert(self.get_current_string_path(), state);
+    }
+
+    /// Puts back the filter, cursor and scroll position last used in the current directory,
+    /// or starts from the top if it wasn't visited before.
+    pub fn restore_directory_state(&mut self) {
+        let state: DirectoryState = self
+            .directory_states
+            .get(&self.get_current_string_path())
+            .cloned()
+            .unwrap_or_default();
+        self.filter_text = state.filter_text;
+        self.populate_current_child_nodes();
+        self.file_tree_state = self
+            .file_tree_state
+            .clone()
+            .with_offset(state.scroll_offset);
+        self.set_dir_cursor(state.dir_cursor);
+    }
+
+    fn restore_location(&mut self, entry: NavigationEntry) {
+        self.parent_file_nodes = entry.parent_file_nodes;
+        self.filter_text = entry.filter_text;
+        self.populate_current_child_nodes();
+        self.reset_cursor_offset();
+        self.set_dir_cursor(entry.dir_cursor);
+    }
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+
+use crate::app::App;
+use crate::path_input::{
+    complete_name, expand_path_input, find_path_candidates, is_path_input, replace_typed_name,
+    split_path_input,
+};
+
+impl App {
+    pub fn is_path_input_mode(&self) -> bool {
+        is_path_input(&self.filter_text)
+    }
+
+    /// Filter applied to the names in the listing, none while a path is being typed.
+    pub fn get_name_filter(&self) -> &str {
+        match self.is_path_input_mode() {
+            true => "",
+            false => self.filter_text.as_str(),
+        }
+    }
+
+    fn get_expanded_path_input(&self) -> String {
+        let home_dir: Option<String> = std::env::var("HOME").ok();
+        expand_path_input(
+            &self.filter_text,
+            &self.get_current_string_path(),
+            home_dir.as_deref(),
+        )
+    }
+
+    /// Lists the entries matching the partially typed path.
+    pub fn refresh_path_candidates(&mut self) {
+        if !self.is_path_input_mode() {
+            self.path_candidates.clear();
+            return;
+        }
+        let expanded_path = self.get_expanded_path_input();
+        let (dir_path, name_prefix) = split_path_input(&expanded_path);
+        self.path_candidates = find_path_candidates(dir_path, name_prefix);
+        self.path_candidates_dir = dir_path.to_string();
+        self.path_candidates_prefix = name_prefix.to_string();
+    }
+
+    pub fn complete_path_input(&mut self) {
+        if self.filter_text == "~" {
+            self.filter_text.push('/');
+            self.refresh_path_candidates();
+            return;
+        }
+        let completed: String =
+            match complete_name(&self.path_candidates_prefix, &self.path_candidates) {
+                Some(completed) => completed,
+                None => return,
+            };
+        self.filter_text = replace_typed_name(&self.filter_text, &completed);
+        self.refresh_path_candidates();
+    }
+
+    /// Opens the typed directory, or the directory of the typed file with the file selected.
+    pub fn jump_to_typed_path(&mut self) {
+        let typed_path: String = self.filter_text.clone();
+        let expanded_path = self.get_expanded_path_input();
+        let target = Path::new(&expanded_path);
+        if !target.exists() {
+            self.show_error(format!("Path '{}' doesn't exist", expanded_path));
+            return;
+        }
+        self.filter_text.clear();
+        self.path_candidates.clear();
+        match typed_path.as_str() {
+            "/" => return self.go_to_root(),
+            "~" | "~/" => return self.go_to_home(),
+            _ => {}
+        }
+        if target.is_dir() {
+            self.go_to_path(&expanded_path);
+            return;
+        }
+        let parent_path = match target
+            .parent()
+            .and_then(|parent| fs::canonicalize(parent).ok())
+        {
+            Some(parent_path) => parent_path,
+            None => return,
+        };
+        let file_name = target
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let parent_path: String = parent_path.to_string_lossy().to_string();
+        self.go_to_path(&parent_path);
+        self.select_path(Some(format!(
+            "{}/{}",
+            parent_path.trim_end_matches('/'),
+            file_name
+        )));
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::mpsc;
+
+use crate::app::{App, BackgroundEventSender};
+use crate::background::BackgroundEvent;
+use crate::preview::{load_preview, Preview, PreviewRequest};
+
+impl App {
+    pub fn toggle_preview(&mut self) {
+        self.preview_enabled = !self.preview_enabled;
+        self.preview = None;
+        self.preview_requested_path = None;
+        self.request_preview();
+    }
+
+    /// Asks the preview thread to load the selected entry,
+    /// unless it's already loaded or being loaded.
+    pub fn request_preview(&mut self) {
+        if !self.preview_enabled {
+            return;
+        }
+        let path: String = match self.get_selected_abs_path() {
+            Some(path) => path,
+            None => {
+                self.preview = None;
+                self.preview_requested_path = None;
+                return;
+            }
+        };
+        if self.preview_requested_path.as_ref() == Some(&path) {
+            return;
+        }
+        self.preview_requested_path = Some(path.clone());
+        let request = PreviewRequest {
+            path,
+            max_lines: self.preview_max_lines,
+            highlight: self.highlight_settings.clone(),
+        };
+        let request_tx = self
+            .preview_request_tx
+            .get_or_insert_with(|| spawn_preview_worker(self.background_event_channel.tx.clone()));
+        if request_tx.send(request).is_err() {
+            self.preview_request_tx = None;
+        }
+    }
+
+    pub fn on_preview_loaded(&mut self, preview: Preview) {
+        if self.preview_requested_path.as_ref() == Some(&preview.path) {
+            self.preview = Some(preview);
+        }
+    }
+}
+
+/// Starts the thread loading previews one at a time.
+/// Requests queued up while a preview was loading are skipped, except for the latest one.
+fn spawn_preview_worker(result_tx: BackgroundEventSender) -> mpsc::Sender<PreviewRequest> {
+    let (request_tx, request_rx) = mpsc::channel::<PreviewRequest>();
+    std::thread::spawn(move || {
+        while let Ok(mut request) = request_rx.recv() {
+            while let Ok(newer_request) = request_rx.try_recv() {
+                request = newer_request;
+            }
+            let content = load_preview(&request.path, request.max_lines, &request.highlight);
+            let preview = Preview {
+                path: request.path,
+                content,
+            };
+            if result_tx
+                .send(BackgroundEvent::PreviewLoaded(preview))
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+    request_tx
+}
@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::app::App;
+use crate::background::BackgroundEvent;
+use crate::filesystem::{walk_subtree, FileNode, SubtreeEntry, MAX_SUBTREE_ENTRIES};
+use crate::tree::{merge_subtree_nodes, render_subtree_nodes, TreeNode, TreeNodeType};
+
+const MAX_DISPLAYED_SUBTREE_MATCHES: usize = 1000;
+
+impl App {
+    pub fn toggle_recursive_search(&mut self) {
+        self.recursive_search = !self.recursive_search;
+        match self.recursive_search {
+            true => self.start_subtree_search(),
+            false => self.stop_subtree_search(),
+        }
+        self.render_tree_nodes();
+        self.set_dir_cursor(0);
+    }
+
+    pub fn start_subtree_search(&mut self) {
+        self.stop_subtree_search();
+        self.subtree_search_generation += 1;
+        self.subtree_search_running = true;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.subtree_search_cancelled = cancelled.clone();
+
+        let generation = self.subtree_search_generation;
+        let root = PathBuf::from(self.get_current_string_path());
+        let entry_filter = self.entry_filter.clone();
+        let with_details = self.long_listing;
+        let result_tx = self.background_event_channel.tx.clone();
+        std::thread::spawn(move || {
+            walk_subtree(
+                &root,
+                &entry_filter,
+                with_details,
+                &cancelled,
+                |entries: Vec<SubtreeEntry>| {
+                    result_tx
+                        .send(BackgroundEvent::SubtreeEntriesFound {
+                            generation,
+                            entries,
+                        })
+                        .is_ok()
+                },
+            );
+            result_tx
+                .send(BackgroundEvent::SubtreeSearchFinished { generation })
+                .ok();
+        });
+    }
+
+    pub fn stop_subtree_search(&mut self) {
+        self.subtree_search_cancelled.store(true, Ordering::Relaxed);
+        self.subtree_search_running = false;
+        self.subtree_entries.clear();
+    }
+
+    pub fn on_subtree_entries_found(&mut self, generation: u64, mut entries: Vec<SubtreeEntry>) {
+        if generation != self.subtree_search_generation || !self.recursive_search {
+            return;
+        }
+        let capacity = MAX_SUBTREE_ENTRIES.saturating_sub(self.subtree_entries.len());
+        if entries.len() >= capacity {
+            entries.truncate(capacity);
+            self.subtree_search_cancelled.store(true, Ordering::Relaxed);
+            self.subtree_search_running = false;
+        }
+        if entries.is_empty() {
+            return;
+        }
+        let selected_path: Option<String> = self.get_selected_abs_path();
+        let ranked_nodes = std::mem::take(&mut self.child_tree_nodes);
+        self.child_tree_nodes = merge_subtree_nodes(
+            ranked_nodes,
+            &entries,
+            self.get_name_filter(),
+            self.match_mode,
+            MAX_DISPLAYED_SUBTREE_MATCHES,
+        );
+        self.subtree_entries.extend(entries);
+        self.refresh_marked_nodes();
+        self.reset_cursor_offset();
+        if let Some(selected_path) = selected_path {
+            let new_cursor = self
+                .child_tree_nodes
+                .iter()
+                .position(|node| self.build_tree_node_path(node) == selected_path);
+            self.set_dir_cursor(new_cursor.unwrap_or(0));
+        }
+    }
+
+    pub fn on_subtree_search_finished(&mut self, generation: u64) {
+        if generation == self.subtree_search_generation {
+            self.subtree_search_running = false;
+        }
+    }
+
+    pub fn render_subtree_tree_nodes(&self) -> Vec<TreeNode> {
+        render_subtree_nodes(
+            &self.subtree_entries,
+            self.get_name_filter(),
+            self.match_mode,
+            MAX_DISPLAYED_SUBTREE_MATCHES,
+        )
+    }
+
+    /// Leaves the recursive search and opens the directory of the found entry.
+    /// Directories are entered, while files are selected in their parent directory.
+    pub fn go_into_subtree_entry(&mut self, entry: &SubtreeEntry) {
+        let file_node: &FileNode = entry.file_node();
+        let mut dir_route: Vec<FileNode> = entry.route.clone();
+        if !file_node.is_directory {
+            dir_route.pop();
+        }
+        self.remember_location();
+        self.recursive_search = false;
+        self.stop_subtree_search();
+        self.parent_file_nodes.extend(dir_route);
+        self.record_history_visit(self.get_current_string_path());
+        self.filter_text.clear();
+        self.populate_current_child_nodes();
+        self.reset_cursor_offset();
+        let new_cursor = match file_node.is_directory {
+            true => None,
+            false => self
+                .child_tree_nodes
+                .iter()
+                .position(|node| node.name() == file_node.name),
+        };
+        self.set_dir_cursor(new_cursor.unwrap_or(0));
+    }
+
+    pub fn get_subtree_search_status(&self) -> Option<String> {
+        if !self.recursive_search {
+            return None;
+        }
+        let matches_count = self
+            .child_tree_nodes
+            .iter()
+            .filter(|node| matches!(node.kind, TreeNodeType::SubtreeNode(_)))
+            .count();
+        let limit_reached = self.subtree_entries.len() >= MAX_SUBTREE_ENTRIES;
+        let status = match (self.subtree_search_running, limit_reached) {
+            (true, _) => "scanning…",
+            (false, true) => "limit reached",
+            (false, false) => "done",
+        };
+        Some(format!(
+            "recursive: {} of {}{} entries, {}",
+            matches_count,
+            self.subtree_entries.len(),
+            if limit_reached { "+" } else { "" },
+            status
+        ))
+    }
+}
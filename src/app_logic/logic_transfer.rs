@@ -0,0 +1,206 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::action_menu::Operation;
+use crate::app::App;
+use crate::appdata::WindowFocus;
+use crate::background::BackgroundEvent;
+use crate::errors::contextualized_error;
+use crate::filesystem::get_string_abs_path;
+use crate::transfer::{
+    measure_transfer, path_exists, plan_transfer, run_transfer, suggest_free_name, PendingTransfer,
+    TransferMode, TransferOutcome, TransferProgress, TransferRegister,
+};
+use crate::trash::select_trash_dir;
+use crate::undo::UndoRecord;
+
+impl App {
+    /// Registers the marked entries (or the selected one) to be copied or moved on paste.
+    pub fn register_transfer(&mut self, mode: TransferMode) {
+        let paths: Vec<String> = match self.marked_routes.is_empty() {
+            true => match self.get_selected_abs_path() {
+                Some(abs_path) => vec![abs_path],
+                None => return,
+            },
+            false => self.marked_routes.iter().map(get_string_abs_path).collect(),
+        };
+        self.transfer_register = Some(TransferRegister { mode, paths });
+        self.marked_routes.clear();
+        self.populate_current_child_nodes();
+    }
+
+    /// Pastes the registered paths into the current directory,
+    /// asking first what to do with every name that's already taken.
+    pub fn paste_transfer_register(&mut self) {
+        if self.transfer_status.is_some() {
+            self.show_error("Wait until the running transfer finishes".to_string());
+            return;
+        }
+        let register: TransferRegister = match self.transfer_register.clone() {
+            Some(register) => register,
+            None => {
+                self.show_error("Nothing to paste, yank or cut files first".to_string());
+                return;
+            }
+        };
+        let target_dir: String = self.get_current_dir_abs_path();
+        let mut items = match plan_transfer(&register.paths, &target_dir) {
+            Ok(items) => items,
+            Err(e) => {
+                self.show_error(contextualized_error(&e));
+                return;
+            }
+        };
+        if register.mode == TransferMode::Move {
+            items.retain(|item| item.source != item.target); // already in place
+        }
+        self.pending_transfer = Some(PendingTransfer {
+            mode: register.mode,
+            target_dir,
+            items,
+            conflict_index: 0,
+        });
+        self.ask_next_paste_conflict();
+    }
+
+    fn ask_next_paste_conflict(&mut self) {
+        let pending: &mut PendingTransfer = match self.pending_transfer.as_mut() {
+            Some(pending) => pending,
+            None => return,
+        };
+        let conflict = pending.items[pending.conflict_index..]
+            .iter()
+            .position(|item| !item.overwrite && path_exists(&item.target));
+        match conflict {
+            Some(offset) => {
+                pending.conflict_index += offset;
+                let item = &pending.items[pending.conflict_index];
+                let name: String = item.target.rsplit('/').next().unwrap_or("").to_string();
+                let free_name = suggest_free_name(&pending.target_dir, &name);
+                self.action_menu_operation = Some(Operation::ResolvePasteConflict);
+                self.open_action_menu_step2(
+                    format!(
+                        "\"{}\" already exists. Paste as a new name, \"overwrite\" or \"skip\"?",
+                        name
+                    ),
+                    free_name,
+                );
+            }
+            None => {
+                let pending = self.pending_transfer.take().unwrap();
+                self.start_transfer(pending);
+            }
+        }
+    }
+
+    pub fn resolve_paste_conflict(&mut self, answer: String) {
+        self.window_focus = WindowFocus::Tree;
+        let pending: &mut PendingTransfer = match self.pending_transfer.as_mut() {
+            Some(pending) => pending,
+            None => return,
+        };
+        let index = pending.conflict_index;
+        match answer.as_str() {
+            "" => {
+                self.pending_transfer = None;
+                self.show_error("Paste aborted".to_string());
+                return;
+            }
+            "skip" => {
+                pending.items.remove(index);
+            }
+            "overwrite" => {
+                // the replaced target goes to the trash, better find out now if it can't
+                let target: String = pending.items[index].target.clone();
+                match self
+                    .trash_dir()
+                    .and_then(|trash_dir| select_trash_dir(&trash_dir, &target))
+                {
+                    Ok(_) => self.pending_transfer.as_mut().unwrap().items[index].overwrite = true,
+                    Err(e) => self.show_error(contextualized_error(&e)),
+                }
+            }
+            name if name.contains('/') || name == "." || name == ".." => {
+                self.show_error(format!("Invalid file name: {}", name));
+            }
+            name => {
+                let target = Path::new(&pending.target_dir).join(name);
+                pending.items[index].target = target.to_string_lossy().to_string();
+            }
+        }
+        self.ask_next_paste_conflict();
+    }
+
+    fn start_transfer(&mut self, pending: PendingTransfer) {
+        if pending.items.is_empty() {
+            return;
+        }
+        let mode = pending.mode;
+        let trash_dir: Option<PathBuf> = self.trash_dir().ok();
+        self.transfer_status = Some((mode, TransferProgress::default()));
+        let result_tx = self.background_event_channel.tx.clone();
+        std::thread::spawn(move || {
+            let mut progress = measure_transfer(&pending.items);
+            result_tx
+                .send(BackgroundEvent::TransferProgress(progress))
+                .ok();
+            let mut last_sent = Instant::now();
+            let mut on_progress = |progress: &TransferProgress| {
+                if last_sent.elapsed() >= Duration::from_millis(100) {
+                    result_tx
+                        .send(BackgroundEvent::TransferProgress(*progress))
+                        .ok();
+                    last_sent = Instant::now();
+                }
+            };
+            let outcome = run_transfer(
+                &pending.items,
+                mode,
+                trash_dir.as_deref(),
+                &mut progress,
+                &mut on_progress,
+            );
+            result_tx
+                .send(BackgroundEvent::TransferFinished { mode, outcome })
+                .ok();
+        });
+    }
+
+    pub fn on_transfer_progress(&mut self, progress: TransferProgress) {
+        if let Some((_, status_progress)) = self.transfer_status.as_mut() {
+            *status_progress = progress;
+        }
+    }
+
+    pub fn on_transfer_finished(&mut self, mode: TransferMode, outcome: TransferOutcome) {
+        self.transfer_status = None;
+        if mode == TransferMode::Move {
+            self.transfer_register = None; // sources are gone
+        }
+        if !outcome.done_items.is_empty() {
+            self.record_undo(UndoRecord::Transfer {
+                mode,
+                items: outcome.done_items,
+                replaced: outcome.replaced,
+            });
+        }
+        self.populate_current_child_nodes();
+        if !outcome.errors.is_empty() {
+            self.show_error(outcome.errors.join("\n"));
+        }
+    }
+
+    pub fn get_transfer_status(&self) -> Option<String> {
+        if let Some((mode, progress)) = &self.transfer_status {
+            return Some(format!(
+                "{} {}/{} files, {}%",
+                mode.progress_verb(),
+                progress.done_files,
+                progress.total_files,
+                progress.percent()
+            ));
+        }
+        let register = self.transfer_register.as_ref()?;
+        Some(format!("{} {}", register.paths.len(), register.mode.name()))
+    }
+}
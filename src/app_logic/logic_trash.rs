@@ -0,0 +1,76 @@
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+
+use crate::app::App;
+use crate::appdata::WindowFocus;
+use crate::errors::contextualized_error;
+use crate::trash::{
+    default_trash_dir, list_all_trash, restore_trash_entry, trash_path, TrashEntry,
+};
+use crate::tree::{TreeNode, TreeNodeType};
+use crate::undo::UndoRecord;
+
+impl App {
+    pub fn trash_dir(&self) -> Result<PathBuf> {
+        default_trash_dir()
+            .ok_or_else(|| anyhow!("can't locate trash: neither XDG_DATA_HOME nor HOME is set"))
+    }
+
+    pub fn trash_selected_node(&mut self) {
+        let tree_node: TreeNode = match self.get_selected_tree_node() {
+            Some(tree_node) => tree_node,
+            None => return,
+        };
+        if tree_node.kind == TreeNodeType::SelfReference {
+            self.show_error("Can't move the current directory to trash".to_string());
+            return;
+        }
+        let abs_path: String = match self.get_selected_abs_path() {
+            Some(abs_path) => abs_path,
+            None => return,
+        };
+        let result = self
+            .trash_dir()
+            .and_then(|trash_dir| trash_path(&trash_dir, &abs_path));
+        match result {
+            Ok(entry) => self.record_undo(UndoRecord::Trash { entry }),
+            Err(e) => self.show_error(contextualized_error(&e)),
+        }
+        self.populate_current_child_nodes();
+    }
+
+    pub fn open_trash_dialog(&mut self) {
+        let entries = self
+            .trash_dir()
+            .and_then(|trash_dir| list_all_trash(&trash_dir));
+        match entries {
+            Ok(entries) => self.trash_entries = entries,
+            Err(e) => {
+                self.show_error(contextualized_error(&e));
+                return;
+            }
+        }
+        self.trash_cursor = 0;
+        self.window_focus = WindowFocus::Trash;
+    }
+
+    pub fn close_trash_dialog(&mut self) {
+        self.window_focus = WindowFocus::Tree;
+    }
+
+    pub fn restore_selected_trash_entry(&mut self) {
+        let entry: TrashEntry = match self.trash_entries.get(self.trash_cursor) {
+            Some(entry) => entry.clone(),
+            None => return,
+        };
+        if let Err(e) = restore_trash_entry(&entry) {
+            self.show_error(contextualized_error(&e));
+            return;
+        }
+        self.trash_entries.remove(self.trash_cursor);
+        self.trash_cursor = self
+            .trash_cursor
+            .min(self.trash_entries.len().saturating_sub(1));
+        self.populate_current_child_nodes();
+    }
+}
@@ -0,0 +1,36 @@
+use crate::app::App;
+use crate::errors::contextualized_error;
+use crate::undo::{revert_change, UndoRecord, UNDO_JOURNAL_LIMIT};
+
+impl App {
+    pub fn record_undo(&mut self, record: UndoRecord) {
+        self.undo_journal.push(record);
+        if self.undo_journal.len() > UNDO_JOURNAL_LIMIT {
+            self.undo_journal.remove(0);
+        }
+    }
+
+    /// Reverts the latest recorded change.
+    /// A change that can't be reverted is dropped from the journal.
+    pub fn undo_last_change(&mut self) {
+        if self.transfer_status.is_some() {
+            self.show_error("Wait until the running transfer finishes".to_string());
+            return;
+        }
+        let record: UndoRecord = match self.undo_journal.pop() {
+            Some(record) => record,
+            None => {
+                self.show_error("Nothing to undo".to_string());
+                return;
+            }
+        };
+        let result = self
+            .trash_dir()
+            .and_then(|trash_dir| revert_change(&record, &trash_dir));
+        match result {
+            Ok(message) => self.show_info(message),
+            Err(e) => self.show_error(contextualized_error(&e)),
+        }
+        self.populate_current_child_nodes();
+    }
+}
@@ -0,0 +1,144 @@
+use crate::app::App;
+use crate::appdata::WindowFocus;
+use crate::background::BackgroundEvent;
+use crate::errors::contextualized_error;
+use crate::viewer::{FileViewer, ViewerChunk, ViewerPrompt, ViewerReader};
+
+impl App {
+    pub fn open_viewer(&mut self, path: String) {
+        let reader =
+            match ViewerReader::open(&path, self.view_max_bytes, self.highlight_settings.clone()) {
+                Ok(reader) => reader,
+                Err(e) => {
+                    self.show_error(contextualized_error(&e));
+                    return;
+                }
+            };
+        self.viewer_generation += 1;
+        self.viewer = Some(FileViewer::new(path, reader));
+        self.window_focus = WindowFocus::Viewer;
+        self.request_viewer_chunk();
+    }
+
+    pub fn close_viewer(&mut self) {
+        self.viewer = None;
+        self.window_focus = WindowFocus::Tree;
+    }
+
+    /// Reads the next chunk of the file in the background when the viewer is close to its end.
+    pub fn request_viewer_chunk(&mut self) {
+        let viewer: &mut FileViewer = match self.viewer.as_mut() {
+            Some(viewer) => viewer,
+            None => return,
+        };
+        if !viewer.needs_more_lines() {
+            return;
+        }
+        viewer.loading = true;
+        let reader = viewer.reader.clone();
+        let generation = self.viewer_generation;
+        let result_tx = self.background_event_channel.tx.clone();
+        std::thread::spawn(move || {
+            let result = match reader.lock() {
+                Ok(mut reader) => reader.read_chunk().map_err(|e| contextualized_error(&e)),
+                Err(_) => Err("file reader is poisoned".to_string()),
+            };
+            result_tx
+                .send(BackgroundEvent::ViewerChunkLoaded { generation, result })
+                .ok();
+        });
+    }
+
+    pub fn on_viewer_chunk_loaded(&mut self, generation: u64, result: Result<ViewerChunk, String>) {
+        if generation != self.viewer_generation {
+            return;
+        }
+        let viewer: &mut FileViewer = match self.viewer.as_mut() {
+            Some(viewer) => viewer,
+            None => return,
+        };
+        match result {
+            Ok(chunk) => viewer.append_chunk(chunk),
+            Err(message) => {
+                viewer.loading = false;
+                viewer.eof = true;
+                viewer.pending_jump = None;
+                self.show_error(message);
+                return;
+            }
+        }
+        self.request_viewer_chunk();
+    }
+
+    fn with_viewer<F: FnOnce(&mut FileViewer)>(&mut self, action: F) {
+        if let Some(viewer) = self.viewer.as_mut() {
+            viewer.message = None;
+            action(viewer);
+        }
+        self.request_viewer_chunk();
+    }
+
+    pub fn scroll_viewer(&mut self, delta: i64) {
+        self.with_viewer(|viewer| viewer.scroll_by(delta));
+    }
+
+    pub fn scroll_viewer_page(&mut self, pages: i64) {
+        self.with_viewer(|viewer| viewer.scroll_by(pages * viewer.page_height.max(1) as i64));
+    }
+
+    pub fn scroll_viewer_horizontally(&mut self, delta: i64) {
+        self.with_viewer(|viewer| viewer.scroll_x_by(delta));
+    }
+
+    pub fn viewer_go_to_top(&mut self) {
+        self.with_viewer(|viewer| viewer.go_to_line(0));
+    }
+
+    pub fn viewer_go_to_bottom(&mut self) {
+        self.with_viewer(|viewer| viewer.go_to_line(usize::MAX));
+    }
+
+    pub fn viewer_search_next(&mut self, forward: bool) {
+        self.with_viewer(|viewer| viewer.search_next(forward));
+    }
+
+    pub fn open_viewer_prompt(&mut self, prompt: ViewerPrompt) {
+        self.with_viewer(|viewer| {
+            viewer.prompt = Some(prompt);
+            viewer.prompt_text.clear();
+        });
+    }
+
+    pub fn is_viewer_prompt_open(&self) -> bool {
+        self.viewer.as_ref().is_some_and(|it| it.prompt.is_some())
+    }
+
+    pub fn close_viewer_prompt(&mut self) {
+        self.with_viewer(|viewer| viewer.prompt = None);
+    }
+
+    pub fn type_viewer_prompt(&mut self, c: char) {
+        self.with_viewer(|viewer| viewer.prompt_text.push(c));
+    }
+
+    pub fn backspace_viewer_prompt(&mut self) {
+        self.with_viewer(|viewer| {
+            viewer.prompt_text.pop();
+        });
+    }
+
+    pub fn submit_viewer_prompt(&mut self) {
+        self.with_viewer(|viewer| {
+            let text = std::mem::take(&mut viewer.prompt_text);
+            match viewer.prompt.take() {
+                Some(ViewerPrompt::Search) if !text.is_empty() => viewer.search(text),
+                Some(ViewerPrompt::Search) => viewer.search_next(true), // repeat the last search
+                Some(ViewerPrompt::GotoLine) => match text.trim().parse::<usize>() {
+                    Ok(line_number) => viewer.go_to_line(line_number.saturating_sub(1)),
+                    Err(_) => viewer.message = Some(format!("Invalid line number: {}", text)),
+                },
+                None => {}
+            }
+        });
+    }
+}
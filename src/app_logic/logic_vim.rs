@@ -0,0 +1,50 @@
+use crate::app::App;
+use crate::vim::{parse_vim_keys, VimKeys, VimMode};
+
+impl App {
+    pub fn is_vim_normal_mode(&self) -> bool {
+        self.vim_mode && self.vim.mode == VimMode::Normal
+    }
+
+    pub fn enter_vim_insert_mode(&mut self) {
+        self.vim.mode = VimMode::Insert;
+        self.vim.pending_keys.clear();
+    }
+
+    pub fn enter_vim_normal_mode(&mut self) {
+        self.vim.mode = VimMode::Normal;
+        self.vim.pending_keys.clear();
+    }
+
+    pub fn has_vim_pending_keys(&self) -> bool {
+        !self.vim.pending_keys.is_empty()
+    }
+
+    pub fn clear_vim_pending_keys(&mut self) {
+        self.vim.pending_keys.clear();
+    }
+
+    /// Adds the key to the sequence typed in the normal mode.
+    /// The sequence is reset once it's complete or turns out unknown.
+    pub fn push_vim_key(&mut self, c: char) -> VimKeys {
+        self.vim.pending_keys.push(c);
+        let keys = parse_vim_keys(&self.vim.pending_keys);
+        if keys != VimKeys::Pending {
+            self.vim.pending_keys.clear();
+        }
+        keys
+    }
+
+    /// Moves the cursor to the entry with the given number, counting from 1.
+    pub fn go_to_entry_number(&mut self, number: usize) {
+        self.set_dir_cursor(number.saturating_sub(1));
+    }
+
+    pub fn get_vim_mode_indicator(&self) -> Option<String> {
+        if !self.vim_mode {
+            return None;
+        }
+        let indicator = format!("-- {} -- {}", self.vim.mode.name(), self.vim.pending_keys);
+        Some(indicator.trim_end().to_string())
+    }
+}
@@ -1,2 +1,17 @@
 mod app_logic;
 mod logic_action_menu;
+mod logic_bookmarks;
+mod logic_breadcrumbs;
+mod logic_entry_filter;
+mod logic_help;
+mod logic_history;
+mod logic_mouse;
+mod logic_navigation;
+mod logic_path_input;
+mod logic_preview;
+mod logic_recursive_search;
+mod logic_transfer;
+mod logic_trash;
+mod logic_undo;
+mod logic_viewer;
+mod logic_vim;
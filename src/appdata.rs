@@ -1,8 +1,16 @@
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+use crate::filesystem::FileNode;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub enum WindowFocus {
     Tree,
     ActionMenu,
     ActionMenuStep2,
+    Bookmarks,
+    History,
+    Viewer,
+    Trash,
+    Breadcrumbs,
+    Help,
 }
 
 impl Default for WindowFocus {
@@ -10,3 +18,19 @@ impl Default for WindowFocus {
         WindowFocus::Tree
     }
 }
+
+/// Visited location for going back and forward.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigationEntry {
+    pub parent_file_nodes: Vec<FileNode>,
+    pub dir_cursor: usize,
+    pub filter_text: String,
+}
+
+/// Position in a directory, restored when the directory is entered again.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirectoryState {
+    pub dir_cursor: usize,
+    pub scroll_offset: usize,
+    pub filter_text: String,
+}
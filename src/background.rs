@@ -1,27 +1,64 @@
 use crate::app::App;
+use crate::filesystem::SubtreeEntry;
+use crate::preview::Preview;
+use crate::transfer::{TransferMode, TransferOutcome, TransferProgress};
+use crate::viewer::ViewerChunk;
 
 #[derive(Clone, Debug)]
 pub enum BackgroundEvent {
     InfoMessage(String),
     ErrorMessage(String),
+    SubtreeEntriesFound {
+        generation: u64,
+        entries: Vec<SubtreeEntry>,
+    },
+    SubtreeSearchFinished {
+        generation: u64,
+    },
+    PreviewLoaded(Preview),
+    ViewerChunkLoaded {
+        generation: u64,
+        result: Result<ViewerChunk, String>,
+    },
+    TransferProgress(TransferProgress),
+    TransferFinished {
+        mode: TransferMode,
+        outcome: TransferOutcome,
+    },
 }
 
 impl App {
     pub fn check_background_events(&mut self) {
-        self.background_event_channel
-            .rx
-            .try_recv()
-            .ok()
-            .map(|event: BackgroundEvent| {
-                self.consume_background_event(event);
-            });
+        while let Ok(event) = self.background_event_channel.rx.try_recv() {
+            self.consume_background_event(event);
+        }
     }
 
     fn consume_background_event(&mut self, event: BackgroundEvent) {
         match event {
-            BackgroundEvent::InfoMessage(message) => self.show_info(message),
-            BackgroundEvent::ErrorMessage(message) => self.show_error(message),
+            BackgroundEvent::InfoMessage(message) => {
+                self.show_info(message);
+                self.refresh_visible_nodes();
+            }
+            BackgroundEvent::ErrorMessage(message) => {
+                self.show_error(message);
+                self.refresh_visible_nodes();
+            }
+            BackgroundEvent::SubtreeEntriesFound {
+                generation,
+                entries,
+            } => self.on_subtree_entries_found(generation, entries),
+            BackgroundEvent::SubtreeSearchFinished { generation } => {
+                self.on_subtree_search_finished(generation)
+            }
+            BackgroundEvent::PreviewLoaded(preview) => self.on_preview_loaded(preview),
+            BackgroundEvent::ViewerChunkLoaded { generation, result } => {
+                self.on_viewer_chunk_loaded(generation, result)
+            }
+            BackgroundEvent::TransferProgress(progress) => self.on_transfer_progress(progress),
+            BackgroundEvent::TransferFinished { mode, outcome } => {
+                self.on_transfer_finished(mode, outcome)
+            }
         };
-        self.populate_current_child_nodes();
     }
 }
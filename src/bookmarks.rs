@@ -0,0 +1,82 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::fuzzy::{match_words, MatchMode};
+use crate::storage::{data_dir, load_data_file, save_data_file};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BookmarksFile {
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+}
+
+/// Bookmark matching the filter, with positions of matched characters in its name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookmarkMatch {
+    pub index: usize,
+    pub name_indices: Vec<usize>,
+}
+
+pub fn default_bookmarks_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("bookmarks.toml"))
+}
+
+pub fn load_bookmarks(path: &Path) -> Result<Vec<Bookmark>> {
+    let file: BookmarksFile = load_data_file(path)?;
+    Ok(file.bookmarks)
+}
+
+pub fn save_bookmarks(path: &Path, bookmarks: &[Bookmark]) -> Result<()> {
+    let file = BookmarksFile {
+        bookmarks: bookmarks.to_vec(),
+    };
+    save_data_file(path, &file)
+}
+
+/// Adds a bookmark, replacing the one with the same name.
+pub fn upsert_bookmark(bookmarks: &mut Vec<Bookmark>, bookmark: Bookmark) {
+    match bookmarks.iter_mut().find(|it| it.name == bookmark.name) {
+        Some(existing) => existing.path = bookmark.path,
+        None => bookmarks.push(bookmark),
+    }
+}
+
+/// Matches bookmarks by name or path, the best matches first.
+pub fn filter_bookmarks(
+    bookmarks: &[Bookmark],
+    filter_text: &str,
+    match_mode: MatchMode,
+) -> Vec<BookmarkMatch> {
+    let filter_words: Vec<String> = filter_text
+        .to_lowercase()
+        .split_whitespace()
+        .map(|it| it.to_string())
+        .collect();
+    let mut matches: Vec<(i32, BookmarkMatch)> = bookmarks
+        .iter()
+        .enumerate()
+        .filter_map(|(index, bookmark)| {
+            let name_match = match_words(&bookmark.name, &filter_words, match_mode);
+            let text_match = name_match
+                .clone()
+                .or_else(|| match_words(&bookmark.path, &filter_words, match_mode))?;
+            let name_indices = name_match.map(|it| it.indices).unwrap_or_default();
+            Some((
+                text_match.score,
+                BookmarkMatch {
+                    index,
+                    name_indices,
+                },
+            ))
+        })
+        .collect();
+    matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    matches.into_iter().map(|(_, it)| it).collect()
+}
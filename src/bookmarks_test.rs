@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+
+    use crate::bookmarks::{
+        filter_bookmarks, load_bookmarks, save_bookmarks, upsert_bookmark, Bookmark,
+    };
+    use crate::fuzzy::MatchMode;
+    use crate::testing::TempDir;
+
+    fn bookmark(name: &str, path: &str) -> Bookmark {
+        Bookmark {
+            name: name.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_bookmarks() {
+        let dir = TempDir::new("bookmarks");
+        let path = dir.join("nested/bookmarks.toml");
+        assert_eq!(load_bookmarks(&path).unwrap(), vec![]);
+
+        let bookmarks = vec![
+            bookmark("projects", "/home/user/projects"),
+            bookmark("with \"quotes\"", "/tmp/dir with space"),
+        ];
+        save_bookmarks(&path, &bookmarks).unwrap();
+        assert_eq!(load_bookmarks(&path).unwrap(), bookmarks);
+    }
+
+    #[test]
+    fn test_upsert_bookmark() {
+        let mut bookmarks = vec![bookmark("a", "/a"), bookmark("b", "/b")];
+        upsert_bookmark(&mut bookmarks, bookmark("a", "/new"));
+        upsert_bookmark(&mut bookmarks, bookmark("c", "/c"));
+        assert_eq!(
+            bookmarks,
+            vec![
+                bookmark("a", "/new"),
+                bookmark("b", "/b"),
+                bookmark("c", "/c")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filter_bookmarks() {
+        let bookmarks = vec![
+            bookmark("music", "/home/user/media/music"),
+            bookmark("projects", "/home/user/src"),
+            bookmark("config", "/etc"),
+        ];
+        let matches = filter_bookmarks(&bookmarks, "prj", MatchMode::Fuzzy);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].index, 1);
+        assert_eq!(matches[0].name_indices, vec![0, 1, 3]);
+
+        let by_path = filter_bookmarks(&bookmarks, "media", MatchMode::Fuzzy);
+        assert_eq!(by_path.len(), 1);
+        assert_eq!(by_path[0].index, 0);
+        assert!(by_path[0].name_indices.is_empty());
+
+        assert_eq!(filter_bookmarks(&bookmarks, "", MatchMode::Fuzzy).len(), 3);
+    }
+}
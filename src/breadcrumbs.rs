@@ -0,0 +1,115 @@
+use ratatui::layout::{Position, Rect};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+const ROOT_LABEL: &str = "/";
+const SEPARATOR: &str = "/";
+const COLLAPSED_LABEL: &str = "…";
+
+/// Piece of the breadcrumb bar. Segments lead to the ancestor with `depth` path nodes (0 is the root),
+/// separators and collapsed segments have no depth.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreadcrumbPart {
+    pub text: String,
+    pub depth: Option<usize>,
+}
+
+/// Where a segment was last rendered, for mouse clicks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreadcrumbArea {
+    pub area: Rect,
+    pub depth: usize,
+}
+
+pub fn breadcrumb_at(areas: &[BreadcrumbArea], column: u16, row: u16) -> Option<usize> {
+    areas
+        .iter()
+        .find(|it| it.area.contains(Position::new(column, row)))
+        .map(|it| it.depth)
+}
+
+/// Lays out the path from the root to the current directory within `max_width` terminal columns.
+/// The root, the current directory and the selected segment are always shown,
+/// then segments closest to the current directory. Omitted segments collapse to `…`.
+pub fn layout_breadcrumbs(
+    names: &[String],
+    selected: Option<usize>,
+    max_width: usize,
+) -> Vec<BreadcrumbPart> {
+    let depth_count = names.len() + 1;
+    let mut visible: Vec<bool> = vec![false; depth_count];
+    visible[0] = true;
+    visible[names.len()] = true;
+    if let Some(selected) = selected.filter(|it| *it < depth_count) {
+        visible[selected] = true;
+    }
+    for depth in (1..names.len()).rev() {
+        if visible[depth] {
+            continue;
+        }
+        visible[depth] = true;
+        if parts_width(&build_parts(names, &visible)) > max_width {
+            visible[depth] = false;
+        }
+    }
+    let mut parts = build_parts(names, &visible);
+    let overflow = parts_width(&parts).saturating_sub(max_width);
+    if overflow > 0 && !names.is_empty() {
+        // even the current directory alone doesn't fit, cut off the beginning of its name
+        let last = parts.last_mut().unwrap();
+        let keep_width = last
+            .text
+            .width()
+            .saturating_sub(overflow + COLLAPSED_LABEL.width());
+        last.text = format!("{}{}", COLLAPSED_LABEL, text_tail(&last.text, keep_width));
+    }
+    parts
+}
+
+fn build_parts(names: &[String], visible: &[bool]) -> Vec<BreadcrumbPart> {
+    let mut parts = vec![BreadcrumbPart {
+        text: ROOT_LABEL.to_string(),
+        depth: Some(0),
+    }];
+    for (index, name) in names.iter().enumerate() {
+        let depth = index + 1;
+        if !visible[depth] && !visible[depth - 1] {
+            continue; // already collapsed with the previous segment
+        }
+        if depth > 1 {
+            parts.push(BreadcrumbPart {
+                text: SEPARATOR.to_string(),
+                depth: None,
+            });
+        }
+        parts.push(match visible[depth] {
+            true => BreadcrumbPart {
+                text: name.clone(),
+                depth: Some(depth),
+            },
+            false => BreadcrumbPart {
+                text: COLLAPSED_LABEL.to_string(),
+                depth: None,
+            },
+        });
+    }
+    parts
+}
+
+/// End of the text that fits in `max_width` columns, but at least its last character.
+fn text_tail(text: &str, max_width: usize) -> &str {
+    let mut start = text.len();
+    let mut width = 0;
+    for (index, c) in text.char_indices().rev() {
+        width += c.width().unwrap_or(0);
+        if width > max_width && start < text.len() {
+            break;
+        }
+        start = index;
+    }
+    &text[start..]
+}
+
+/// Columns taken by the parts in the terminal, where wide characters take two.
+pub fn parts_width(parts: &[BreadcrumbPart]) -> usize {
+    parts.iter().map(|part| part.text.width()).sum()
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::layout::Rect;
+    use ratatui::Terminal;
+
+    use crate::app::App;
+    use crate::breadcrumbs::{
+        breadcrumb_at, layout_breadcrumbs, parts_width, BreadcrumbArea, BreadcrumbPart,
+    };
+    use crate::filesystem::{FileNode, FileType};
+    use crate::ui;
+
+    fn names(path: &str) -> Vec<String> {
+        path.split('/').map(|it| it.to_string()).collect()
+    }
+
+    fn dir_nodes(path: &str) -> Vec<FileNode> {
+        names(path)
+            .into_iter()
+            .map(|name| FileNode {
+                lowercase_name: name.to_lowercase(),
+                name,
+                file_type: FileType::Directory,
+                is_symlink: false,
+                is_directory: true,
+                details: None,
+            })
+            .collect()
+    }
+
+    fn render(parts: &[BreadcrumbPart]) -> String {
+        parts.iter().map(|it| it.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_layout_breadcrumbs() {
+        let path = names("home/user/projects/fpick");
+        let parts = layout_breadcrumbs(&path, None, 100);
+        assert_eq!(render(&parts), "/home/user/projects/fpick");
+        let depths: Vec<usize> = parts.iter().filter_map(|it| it.depth).collect();
+        assert_eq!(depths, vec![0, 1, 2, 3, 4]);
+
+        let parts = layout_breadcrumbs(&path, None, 20);
+        assert_eq!(render(&parts), "/…/projects/fpick");
+        assert!(parts_width(&parts) <= 20);
+
+        let parts = layout_breadcrumbs(&path, Some(1), 16);
+        assert_eq!(render(&parts), "/home/…/fpick");
+
+        assert_eq!(render(&layout_breadcrumbs(&[], None, 10)), "/");
+    }
+
+    #[test]
+    fn test_layout_breadcrumbs_truncates_long_name() {
+        let path = names("home/a_very_long_directory_name");
+        let parts = layout_breadcrumbs(&path, None, 12);
+        assert_eq!(render(&parts), "/…/…ory_name");
+        assert_eq!(parts_width(&parts), 12);
+        assert_eq!(parts.last().unwrap().depth, Some(2));
+    }
+
+    #[test]
+    fn test_layout_breadcrumbs_with_wide_characters() {
+        let path = names("home/文档/项目目录");
+        let parts = layout_breadcrumbs(&path, None, 14);
+        assert_eq!(render(&parts), "/…/项目目录");
+        assert_eq!(parts_width(&parts), 11);
+
+        let parts = layout_breadcrumbs(&path, None, 8);
+        assert_eq!(render(&parts), "/…/…目录");
+        assert_eq!(parts_width(&parts), 8);
+    }
+
+    #[test]
+    fn test_breadcrumb_areas_with_wide_characters() {
+        let mut app = App::new();
+        app.parent_file_nodes = dir_nodes("home/文档/src");
+        let mut terminal = Terminal::new(TestBackend::new(60, 10)).unwrap();
+        terminal.draw(|frame| ui::render(&mut app, frame)).unwrap();
+
+        let area = app.breadcrumb_areas.last().unwrap().area;
+        assert_eq!(app.breadcrumb_areas.last().unwrap().depth, 3);
+        assert_eq!(area.x, 12); // after the border and "/home/文档/"
+        assert_eq!(area.width, 3);
+        assert_eq!(terminal.backend().buffer()[(area.x, area.y)].symbol(), "s");
+    }
+
+    #[test]
+    fn test_breadcrumb_at() {
+        let areas = vec![
+            BreadcrumbArea {
+                area: Rect::new(1, 0, 1, 1),
+                depth: 0,
+            },
+            BreadcrumbArea {
+                area: Rect::new(2, 0, 4, 1),
+                depth: 1,
+            },
+        ];
+        assert_eq!(breadcrumb_at(&areas, 1, 0), Some(0));
+        assert_eq!(breadcrumb_at(&areas, 5, 0), Some(1));
+        assert_eq!(breadcrumb_at(&areas, 6, 0), None);
+        assert_eq!(breadcrumb_at(&areas, 3, 1), None);
+    }
+
+    #[test]
+    fn test_breadcrumb_cursor() {
+        let mut app = App::new();
+        app.parent_file_nodes = dir_nodes("home/user");
+        app.open_breadcrumbs();
+        assert_eq!(app.get_selected_breadcrumb(), Some(1));
+        app.move_breadcrumb_cursor(-5);
+        assert_eq!(app.breadcrumb_cursor, 0);
+        app.move_breadcrumb_cursor(5);
+        assert_eq!(app.breadcrumb_cursor, 2);
+        app.close_breadcrumbs();
+        assert_eq!(app.get_selected_breadcrumb(), None);
+    }
+}
@@ -0,0 +1,208 @@
+use anyhow::{Context, Result};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::action_menu::UserActionConfig;
+use crate::fuzzy::MatchMode;
+use crate::sorting::SortMode;
+
+pub const DEFAULT_TICK_RATE_MS: u64 = 500;
+
+/// Settings read from `$XDG_CONFIG_HOME/fpick/config.toml`.
+/// Every key is optional, command-line options take precedence.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub path_mode: PathMode,
+    pub match_mode: Option<MatchMode>,
+    pub sort: Option<SortMode>,
+    pub sort_reverse: bool,
+    pub multi: bool,
+    pub null_separator: bool,
+    pub recursive: bool,
+    pub preview: bool,
+    pub preview_max_lines: Option<usize>,
+    pub view_max_bytes: Option<u64>,
+    pub syntax_highlighting: Option<bool>,
+    pub syntax_theme: Option<String>,
+    pub long_listing: bool,
+    pub record_history: Option<bool>,
+    pub hide_dotfiles: bool,
+    pub respect_ignore_files: bool,
+    pub vim_mode: bool,
+    pub tick_rate_ms: Option<u64>,
+    pub theme: ThemeConfig,
+    pub actions: Vec<UserActionConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathMode {
+    #[default]
+    Auto, // relative when the path is inside the starting directory, absolute otherwise
+    Relative,
+    Absolute,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub text: Option<String>,
+    pub directory: Option<String>,
+    pub symlink: Option<String>,
+    pub self_reference: Option<String>,
+    pub match_highlight: Option<String>,
+    pub marked: Option<String>,
+    pub subtree_prefix: Option<String>,
+    pub details: Option<String>,
+    pub search_text: Option<String>,
+    pub popup_background: Option<String>,
+    pub error_background: Option<String>,
+    pub info_background: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub text: Color,
+    pub directory: Color,
+    pub symlink: Color,
+    pub self_reference: Color,
+    pub match_highlight: Color,
+    pub marked: Color,
+    pub subtree_prefix: Color,
+    pub details: Color,
+    pub search_text: Color,
+    pub popup_background: Color,
+    pub error_background: Color,
+    pub info_background: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            text: Color::White,
+            directory: Color::LightBlue,
+            symlink: Color::LightCyan,
+            self_reference: Color::LightYellow,
+            match_highlight: Color::LightYellow,
+            marked: Color::LightMagenta,
+            subtree_prefix: Color::Gray,
+            details: Color::Gray,
+            search_text: Color::LightYellow,
+            popup_background: Color::DarkGray,
+            error_background: Color::Red,
+            info_background: Color::Blue,
+        }
+    }
+}
+
+impl ThemeConfig {
+    /// Resolves the colors, keeping the default of every invalid one and reporting it in `errors`.
+    pub fn resolve(&self, errors: &mut Vec<String>) -> Theme {
+        let default = Theme::default();
+        Theme {
+            text: parse_color("text", &self.text, default.text, errors),
+            directory: parse_color("directory", &self.directory, default.directory, errors),
+            symlink: parse_color("symlink", &self.symlink, default.symlink, errors),
+            self_reference: parse_color(
+                "self_reference",
+                &self.self_reference,
+                default.self_reference,
+                errors,
+            ),
+            match_highlight: parse_color(
+                "match_highlight",
+                &self.match_highlight,
+                default.match_highlight,
+                errors,
+            ),
+            marked: parse_color("marked", &self.marked, default.marked, errors),
+            subtree_prefix: parse_color(
+                "subtree_prefix",
+                &self.subtree_prefix,
+                default.subtree_prefix,
+                errors,
+            ),
+            details: parse_color("details", &self.details, default.details, errors),
+            search_text: parse_color(
+                "search_text",
+                &self.search_text,
+                default.search_text,
+                errors,
+            ),
+            popup_background: parse_color(
+                "popup_background",
+                &self.popup_background,
+                default.popup_background,
+                errors,
+            ),
+            error_background: parse_color(
+                "error_background",
+                &self.error_background,
+                default.error_background,
+                errors,
+            ),
+            info_background: parse_color(
+                "info_background",
+                &self.info_background,
+                default.info_background,
+                errors,
+            ),
+        }
+    }
+}
+
+fn parse_color(
+    key: &str,
+    value: &Option<String>,
+    default: Color,
+    errors: &mut Vec<String>,
+) -> Color {
+    match value {
+        None => default,
+        Some(value) => Color::from_str(value).unwrap_or_else(|_| {
+            errors.push(format!("invalid color '{}' for theme.{}", value, key));
+            default
+        }),
+    }
+}
+
+/// `$XDG_CONFIG_HOME/fpick`, usually `~/.config/fpick`.
+pub fn config_dir() -> Option<PathBuf> {
+    match std::env::var("XDG_CONFIG_HOME") {
+        Ok(config_home) if !config_home.is_empty() => {
+            Some(PathBuf::from(config_home).join("fpick"))
+        }
+        _ => std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".config/fpick")),
+    }
+}
+
+pub fn default_config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+pub fn default_keymap_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("keymap.toml"))
+}
+
+/// Reads the config file. A missing file at the default location is not an error,
+/// but a missing file given explicitly is.
+pub fn load_config(explicit_path: Option<&String>) -> Result<Config> {
+    let path: PathBuf = match explicit_path {
+        Some(path) => PathBuf::from(path),
+        None => match default_config_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        },
+    };
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read config file '{}'", path.to_string_lossy()))?;
+    let config: Config = toml::from_str(&content)
+        .with_context(|| format!("invalid config file '{}'", path.to_string_lossy()))?;
+    Ok(config)
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use ratatui::style::Color;
+    use std::fs;
+
+    use crate::app::App;
+    use crate::config::{load_config, PathMode, Theme};
+    use crate::fuzzy::MatchMode;
+    use crate::sorting::SortMode;
+    use crate::testing::{path_string, TempDir};
+
+    fn app_with_config(dir: &TempDir, content: &str) -> App {
+        let path = dir.join("config.toml");
+        fs::write(&path, content).unwrap();
+        let mut app = App::new();
+        app.config_path = Some(path_string(&path));
+        app
+    }
+
+    #[test]
+    fn test_load_config() {
+        let dir = TempDir::new("config-load");
+        let path = path_string(&dir.join("config.toml"));
+        fs::write(
+            &path,
+            "path_mode = \"absolute\"\nmatch_mode = \"exact\"\nsort = \"size\"\n[theme]\ntext = \"red\"\n",
+        )
+        .unwrap();
+        let config = load_config(Some(&path)).unwrap();
+        assert_eq!(config.path_mode, PathMode::Absolute);
+        assert_eq!(config.match_mode, Some(MatchMode::Substring));
+        assert_eq!(config.sort, Some(SortMode::Size));
+
+        fs::write(&path, "unknown_key = 1\n").unwrap();
+        assert!(load_config(Some(&path)).is_err());
+        fs::write(&path, "sort = [\n").unwrap();
+        assert!(load_config(Some(&path)).is_err());
+        let missing = path_string(&dir.join("missing.toml"));
+        assert!(load_config(Some(&missing)).is_err());
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let dir = TempDir::new("config-apply");
+        let mut app = app_with_config(
+            &dir,
+            r#"
+path_mode = "relative"
+sort = "mtime"
+tick_rate_ms = 1
+vim_mode = true
+[theme]
+directory = "green"
+[[actions]]
+name = "Blame"
+command = "git blame {}"
+"#,
+        );
+        app.absolute_path = true; // given in the command line
+        let actions_count = app.known_menu_actions.len();
+        assert_eq!(app.apply_config().unwrap(), Vec::<String>::new());
+        assert!(!app.relative_path);
+        assert_eq!(app.sort_order.mode, SortMode::Modified);
+        assert_eq!(app.tick_rate, 10);
+        assert!(app.vim_mode);
+        assert_eq!(app.theme.directory, Color::Green);
+        assert_eq!(app.theme.text, Theme::default().text);
+        assert_eq!(app.known_menu_actions.len(), actions_count + 1);
+    }
+
+    #[test]
+    fn test_command_line_sort_over_config() {
+        let dir = TempDir::new("config-sort");
+        let mut app = app_with_config(&dir, "sort = \"size\"\n");
+        app.cli_sort_mode = Some(SortMode::Name); // --sort name
+        app.apply_config().unwrap();
+        assert_eq!(app.sort_order.mode, SortMode::Name);
+
+        app.cli_sort_mode = None;
+        app.apply_config().unwrap();
+        assert_eq!(app.sort_order.mode, SortMode::Size);
+    }
+
+    #[test]
+    fn test_invalid_settings_dont_stop_valid_ones() {
+        let dir = TempDir::new("config-invalid");
+        let mut app = app_with_config(
+            &dir,
+            r##"
+syntax_theme = "no such theme"
+preview = true
+[theme]
+text = "not a color"
+directory = "green"
+symlink = "#zzzzzz"
+[[actions]]
+name = ""
+command = "true"
+[[actions]]
+name = "Valid"
+command = "true"
+"##,
+        );
+        let actions_count = app.known_menu_actions.len();
+        let errors = app.apply_config().unwrap();
+        assert_eq!(errors.len(), 4, "{:?}", errors);
+        assert!(errors[0].contains("theme.text"));
+        assert!(errors[1].contains("theme.symlink"));
+        assert!(errors[2].contains("action name must not be empty"));
+        assert!(errors[3].contains("no such theme"));
+        assert_eq!(app.theme.text, Theme::default().text);
+        assert_eq!(app.theme.directory, Color::Green);
+        assert!(app.preview_enabled);
+        assert_eq!(app.known_menu_actions.len(), actions_count + 1);
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 };
 
 use anyhow::Result;
-use crossterm::event::{self, Event as CrosstermEvent, KeyEvent};
+use crossterm::event::{self, Event as CrosstermEvent, KeyEvent, MouseEvent, MouseEventKind};
 
 /// Terminal events.
 #[derive(Clone, Copy, Debug)]
@@ -14,8 +14,12 @@ pub enum Event {
     Tick,
     /// Key press.
     Key(KeyEvent),
+    /// Mouse click or scroll.
+    Mouse(MouseEvent),
     /// Terminal resize.
     Resize,
+    /// Background event waiting to be handled.
+    Background,
 }
 
 /// Terminal event handler.
@@ -67,6 +71,16 @@ impl EventHandler {
                                 }
                             }
                         }
+                        // the capture reports every motion too, which would only cause redraws
+                        CrosstermEvent::Mouse(MouseEvent {
+                            kind: MouseEventKind::Moved | MouseEventKind::Drag(_),
+                            ..
+                        }) => {}
+                        CrosstermEvent::Mouse(e) if !Self::is_suspended(&suspended_store) => {
+                            sender
+                                .send(Event::Mouse(e))
+                                .expect("failed to send mouse event");
+                        }
                         CrosstermEvent::Resize(_, _) => {
                             sender
                                 .send(Event::Resize)
@@ -85,6 +99,10 @@ impl EventHandler {
         self
     }
 
+    pub fn waker(&self) -> mpsc::Sender<Event> {
+        self.sender.clone()
+    }
+
     pub fn next(&self) -> Result<Event> {
         Ok(self.receiver.recv()?)
     }
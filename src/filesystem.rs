@@ -1,6 +1,17 @@
 use anyhow::{Context, Result};
+use lazy_static::lazy_static;
+use std::collections::{HashMap, VecDeque};
 use std::fs::{self, DirEntry, Metadata, ReadDir};
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::ignore::EntryFilter;
+
+lazy_static! {
+    static ref USER_NAMES: HashMap<u32, String> = read_user_names();
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct FileNode {
@@ -9,6 +20,75 @@ pub struct FileNode {
     pub lowercase_name: String,
     pub is_symlink: bool,
     pub is_directory: bool,
+    pub details: Option<FileDetails>, // collected only when needed for sorting or long listing
+}
+
+/// Metadata of the target file (for symlinks, of the file they point to).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileDetails {
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub mode: u32, // permission bits
+    pub uid: u32,
+}
+
+impl FileDetails {
+    pub fn from_metadata(metadata: &Metadata) -> FileDetails {
+        FileDetails {
+            size: metadata.len(),
+            modified: metadata.modified().ok(),
+            mode: metadata.mode(),
+            uid: metadata.uid(),
+        }
+    }
+}
+
+/// Formats permission bits like `ls -l` does, e.g. `drwxr-xr-x`.
+pub fn format_mode(mode: u32, is_directory: bool, is_symlink: bool) -> String {
+    let type_char = match (is_symlink, is_directory) {
+        (true, _) => 'l',
+        (false, true) => 'd',
+        (false, false) => '-',
+    };
+    let mut text = String::from(type_char);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        text.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        text.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        text.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    text
+}
+
+/// Resolves a user name from `/etc/passwd`, falling back to the numeric id.
+pub fn owner_name(uid: u32) -> String {
+    match USER_NAMES.get(&uid) {
+        Some(name) => name.clone(),
+        None => uid.to_string(),
+    }
+}
+
+fn read_user_names() -> HashMap<u32, String> {
+    let content = fs::read_to_string("/etc/passwd").unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let uid: u32 = fields.nth(1)?.parse().ok()?;
+            Some((uid, name.to_string()))
+        })
+        .collect()
+}
+
+impl FileNode {
+    pub fn size(&self) -> u64 {
+        self.details.map(|details| details.size).unwrap_or(0)
+    }
+
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.details.and_then(|details| details.modified)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -18,7 +98,8 @@ pub enum FileType {
     Other,
 }
 
-pub fn list_files(dir_path: &Path) -> Result<Vec<FileNode>> {
+/// Lists the directory entries, reading their metadata only if `with_details` is set.
+pub fn list_files(dir_path: &Path, with_details: bool) -> Result<Vec<FileNode>> {
     let dir_entries: ReadDir = fs::read_dir(dir_path)
         .with_context(|| format!("failed to read directory '{}'", dir_path.to_string_lossy()))?;
 
@@ -33,13 +114,23 @@ pub fn list_files(dir_path: &Path) -> Result<Vec<FileNode>> {
             let is_symlink = file_type.is_symlink();
             let mut is_directory = file_type.is_dir();
 
+            let mut details: Option<FileDetails> = None;
             let resolved_file_type = if is_symlink {
                 let symlink_md: Metadata = fs::metadata(entry.path())
                     .context("failed to read symlink metadata")
                     .ok()?;
                 is_directory = symlink_md.is_dir();
+                if with_details {
+                    details = Some(FileDetails::from_metadata(&symlink_md));
+                }
                 symlink_md.file_type()
             } else {
+                if with_details {
+                    details = entry
+                        .metadata()
+                        .ok()
+                        .map(|md| FileDetails::from_metadata(&md));
+                }
                 file_type
             };
             let file_type = if is_directory {
@@ -57,6 +148,7 @@ pub fn list_files(dir_path: &Path) -> Result<Vec<FileNode>> {
                 lowercase_name,
                 is_symlink,
                 is_directory,
+                details,
             })
         })
         .collect();
@@ -103,6 +195,7 @@ pub fn get_path_file_nodes(path: &String) -> Result<Vec<FileNode>> {
                     lowercase_name,
                     is_symlink: false,
                     is_directory: false,
+                    details: None,
                 })
             }
         })
@@ -134,3 +227,90 @@ pub fn nodes_start_with(nodes: &Vec<FileNode>, start: &Vec<FileNode>) -> bool {
     }
     true
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtreeEntry {
+    pub route: Vec<FileNode>, // nodes leading from the search root to the entry
+    pub relative_path: String,
+    pub lowercase_path: String,
+}
+
+impl SubtreeEntry {
+    pub fn new(route: Vec<FileNode>) -> Self {
+        let relative_path = route
+            .iter()
+            .map(|node| node.name.as_str())
+            .collect::<Vec<&str>>()
+            .join("/");
+        let lowercase_path = relative_path.to_lowercase();
+        SubtreeEntry {
+            route,
+            relative_path,
+            lowercase_path,
+        }
+    }
+
+    pub fn file_node(&self) -> &FileNode {
+        self.route.last().unwrap()
+    }
+}
+
+/// Number of entries a recursive search collects at most,
+/// as all of them are ranked again on every typed character.
+pub const MAX_SUBTREE_ENTRIES: usize = 50_000;
+
+/// Walks the directory tree below `root` breadth-first, reporting found entries in batches.
+/// Entries rejected by the filter are skipped, ignore files are picked up on the way down.
+/// Symlinked directories are not followed to avoid cycles.
+/// File details are read only when `with_details` is set, as for `list_files`.
+/// Stops early when `cancelled` is set or when `on_batch` returns false.
+pub fn walk_subtree<F>(
+    root: &Path,
+    filter: &EntryFilter,
+    with_details: bool,
+    cancelled: &AtomicBool,
+    mut on_batch: F,
+) where
+    F: FnMut(Vec<SubtreeEntry>) -> bool,
+{
+    let mut dir_queue: VecDeque<(Vec<FileNode>, EntryFilter)> = VecDeque::new();
+    dir_queue.push_back((vec![], filter.clone()));
+    let mut batch: Vec<SubtreeEntry> = Vec::new();
+    let mut last_sent = Instant::now();
+    while let Some((dir_route, mut dir_filter)) = dir_queue.pop_front() {
+        if cancelled.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut dir_path = root.to_path_buf();
+        dir_route.iter().for_each(|node| dir_path.push(&node.name));
+        let dir_path_str = dir_path.to_string_lossy().to_string();
+        if let Some(ignore_rules) = dir_filter.ignore_rules.as_mut() {
+            if !dir_route.is_empty() {
+                ignore_rules.add_dir(&dir_path_str);
+            }
+        }
+        let mut nodes = match list_files(&dir_path, with_details) {
+            Ok(nodes) => nodes,
+            Err(_) => continue,
+        };
+        nodes.retain(|node| dir_filter.accepts(&dir_path_str, node));
+        nodes.sort_by(|a, b| a.lowercase_name.cmp(&b.lowercase_name));
+        for node in nodes {
+            let mut route = dir_route.clone();
+            route.push(node.clone());
+            if node.is_directory && !node.is_symlink {
+                dir_queue.push_back((route.clone(), dir_filter.clone()));
+            }
+            batch.push(SubtreeEntry::new(route));
+        }
+        if batch.len() >= 1000 || last_sent.elapsed() >= Duration::from_millis(100) {
+            if !on_batch(std::mem::take(&mut batch)) {
+                return;
+            }
+            last_sent = Instant::now();
+        }
+    }
+    if !batch.is_empty() {
+        on_batch(batch);
+    }
+}
@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    use crate::action_menu::{human_readable_size, short_readable_size};
+    use crate::filesystem::{format_mode, list_files};
+    use crate::testing::TempDir;
+
+    #[test]
+    fn test_format_mode() {
+        assert_eq!(format_mode(0o755, true, false), "drwxr-xr-x");
+        assert_eq!(format_mode(0o100644, false, false), "-rw-r--r--");
+        assert_eq!(format_mode(0o777, false, true), "lrwxrwxrwx");
+        assert_eq!(format_mode(0o000, false, false), "----------");
+    }
+
+    #[test]
+    fn test_list_files_details_on_demand() {
+        let dir = TempDir::new("details");
+        fs::write(dir.join("file"), "12345").unwrap();
+        fs::set_permissions(dir.join("file"), fs::Permissions::from_mode(0o640)).unwrap();
+
+        let nodes = list_files(&dir, false).unwrap();
+        assert!(nodes[0].details.is_none());
+        let nodes = list_files(&dir, true).unwrap();
+        let details = nodes[0].details.unwrap();
+        assert_eq!(details.size, 5);
+        assert_eq!(details.mode & 0o777, 0o640);
+        assert!(details.modified.is_some());
+    }
+
+    #[test]
+    fn test_readable_sizes() {
+        assert_eq!(short_readable_size(999), "999 B");
+        assert_eq!(short_readable_size(1_230_000), "1.23 MB");
+        assert_eq!(human_readable_size(1000), "1000 bytes");
+        assert_eq!(human_readable_size(2500), "2.50 kB (2500 bytes)");
+    }
+}
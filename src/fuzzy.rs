@@ -0,0 +1,199 @@
+use serde::Deserialize;
+
+const SCORE_MATCH: i32 = 16;
+const SCORE_GAP_START: i32 = -3;
+const SCORE_GAP_EXTENSION: i32 = -1;
+const BONUS_PATH_SEPARATOR: i32 = 9;
+const BONUS_BOUNDARY: i32 = 8;
+const BONUS_CAMEL_CASE: i32 = 7;
+const BONUS_CONSECUTIVE: i32 = 4;
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+const SCORE_IMPOSSIBLE: i32 = i32::MIN / 2;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    #[default]
+    Fuzzy, // characters of a word have to appear in order, but not necessarily next to each other
+    #[serde(rename = "exact")]
+    Substring, // every word has to be contained in the text
+}
+
+impl MatchMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MatchMode::Fuzzy => "fuzzy",
+            MatchMode::Substring => "exact",
+        }
+    }
+
+    pub fn toggled(&self) -> MatchMode {
+        match self {
+            MatchMode::Fuzzy => MatchMode::Substring,
+            MatchMode::Substring => MatchMode::Fuzzy,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct TextMatch {
+    pub score: i32,
+    pub indices: Vec<usize>, // positions of the matched characters (in chars, not bytes)
+}
+
+/// Matches all whitespace-separated words of a filter against the text.
+/// Returns None if any of the words doesn't match.
+pub fn match_words(text: &str, words: &[String], mode: MatchMode) -> Option<TextMatch> {
+    match mode {
+        MatchMode::Fuzzy => {
+            let mut result = TextMatch::default();
+            for word in words {
+                let word_match = fuzzy_match(text, word)?;
+                result.score += word_match.score;
+                result.indices.extend(word_match.indices);
+            }
+            result.indices.sort();
+            result.indices.dedup();
+            Some(result)
+        }
+        MatchMode::Substring => substring_match(text, words),
+    }
+}
+
+/// Finds the best scored occurrence of the pattern as a subsequence of the text (case-insensitive).
+/// Matches at word boundaries, camelCase humps, after path separators
+/// and consecutive runs of characters get a bonus, while gaps between them are penalized.
+pub fn fuzzy_match(text: &str, pattern: &str) -> Option<TextMatch> {
+    let text_chars: Vec<char> = text.chars().collect();
+    let lower_chars: Vec<char> = text_chars.iter().map(|c| lowercase_char(*c)).collect();
+    let pattern_chars: Vec<char> = pattern.chars().map(lowercase_char).collect();
+    let n = text_chars.len();
+    let m = pattern_chars.len();
+    if m == 0 {
+        return Some(TextMatch::default());
+    }
+    if !is_subsequence(&lower_chars, &pattern_chars) {
+        return None;
+    }
+
+    let bonuses: Vec<i32> = (0..n).map(|i| char_bonus(&text_chars, i)).collect();
+    // scores[j][i] - best score when j-th pattern char is matched at i-th text char
+    let mut scores: Vec<Vec<i32>> = vec![vec![SCORE_IMPOSSIBLE; n]; m];
+    // previous[j][i] - position of the (j-1)-th pattern char in the best alignment
+    let mut previous: Vec<Vec<usize>> = vec![vec![0; n]; m];
+
+    for i in 0..n {
+        if lower_chars[i] == pattern_chars[0] {
+            scores[0][i] = SCORE_MATCH + bonuses[i] * BONUS_FIRST_CHAR_MULTIPLIER;
+        }
+    }
+    for j in 1..m {
+        let mut gap_score = SCORE_IMPOSSIBLE; // best score of a preceding match with a gap
+        let mut gap_position = 0;
+        for i in j..n {
+            if i >= 2 && scores[j - 1][i - 2] > SCORE_IMPOSSIBLE {
+                let opened = scores[j - 1][i - 2] + SCORE_GAP_START;
+                let extended = gap_score + SCORE_GAP_EXTENSION;
+                if opened >= extended {
+                    gap_score = opened;
+                    gap_position = i - 2;
+                } else {
+                    gap_score = extended;
+                }
+            } else if gap_score > SCORE_IMPOSSIBLE {
+                gap_score += SCORE_GAP_EXTENSION;
+            }
+            if lower_chars[i] != pattern_chars[j] {
+                continue;
+            }
+            let consecutive = match scores[j - 1][i - 1] > SCORE_IMPOSSIBLE {
+                true => scores[j - 1][i - 1] + BONUS_CONSECUTIVE,
+                false => SCORE_IMPOSSIBLE,
+            };
+            if consecutive == SCORE_IMPOSSIBLE && gap_score <= SCORE_IMPOSSIBLE {
+                continue;
+            }
+            let score = SCORE_MATCH + bonuses[i];
+            if consecutive >= gap_score {
+                scores[j][i] = consecutive + score;
+                previous[j][i] = i - 1;
+            } else {
+                scores[j][i] = gap_score + score;
+                previous[j][i] = gap_position;
+            }
+        }
+    }
+
+    let (mut position, best_score) = scores[m - 1]
+        .iter()
+        .enumerate()
+        .filter(|(_, score)| **score > SCORE_IMPOSSIBLE)
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))?;
+    let best_score = *best_score;
+    let mut indices: Vec<usize> = vec![0; m];
+    for j in (0..m).rev() {
+        indices[j] = position;
+        position = previous[j][position];
+    }
+    Some(TextMatch {
+        score: best_score.max(1),
+        indices,
+    })
+}
+
+/// Plain case-insensitive substring matching of every word,
+/// with a bonus when the text starts with the first word.
+pub fn substring_match(text: &str, words: &[String]) -> Option<TextMatch> {
+    let lowercase_text: String = text.chars().map(lowercase_char).collect();
+    let mut result = TextMatch::default();
+    for word in words {
+        let lowercase_word: String = word.chars().map(lowercase_char).collect();
+        let byte_pos = lowercase_text.find(&lowercase_word)?;
+        let char_pos = lowercase_text[..byte_pos].chars().count();
+        result.score += 1;
+        result
+            .indices
+            .extend(char_pos..char_pos + lowercase_word.chars().count());
+    }
+    if let Some(first_word) = words.first() {
+        if lowercase_text.starts_with(&first_word.to_lowercase()) {
+            result.score += 10;
+        }
+    }
+    result.indices.sort();
+    result.indices.dedup();
+    Some(result)
+}
+
+fn lowercase_char(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+fn is_subsequence(text: &[char], pattern: &[char]) -> bool {
+    let mut pattern_iter = pattern.iter().peekable();
+    for c in text {
+        if pattern_iter.peek() == Some(&c) {
+            pattern_iter.next();
+        }
+    }
+    pattern_iter.peek().is_none()
+}
+
+fn char_bonus(text: &[char], i: usize) -> i32 {
+    if i == 0 {
+        return BONUS_BOUNDARY;
+    }
+    let prev = text[i - 1];
+    let current = text[i];
+    if prev == '/' {
+        BONUS_PATH_SEPARATOR
+    } else if !prev.is_alphanumeric() && current.is_alphanumeric() {
+        BONUS_BOUNDARY
+    } else if (prev.is_lowercase() && current.is_uppercase())
+        || (!prev.is_ascii_digit() && current.is_ascii_digit())
+    {
+        BONUS_CAMEL_CASE
+    } else {
+        0
+    }
+}
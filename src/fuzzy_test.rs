@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use crate::fuzzy::{fuzzy_match, match_words, substring_match, MatchMode};
+
+    fn words(filter: &str) -> Vec<String> {
+        filter.split_whitespace().map(|it| it.to_string()).collect()
+    }
+
+    #[test]
+    fn test_fuzzy_match_subsequence() {
+        let result = fuzzy_match("logic_action_menu.rs", "lgam").unwrap();
+        assert_eq!(result.indices, vec![0, 2, 6, 13]);
+        assert!(fuzzy_match("logic_action_menu.rs", "lgamx").is_none());
+        assert!(fuzzy_match("logic_action_menu.rs", "mal").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_match_prefers_boundaries() {
+        let result = fuzzy_match("src/app_logic/logic_action_menu.rs", "lam").unwrap();
+        assert_eq!(result.indices, vec![14, 20, 27]);
+        let camel = fuzzy_match("FooBarBaz", "fbb").unwrap();
+        assert_eq!(camel.indices, vec![0, 3, 6]);
+    }
+
+    #[test]
+    fn test_fuzzy_match_scores() {
+        let consecutive = fuzzy_match("menu.rs", "menu").unwrap().score;
+        let scattered = fuzzy_match("my_element_nu", "menu").unwrap().score;
+        assert!(consecutive > scattered);
+        let boundary = fuzzy_match("app_main.rs", "main").unwrap().score;
+        let inner = fuzzy_match("domain.rs", "main").unwrap().score;
+        assert!(boundary > inner);
+    }
+
+    #[test]
+    fn test_fuzzy_match_case_insensitive() {
+        let result = fuzzy_match("README.md", "readme").unwrap();
+        assert_eq!(result.indices, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_substring_match() {
+        let result = substring_match("logic_action_menu.rs", &words("menu act")).unwrap();
+        assert_eq!(result.score, 2);
+        assert_eq!(result.indices, vec![6, 7, 8, 13, 14, 15, 16]);
+        let prefixed = substring_match("logic_action_menu.rs", &words("logic")).unwrap();
+        assert_eq!(prefixed.score, 11);
+        assert!(substring_match("logic_action_menu.rs", &words("lgam")).is_none());
+    }
+
+    #[test]
+    fn test_match_words() {
+        assert!(match_words("logic_action_menu.rs", &words("lgam rs"), MatchMode::Fuzzy).is_some());
+        assert!(
+            match_words("logic_action_menu.rs", &words("lgam"), MatchMode::Substring).is_none()
+        );
+        assert!(match_words("anything", &words(""), MatchMode::Fuzzy).is_some());
+    }
+}
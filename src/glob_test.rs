@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::glob::glob_match;
+
+    #[test]
+    fn test_glob_wildcards() {
+        assert!(glob_match("*.rs", "main.rs"));
+        assert!(glob_match("*.rs", ".rs"));
+        assert!(!glob_match("*.rs", "main.rsx"));
+        assert!(glob_match("Dockerfile*", "Dockerfile.dev"));
+        assert!(glob_match("a*b*c", "aXXbYYc"));
+        assert!(!glob_match("a*b*c", "aXXbYY"));
+        assert!(glob_match("file?.txt", "file1.txt"));
+        assert!(!glob_match("file?.txt", "file10.txt"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("exact", "exact"));
+        assert!(!glob_match("exact", "exactly"));
+    }
+
+    #[test]
+    fn test_glob_classes() {
+        assert!(glob_match("[abc].txt", "b.txt"));
+        assert!(!glob_match("[abc].txt", "d.txt"));
+        assert!(glob_match("file[0-9]", "file7"));
+        assert!(!glob_match("file[!0-9]", "file7"));
+        assert!(glob_match("file[!0-9]", "fileX"));
+        assert!(glob_match("[]]", "]"));
+        assert!(glob_match("[unclosed", "[unclosed"));
+        assert!(glob_match("\\*", "*"));
+        assert!(!glob_match("\\*", "x"));
+    }
+}
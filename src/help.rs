@@ -0,0 +1,98 @@
+use crate::appdata::WindowFocus;
+use crate::fuzzy::{match_words, MatchMode};
+use crate::keymap::Keymap;
+use crate::vim::{VimAction, VIM_SEQUENCES};
+
+/// Line of the help overlay: keys running a command in the window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HelpEntry {
+    pub keys: Vec<String>,
+    pub command: String,
+    pub description: &'static str,
+}
+
+/// Help entry matching the filter, with positions of matched characters in its description.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HelpMatch {
+    pub index: usize,
+    pub description_indices: Vec<usize>,
+}
+
+/// Lists the bindings of the window straight from the keymap, grouping the keys of every command.
+/// The vim sequences come first when the normal mode drives the tree.
+pub fn help_entries(keymap: &Keymap, focus: WindowFocus, vim_mode: bool) -> Vec<HelpEntry> {
+    let mut entries: Vec<HelpEntry> = Vec::new();
+    if vim_mode && focus == WindowFocus::Tree {
+        for (sequence, action) in VIM_SEQUENCES {
+            let key_name = match *sequence {
+                " " => "space".to_string(),
+                sequence => sequence.to_string(),
+            };
+            let (command, description) = match action {
+                VimAction::Run(command) => (command.name(), command.description(focus)),
+                VimAction::Insert => ("insert", "Switch to the insert mode"),
+                VimAction::Search => ("search", "Clear the search and switch to the insert mode"),
+            };
+            push_help_key(&mut entries, key_name, command, description);
+        }
+    }
+    for (key, command) in keymap.bindings.get(&focus).into_iter().flatten() {
+        push_help_key(
+            &mut entries,
+            key.name(),
+            command.name(),
+            command.description(focus),
+        );
+    }
+    entries
+}
+
+fn push_help_key(
+    entries: &mut Vec<HelpEntry>,
+    key_name: String,
+    command: &str,
+    description: &'static str,
+) {
+    match entries.iter_mut().find(|it| it.command == command) {
+        Some(entry) => entry.keys.push(key_name),
+        None => entries.push(HelpEntry {
+            keys: vec![key_name],
+            command: command.to_string(),
+            description,
+        }),
+    }
+}
+
+/// Matches help entries by description, keys or command name, the best matches first.
+pub fn filter_help_entries(
+    entries: &[HelpEntry],
+    filter_text: &str,
+    match_mode: MatchMode,
+) -> Vec<HelpMatch> {
+    let filter_words: Vec<String> = filter_text
+        .to_lowercase()
+        .split_whitespace()
+        .map(|it| it.to_string())
+        .collect();
+    let mut matches: Vec<(i32, HelpMatch)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let description_match = match_words(entry.description, &filter_words, match_mode);
+            let text_match = description_match
+                .clone()
+                .or_else(|| match_words(&entry.keys.join(" "), &filter_words, match_mode))
+                .or_else(|| match_words(&entry.command, &filter_words, match_mode))?;
+            let description_indices = description_match.map(|it| it.indices).unwrap_or_default();
+            Some((
+                text_match.score,
+                HelpMatch {
+                    index,
+                    description_indices,
+                },
+            ))
+        })
+        .collect();
+    matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    matches.into_iter().map(|(_, it)| it).collect()
+}
@@ -0,0 +1,143 @@
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use crate::app::App;
+    use crate::appdata::WindowFocus;
+    use crate::fuzzy::MatchMode;
+    use crate::help::{filter_help_entries, help_entries, HelpEntry};
+    use crate::keyboard::on_show_help;
+    use crate::keymap::{Command, Key, Keymap};
+
+    fn find(entries: &[HelpEntry], command: Command) -> Option<&HelpEntry> {
+        entries.iter().find(|it| it.command == command.name())
+    }
+
+    #[test]
+    fn test_help_entries_follow_keymap() {
+        let mut keymap = Keymap::default();
+        let entries = help_entries(&keymap, WindowFocus::Tree, false);
+        let rename = find(&entries, Command::Rename).unwrap();
+        assert_eq!(rename.keys, vec!["f2", "ctrl+r"]);
+        assert_eq!(
+            rename.description,
+            Command::Rename.description(WindowFocus::Tree)
+        );
+        assert!(find(&entries, Command::ScrollLeft).is_none());
+
+        keymap.unbind(WindowFocus::Tree, Key::parse("f2").unwrap());
+        keymap.bind(
+            WindowFocus::Tree,
+            Key::parse("ctrl+n").unwrap(),
+            Command::Rename,
+        );
+        let entries = help_entries(&keymap, WindowFocus::Tree, false);
+        assert_eq!(
+            find(&entries, Command::Rename).unwrap().keys,
+            vec!["ctrl+r", "ctrl+n"]
+        );
+
+        let entries = help_entries(&keymap, WindowFocus::Viewer, false);
+        assert_eq!(
+            find(&entries, Command::Cancel).unwrap().keys,
+            vec!["esc", "q"]
+        );
+    }
+
+    #[test]
+    fn test_help_describes_commands_per_window() {
+        let keymap = Keymap::default();
+        let trash = help_entries(&keymap, WindowFocus::Trash, false);
+        assert_eq!(
+            find(&trash, Command::Confirm).unwrap().description,
+            "Restore the selected entry"
+        );
+        let tree = help_entries(&keymap, WindowFocus::Tree, false);
+        assert_eq!(
+            find(&tree, Command::Confirm).unwrap().description,
+            "Pick or enter the selected entry"
+        );
+        assert_eq!(
+            find(&tree, Command::MoveToTrash).unwrap().description,
+            "Move the selected entry to the trash"
+        );
+        let viewer = help_entries(&keymap, WindowFocus::Viewer, false);
+        assert_eq!(
+            find(&viewer, Command::MoveDown).unwrap().description,
+            "Scroll down"
+        );
+    }
+
+    #[test]
+    fn test_help_entries_with_vim_sequences() {
+        let entries = help_entries(&Keymap::default(), WindowFocus::Tree, true);
+        assert_eq!(entries[0].keys, vec!["j", "down"]);
+        assert_eq!(find(&entries, Command::Yank).unwrap().keys[0], "yy");
+        assert_eq!(
+            find(&entries, Command::ToggleMark).unwrap().keys[0],
+            "space"
+        );
+        assert!(entries.iter().any(|it| it.command == "insert"));
+    }
+
+    #[test]
+    fn test_filter_help_entries() {
+        let entries = help_entries(&Keymap::default(), WindowFocus::Tree, false);
+        let matches = filter_help_entries(&entries, "bookmark", MatchMode::Substring);
+        let commands: Vec<&str> = matches
+            .iter()
+            .map(|it| entries[it.index].command.as_str())
+            .collect();
+        assert_eq!(commands, vec!["add_bookmark", "show_bookmarks"]);
+        assert!(!matches[0].description_indices.is_empty());
+
+        // keys and command names match too, without highlighting the description
+        let matches = filter_help_entries(&entries, "ctrl+z", MatchMode::Substring);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(entries[matches[0].index].command, "undo");
+        assert!(matches[0].description_indices.is_empty());
+
+        assert_eq!(
+            filter_help_entries(&entries, "", MatchMode::Fuzzy).len(),
+            entries.len()
+        );
+    }
+
+    #[test]
+    fn test_question_mark_in_tree() {
+        let mut app = App::new();
+        let key = KeyEvent::from(KeyCode::Char('?'));
+        assert_eq!(
+            app.keymap.command_for(WindowFocus::Tree, key),
+            Some(Command::ShowHelp)
+        );
+        on_show_help(&mut app, key);
+        assert_eq!(app.window_focus, WindowFocus::Help);
+        app.close_help();
+
+        // typed once a search has started
+        app.type_search_text('a');
+        on_show_help(&mut app, key);
+        assert_eq!(app.window_focus, WindowFocus::Tree);
+        assert_eq!(app.filter_text, "a?");
+        on_show_help(&mut app, KeyEvent::from(KeyCode::F(1)));
+        assert_eq!(app.window_focus, WindowFocus::Help);
+    }
+
+    #[test]
+    fn test_help_returns_to_window() {
+        let mut app = App::new();
+        app.window_focus = WindowFocus::Trash;
+        app.open_help();
+        assert_eq!(app.window_focus, WindowFocus::Help);
+        assert_eq!(app.help_entries[0].command, "cancel");
+        app.type_help_filter('x');
+        app.type_help_filter('y');
+        assert!(app.help_matches.len() < app.help_entries.len());
+        app.backspace_help_filter();
+        app.backspace_help_filter();
+        assert_eq!(app.help_matches.len(), app.help_entries.len());
+        app.close_help();
+        assert_eq!(app.window_focus, WindowFocus::Trash);
+    }
+}
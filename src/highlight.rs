@@ -0,0 +1,139 @@
+use lazy_static::lazy_static;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use std::path::Path;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, HighlightState, Style as SyntectStyle, Theme, ThemeSet};
+use syntect::parsing::{ParseState, SyntaxReference, SyntaxSet};
+
+pub const DEFAULT_SYNTAX_THEME: &str = "base16-ocean.dark";
+
+lazy_static! {
+    static ref SYNTAX_SET: SyntaxSet = SyntaxSet::load_defaults_newlines();
+    static ref THEME_SET: ThemeSet = ThemeSet::load_defaults();
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HighlightSettings {
+    pub enabled: bool,
+    pub theme: String, // name of one of the embedded themes
+}
+
+impl Default for HighlightSettings {
+    fn default() -> Self {
+        HighlightSettings {
+            enabled: true,
+            theme: DEFAULT_SYNTAX_THEME.to_string(),
+        }
+    }
+}
+
+/// Names of the embedded color themes.
+pub fn theme_names() -> Vec<String> {
+    THEME_SET.themes.keys().cloned().collect()
+}
+
+pub fn is_known_theme(name: &str) -> bool {
+    THEME_SET.themes.contains_key(name)
+}
+
+/// Picks a grammar by the file extension or name, falling back to the first line (e.g. a shebang).
+/// Returns `None` for plain text.
+pub fn find_syntax(path: &str, first_line: &str) -> Option<&'static SyntaxReference> {
+    let file_path = Path::new(path);
+    let by_extension = file_path
+        .extension()
+        .and_then(|ext| SYNTAX_SET.find_syntax_by_extension(&ext.to_string_lossy()));
+    let by_name = || {
+        file_path
+            .file_name()
+            .and_then(|name| SYNTAX_SET.find_syntax_by_extension(&name.to_string_lossy()))
+    };
+    let syntax = by_extension
+        .or_else(by_name)
+        .or_else(|| SYNTAX_SET.find_syntax_by_first_line(first_line))?;
+    match syntax.name == SYNTAX_SET.find_syntax_plain_text().name {
+        true => None,
+        false => Some(syntax),
+    }
+}
+
+/// Highlights consecutive lines of a file.
+/// Returns `None` when highlighting is disabled, the file type isn't recognized
+/// or the grammar fails, so the caller falls back to plain text.
+pub fn highlight_lines(
+    path: &str,
+    lines: &[String],
+    settings: &HighlightSettings,
+) -> Option<Vec<Line<'static>>> {
+    let first_line: &str = lines.first().map(|it| it.as_str()).unwrap_or("");
+    ChunkHighlighter::new(path, first_line, settings)?.highlight(lines)
+}
+
+/// Highlighting progress of a file read in parts, carried over from one chunk to the next
+/// so that multi-line constructs (e.g. block comments) keep their colors.
+#[derive(Debug, Clone)]
+pub struct ChunkHighlighter {
+    theme: &'static Theme,
+    highlight_state: HighlightState,
+    parse_state: ParseState,
+}
+
+impl ChunkHighlighter {
+    pub fn new(path: &str, first_line: &str, settings: &HighlightSettings) -> Option<Self> {
+        if !settings.enabled {
+            return None;
+        }
+        let syntax = find_syntax(path, first_line)?;
+        let theme: &'static Theme = THEME_SET.themes.get(&settings.theme)?;
+        let (highlight_state, parse_state) = HighlightLines::new(syntax, theme).state();
+        Some(ChunkHighlighter {
+            theme,
+            highlight_state,
+            parse_state,
+        })
+    }
+
+    /// Highlights the next lines of the file, `None` if the grammar fails.
+    pub fn highlight(&mut self, lines: &[String]) -> Option<Vec<Line<'static>>> {
+        let mut highlighter = HighlightLines::from_state(
+            self.theme,
+            self.highlight_state.clone(),
+            self.parse_state.clone(),
+        );
+        let mut highlighted: Vec<Line<'static>> = Vec::with_capacity(lines.len());
+        for line in lines {
+            let line_nl = format!("{}\n", line);
+            let ranges: Vec<(SyntectStyle, &str)> =
+                highlighter.highlight_line(&line_nl, &SYNTAX_SET).ok()?;
+            let spans: Vec<Span<'static>> = ranges
+                .into_iter()
+                .map(|(style, text)| {
+                    Span::styled(
+                        text.trim_end_matches('\n').to_string(),
+                        convert_style(style),
+                    )
+                })
+                .collect();
+            highlighted.push(Line::from(spans));
+        }
+        (self.highlight_state, self.parse_state) = highlighter.state();
+        Some(highlighted)
+    }
+}
+
+/// Converts the foreground color and font style, keeping the terminal background.
+pub fn convert_style(style: SyntectStyle) -> Style {
+    let fg = style.foreground;
+    let mut converted = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+    if style.font_style.contains(FontStyle::BOLD) {
+        converted = converted.add_modifier(Modifier::BOLD);
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        converted = converted.add_modifier(Modifier::ITALIC);
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        converted = converted.add_modifier(Modifier::UNDERLINED);
+    }
+    converted
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use ratatui::style::{Color, Modifier};
+    use std::fs;
+
+    use crate::highlight::{
+        convert_style, find_syntax, highlight_lines, is_known_theme, HighlightSettings,
+        DEFAULT_SYNTAX_THEME,
+    };
+    use crate::preview::{load_preview, PreviewContent};
+    use crate::testing::TempDir;
+
+    #[test]
+    fn test_find_syntax() {
+        assert_eq!(find_syntax("/src/main.rs", "").unwrap().name, "Rust");
+        assert_eq!(find_syntax("/a/Makefile", "").unwrap().name, "Makefile");
+        assert_eq!(
+            find_syntax("/a/run", "#!/bin/bash").unwrap().name,
+            "Bourne Again Shell (bash)"
+        );
+        assert!(find_syntax("/a/notes.txt", "hello").is_none());
+        assert!(find_syntax("/a/unknown.xyz123", "hello").is_none());
+    }
+
+    #[test]
+    fn test_highlight_lines() {
+        let lines: Vec<String> = vec!["fn main() {".into(), "    let x = 1;".into(), "}".into()];
+        let settings = HighlightSettings::default();
+        let highlighted = highlight_lines("/src/main.rs", &lines, &settings).unwrap();
+        assert_eq!(highlighted.len(), 3);
+        let texts: Vec<String> = highlighted.iter().map(|line| line.to_string()).collect();
+        assert_eq!(texts, lines);
+        assert!(highlighted[0].spans.len() > 1);
+        assert!(highlighted[0]
+            .spans
+            .iter()
+            .all(|span| matches!(span.style.fg, Some(Color::Rgb(..)))));
+
+        assert!(highlight_lines("/a/notes.txt", &lines, &settings).is_none());
+        let disabled = HighlightSettings {
+            enabled: false,
+            ..settings
+        };
+        assert!(highlight_lines("/src/main.rs", &lines, &disabled).is_none());
+    }
+
+    #[test]
+    fn test_convert_style() {
+        let style = syntect::highlighting::Style {
+            foreground: syntect::highlighting::Color {
+                r: 10,
+                g: 20,
+                b: 30,
+                a: 255,
+            },
+            background: syntect::highlighting::Color::BLACK,
+            font_style: syntect::highlighting::FontStyle::BOLD,
+        };
+        let converted = convert_style(style);
+        assert_eq!(converted.fg, Some(Color::Rgb(10, 20, 30)));
+        assert_eq!(converted.bg, None);
+        assert!(converted.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_known_themes() {
+        assert!(is_known_theme(DEFAULT_SYNTAX_THEME));
+        assert!(!is_known_theme("no-such-theme"));
+    }
+
+    #[test]
+    fn test_highlighted_preview() {
+        let dir = TempDir::new("highlight");
+        let content = "fn main() {\n    println!(\"hello\");\n}\n";
+        fs::write(dir.join("main.rs"), content).unwrap();
+        let path = dir.join("main.rs").to_string_lossy().to_string();
+        let settings = HighlightSettings::default();
+
+        match load_preview(&path, 10, &settings) {
+            PreviewContent::Text { highlighted, .. } => assert_eq!(highlighted.unwrap().len(), 3),
+            other => panic!("unexpected preview: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::fuzzy::{match_words, MatchMode};
+use crate::storage::{data_dir, load_data_file, save_data_file};
+
+/// When the ranks sum up above this limit, all of them are scaled down
+/// and the rarely used entries are forgotten.
+const MAX_TOTAL_RANK: f64 = 2000.0;
+const AGING_FACTOR: f64 = 0.9;
+const MIN_RANK: f64 = 1.0;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+/// Visited path with its visits count (decayed over time) and the last visit time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub path: String,
+    pub rank: f64,
+    pub last_access: u64, // seconds since the Unix epoch
+    #[serde(skip)]
+    pub is_directory: bool, // resolved when the history popup is opened
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct HistoryFile {
+    #[serde(default)]
+    entries: Vec<HistoryEntry>,
+}
+
+/// History entry matching the filter, with positions of matched characters in its path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryMatch {
+    pub index: usize,
+    pub indices: Vec<usize>,
+}
+
+pub fn default_history_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("history.toml"))
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let file: HistoryFile = load_data_file(path)?;
+    Ok(file.entries)
+}
+
+pub fn save_history(path: &Path, entries: &[HistoryEntry]) -> Result<()> {
+    let file = HistoryFile {
+        entries: entries.to_vec(),
+    };
+    save_data_file(path, &file)
+}
+
+pub fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|it| it.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn record_visit(entries: &mut Vec<HistoryEntry>, path: &str, now: u64) {
+    match entries.iter_mut().find(|it| it.path == path) {
+        Some(entry) => {
+            entry.rank += 1.0;
+            entry.last_access = now;
+        }
+        None => entries.push(HistoryEntry {
+            path: path.to_string(),
+            rank: 1.0,
+            last_access: now,
+            is_directory: false,
+        }),
+    }
+    let total_rank: f64 = entries.iter().map(|it| it.rank).sum();
+    if total_rank > MAX_TOTAL_RANK {
+        entries.iter_mut().for_each(|it| it.rank *= AGING_FACTOR);
+        entries.retain(|it| it.rank >= MIN_RANK);
+    }
+}
+
+/// Score combining how often and how recently the path was visited, the same way zoxide does.
+pub fn frecency(entry: &HistoryEntry, now: u64) -> f64 {
+    let age = now.saturating_sub(entry.last_access);
+    let factor = match age {
+        _ if age < HOUR => 4.0,
+        _ if age < DAY => 2.0,
+        _ if age < WEEK => 0.5,
+        _ => 0.25,
+    };
+    entry.rank * factor
+}
+
+/// Matches entries by path, the most frecent first.
+pub fn rank_history(
+    entries: &[HistoryEntry],
+    filter_text: &str,
+    match_mode: MatchMode,
+    now: u64,
+) -> Vec<HistoryMatch> {
+    let filter_words: Vec<String> = filter_text
+        .to_lowercase()
+        .split_whitespace()
+        .map(|it| it.to_string())
+        .collect();
+    let mut matches: Vec<(f64, HistoryMatch)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let text_match = match_words(&entry.path, &filter_words, match_mode)?;
+            let history_match = HistoryMatch {
+                index,
+                indices: text_match.indices,
+            };
+            Some((frecency(entry, now), history_match))
+        })
+        .collect();
+    matches.sort_by(|a, b| b.0.total_cmp(&a.0));
+    matches.into_iter().map(|(_, it)| it).collect()
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+
+    use crate::fuzzy::MatchMode;
+    use crate::history::{
+        frecency, load_history, rank_history, record_visit, save_history, HistoryEntry,
+    };
+    use crate::testing::TempDir;
+
+    const NOW: u64 = 1_700_000_000;
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn entry(path: &str, rank: f64, age: u64) -> HistoryEntry {
+        HistoryEntry {
+            path: path.to_string(),
+            rank,
+            last_access: NOW - age,
+            is_directory: false,
+        }
+    }
+
+    #[test]
+    fn test_record_visit() {
+        let mut entries = vec![];
+        record_visit(&mut entries, "/a", NOW - 10);
+        record_visit(&mut entries, "/b", NOW - 5);
+        record_visit(&mut entries, "/a", NOW);
+        assert_eq!(entries, vec![entry("/a", 2.0, 0), entry("/b", 1.0, 5)]);
+    }
+
+    #[test]
+    fn test_record_visit_ages_entries() {
+        let mut entries = vec![entry("/frequent", 1999.0, 0), entry("/rare", 1.0, 0)];
+        record_visit(&mut entries, "/frequent", NOW);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, "/frequent");
+        assert!((entries[0].rank - 1800.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_frecency_prefers_recent_visits() {
+        assert_eq!(frecency(&entry("/a", 10.0, 60), NOW), 40.0);
+        assert_eq!(frecency(&entry("/a", 10.0, 2 * 60 * 60), NOW), 20.0);
+        assert_eq!(frecency(&entry("/a", 10.0, 3 * DAY), NOW), 5.0);
+        assert_eq!(frecency(&entry("/a", 10.0, 30 * DAY), NOW), 2.5);
+    }
+
+    #[test]
+    fn test_rank_history() {
+        let entries = vec![
+            entry("/home/user/old-project", 20.0, 30 * DAY),
+            entry("/home/user/project", 3.0, 60),
+            entry("/etc/hosts", 1.0, 60),
+        ];
+        let ranked: Vec<usize> = rank_history(&entries, "", MatchMode::Fuzzy, NOW)
+            .iter()
+            .map(|it| it.index)
+            .collect();
+        assert_eq!(ranked, vec![1, 0, 2]);
+
+        let matches = rank_history(&entries, "hosts", MatchMode::Fuzzy, NOW);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].index, 2);
+        assert_eq!(matches[0].indices, vec![5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_save_and_load_history() {
+        let dir = TempDir::new("history");
+        let path = dir.join("history.toml");
+        assert_eq!(load_history(&path).unwrap(), vec![]);
+        let entries = vec![entry("/tmp/with 'quote'", 2.5, 0)];
+        save_history(&path, &entries).unwrap();
+        assert_eq!(load_history(&path).unwrap(), entries);
+    }
+}
@@ -0,0 +1,181 @@
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::filesystem::FileNode;
+use crate::glob::glob_match;
+
+const IGNORE_FILE_NAMES: [&str; 2] = [".gitignore", ".ignore"];
+
+/// Single pattern line from an ignore file, in the `.gitignore` format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IgnoreRule {
+    pub segments: Vec<String>, // pattern split by slashes
+    pub negated: bool,         // `!pattern` re-includes previously ignored paths
+    pub dir_only: bool,        // `pattern/` matches directories only
+    pub anchored: bool,        // pattern containing a slash is relative to the ignore file location
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct IgnoreFile {
+    pub base_dir: String, // absolute path of the directory containing the ignore file
+    pub rules: Vec<IgnoreRule>,
+}
+
+/// Ignore rules collected from the directories leading to a listed directory.
+/// Rules from deeper directories take precedence, just like the last matching line does.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IgnoreRules {
+    files: Vec<Arc<IgnoreFile>>,
+}
+
+impl IgnoreRule {
+    pub fn parse(line: &str) -> Option<IgnoreRule> {
+        let mut line: &str = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let negated = line.starts_with('!');
+        if negated {
+            line = &line[1..];
+        }
+        if line.starts_with("\\#") || line.starts_with("\\!") {
+            line = &line[1..];
+        }
+        let dir_only = line.ends_with('/');
+        let line = line.trim_end_matches('/');
+        let anchored = line.contains('/');
+        let segments: Vec<String> = line
+            .split('/')
+            .filter(|it| !it.is_empty())
+            .map(|it| it.to_string())
+            .collect();
+        if segments.is_empty() {
+            return None;
+        }
+        Some(IgnoreRule {
+            segments,
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    /// Checks the rule against a path relative to the ignore file location.
+    pub fn matches(&self, relative_parts: &[&str], is_directory: bool) -> bool {
+        if self.dir_only && !is_directory {
+            return false;
+        }
+        match self.anchored {
+            true => match_segments(&self.segments, relative_parts),
+            false => match relative_parts.last() {
+                Some(name) => glob_match(&self.segments[0], name),
+                None => false,
+            },
+        }
+    }
+}
+
+/// Matches path segments against pattern segments, where `**` stands for any number of segments.
+fn match_segments(pattern: &[String], parts: &[&str]) -> bool {
+    match pattern.first() {
+        None => parts.is_empty(),
+        Some(segment) if segment == "**" => {
+            (0..=parts.len()).any(|skip| match_segments(&pattern[1..], &parts[skip..]))
+        }
+        Some(segment) => match parts.first() {
+            Some(part) => glob_match(segment, part) && match_segments(&pattern[1..], &parts[1..]),
+            None => false,
+        },
+    }
+}
+
+impl IgnoreFile {
+    pub fn parse(base_dir: &str, content: &str) -> IgnoreFile {
+        IgnoreFile {
+            base_dir: base_dir.trim_end_matches('/').to_string(),
+            rules: content.lines().filter_map(IgnoreRule::parse).collect(),
+        }
+    }
+
+    /// Returns Some(true) if the path is ignored, Some(false) if it's explicitly re-included
+    /// or None if no rule matches it.
+    pub fn check(&self, abs_path: &str, is_directory: bool) -> Option<bool> {
+        let relative: &str = abs_path.strip_prefix(&self.base_dir)?;
+        if !relative.starts_with('/') {
+            return None;
+        }
+        let relative_parts: Vec<&str> = relative.split('/').filter(|it| !it.is_empty()).collect();
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(&relative_parts, is_directory))
+            .map(|rule| !rule.negated)
+    }
+}
+
+impl IgnoreRules {
+    /// Reads the ignore files from every directory on the way to the given one.
+    /// Inside a git repository, the directories above its root are skipped.
+    pub fn load_along(dir_paths: &[String]) -> IgnoreRules {
+        let repo_root_index: usize = dir_paths
+            .iter()
+            .rposition(|dir_path| Path::new(dir_path).join(".git").exists())
+            .unwrap_or(0);
+        let mut rules = IgnoreRules::default();
+        for dir_path in &dir_paths[repo_root_index..] {
+            rules.add_dir(dir_path);
+        }
+        rules
+    }
+
+    /// Adds the ignore files placed in a directory, returning whether any was found.
+    pub fn add_dir(&mut self, dir_path: &str) -> bool {
+        let mut found = false;
+        for file_name in IGNORE_FILE_NAMES {
+            let file_path = Path::new(dir_path).join(file_name);
+            if let Ok(content) = fs::read_to_string(&file_path) {
+                let ignore_file = IgnoreFile::parse(dir_path, &content);
+                if !ignore_file.rules.is_empty() {
+                    self.files.push(Arc::new(ignore_file));
+                    found = true;
+                }
+            }
+        }
+        found
+    }
+
+    pub fn is_ignored(&self, abs_path: &str, is_directory: bool) -> bool {
+        self.files
+            .iter()
+            .rev()
+            .find_map(|file| file.check(abs_path, is_directory))
+            .unwrap_or(false)
+    }
+}
+
+/// Decides which directory entries are displayed.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EntryFilter {
+    pub hide_dotfiles: bool,
+    pub ignore_rules: Option<IgnoreRules>,
+}
+
+impl EntryFilter {
+    pub fn is_active(&self) -> bool {
+        self.hide_dotfiles || self.ignore_rules.is_some()
+    }
+
+    pub fn accepts(&self, dir_path: &str, node: &FileNode) -> bool {
+        if self.hide_dotfiles && node.name.starts_with('.') {
+            return false;
+        }
+        if let Some(ignore_rules) = &self.ignore_rules {
+            let abs_path = format!("{}/{}", dir_path.trim_end_matches('/'), node.name);
+            if ignore_rules.is_ignored(&abs_path, node.is_directory) {
+                return false;
+            }
+        }
+        true
+    }
+}
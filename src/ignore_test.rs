@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::atomic::AtomicBool;
+
+    use crate::filesystem::{walk_subtree, SubtreeEntry};
+    use crate::ignore::{EntryFilter, IgnoreFile, IgnoreRule, IgnoreRules};
+    use crate::testing::TempDir;
+
+    #[test]
+    fn test_parse_ignore_rule() {
+        let rule = IgnoreRule::parse("target/").unwrap();
+        assert_eq!(rule.segments, vec!["target"]);
+        assert!(rule.dir_only && !rule.anchored && !rule.negated);
+        let rule = IgnoreRule::parse("!/docs/*.md").unwrap();
+        assert_eq!(rule.segments, vec!["docs", "*.md"]);
+        assert!(rule.negated && rule.anchored);
+        assert!(IgnoreRule::parse("# comment").is_none());
+        assert!(IgnoreRule::parse("   ").is_none());
+        assert_eq!(
+            IgnoreRule::parse("\\#hash").unwrap().segments,
+            vec!["#hash"]
+        );
+    }
+
+    #[test]
+    fn test_ignore_file_check() {
+        let content = "*.log\n!keep.log\ntarget/\n/build\ndocs/**/*.tmp\n";
+        let file = IgnoreFile::parse("/repo", content);
+        assert_eq!(file.check("/repo/debug.log", false), Some(true));
+        assert_eq!(file.check("/repo/sub/debug.log", false), Some(true));
+        assert_eq!(file.check("/repo/keep.log", false), Some(false));
+        assert_eq!(file.check("/repo/target", true), Some(true));
+        assert_eq!(file.check("/repo/target", false), None);
+        assert_eq!(file.check("/repo/build", true), Some(true));
+        assert_eq!(file.check("/repo/sub/build", true), None);
+        assert_eq!(file.check("/repo/docs/a/b/x.tmp", false), Some(true));
+        assert_eq!(file.check("/repo/docs/x.tmp", false), Some(true));
+        assert_eq!(file.check("/repository/debug.log", false), None);
+    }
+
+    #[test]
+    fn test_entry_filter_with_nested_ignore_files() {
+        let dir = TempDir::new("ignore");
+        fs::create_dir_all(dir.join("sub/target")).unwrap();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join(".gitignore"), "*.log\ntarget/\n").unwrap();
+        fs::write(dir.join("sub/.ignore"), "!important.log\n").unwrap();
+        for name in [
+            "a.log",
+            "a.txt",
+            "sub/important.log",
+            "sub/b.log",
+            "sub/target/c.txt",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let root = dir.to_string_lossy().to_string();
+        let filter = EntryFilter {
+            hide_dotfiles: true,
+            ignore_rules: Some(IgnoreRules::load_along(&[root])),
+        };
+        let mut found: Vec<String> = vec![];
+        walk_subtree(
+            &dir,
+            &filter,
+            false,
+            &AtomicBool::new(false),
+            |entries: Vec<SubtreeEntry>| {
+                found.extend(entries.into_iter().map(|it| it.relative_path));
+                true
+            },
+        );
+        found.sort();
+        assert_eq!(found, vec!["a.txt", "sub", "sub/important.log"]);
+    }
+
+    #[test]
+    fn test_load_along_stops_at_repository_root() {
+        let dir = TempDir::new("ignore-repo-root");
+        fs::create_dir_all(dir.join("repo/.git")).unwrap();
+        fs::create_dir_all(dir.join("repo/src")).unwrap();
+        fs::write(dir.join(".gitignore"), "*.rs\n").unwrap();
+        fs::write(dir.join("repo/.gitignore"), "*.log\n").unwrap();
+        let dir_paths: Vec<String> = ["", "/repo", "/repo/src"]
+            .iter()
+            .map(|it| format!("{}{}", dir.to_string_lossy(), it))
+            .collect();
+
+        let rules = IgnoreRules::load_along(&dir_paths);
+        let src = &dir_paths[2];
+        assert!(rules.is_ignored(&format!("{}/debug.log", src), false));
+        assert!(!rules.is_ignored(&format!("{}/main.rs", src), false));
+
+        // without a repository, every directory on the way counts
+        let rules = IgnoreRules::load_along(&dir_paths[..1]);
+        assert!(rules.is_ignored(&format!("{}/main.rs", dir_paths[0]), false));
+    }
+}
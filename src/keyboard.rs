@@ -1,15 +1,49 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::{app::App, appdata::WindowFocus, logs::log, tui::Tui};
+use crate::{
+    app::App,
+    appdata::WindowFocus,
+    keymap::{Command, Key},
+    logs::log,
+    transfer::TransferMode,
+    tui::Tui,
+    viewer::ViewerPrompt,
+    vim::{VimAction, VimKeys},
+};
 
 pub fn update_on_key(app: &mut App, key_event: KeyEvent, tui: &mut Tui) {
     if handle_master_key(app, key_event) {
         return;
     }
+    if app.window_focus == WindowFocus::Tree && app.vim_mode && on_key_vim(app, key_event) {
+        return;
+    }
+    if app.window_focus == WindowFocus::Viewer && app.is_viewer_prompt_open() {
+        on_key_viewer_prompt(app, key_event);
+        return;
+    }
+    let command: Command = match app.keymap.command_for(app.window_focus, key_event) {
+        Some(command) => command,
+        None => {
+            on_unbound_key(app, key_event);
+            return;
+        }
+    };
+    // help is available in every window
+    if command == Command::ShowHelp {
+        on_show_help(app, key_event);
+        return;
+    }
     match app.window_focus {
-        WindowFocus::Tree => on_key_tree(app, key_event),
-        WindowFocus::ActionMenu => on_key_action_menu(app, key_event, tui),
-        WindowFocus::ActionMenuStep2 => on_key_action_menu_step2(app, key_event, tui),
+        WindowFocus::Tree => on_command_tree(app, command),
+        WindowFocus::ActionMenu => on_command_action_menu(app, command, tui),
+        WindowFocus::ActionMenuStep2 => on_command_action_menu_step2(app, command, tui),
+        WindowFocus::Bookmarks => on_command_bookmarks(app, command),
+        WindowFocus::History => on_command_history(app, command),
+        WindowFocus::Viewer => on_command_viewer(app, command),
+        WindowFocus::Trash => on_command_trash(app, command),
+        WindowFocus::Breadcrumbs => on_command_breadcrumbs(app, command),
+        WindowFocus::Help => on_command_help(app, command),
     }
 }
 
@@ -26,82 +60,274 @@ pub fn handle_master_key(app: &mut App, key_event: KeyEvent) -> bool {
     true
 }
 
-pub fn on_key_tree(app: &mut App, key_event: KeyEvent) {
-    match key_event.code {
-        KeyCode::Esc if !app.filter_text.is_empty() => app.clear_search_text(),
-        KeyCode::Esc => app.quit(),
-        KeyCode::Down => app.move_cursor(1),
-        KeyCode::Up => app.move_cursor(-1),
-        KeyCode::Left => app.go_up(),
-        KeyCode::Char('/') => app.go_to_root(),
-        KeyCode::Char('~') => app.go_to_home(),
-        KeyCode::Right | KeyCode::Tab => app.go_into(),
-        KeyCode::Enter if key_event.modifiers == KeyModifiers::ALT => app.open_action_dialog(),
-        KeyCode::Enter if key_event.modifiers == KeyModifiers::SHIFT => app.open_action_dialog(),
-        KeyCode::Enter if key_event.modifiers == KeyModifiers::SUPER => app.open_action_dialog(),
-        KeyCode::Enter if key_event.modifiers == KeyModifiers::META => app.open_action_dialog(),
-        KeyCode::Enter if key_event.modifiers == KeyModifiers::CONTROL => app.open_action_dialog(),
-        KeyCode::Enter => app.enter_selected_node(),
-        KeyCode::Char('>') => app.pick_current_dir(), // Shift + .
-        KeyCode::F(2) => app.rename_selected_node(),
-        KeyCode::F(4) => app.open_action_dialog(),
-        KeyCode::F(5) => app.populate_current_child_nodes(),
-        KeyCode::PageDown => app.move_cursor(20),
-        KeyCode::PageUp => app.move_cursor(-20),
-        KeyCode::Home => app.move_cursor(-(app.child_file_nodes.len() as i32)),
-        KeyCode::End => app.move_cursor(app.child_file_nodes.len() as i32),
-        KeyCode::Char('r') if is_ctrl(key_event) => app.rename_selected_node(),
-        KeyCode::Char('d') if is_ctrl(key_event) => app.delete_selected_node_confirm(),
-        KeyCode::Char('u') if is_ctrl(key_event) => app.clear_search_text(),
-        KeyCode::Char('w') if is_ctrl(key_event) => app.backspace_search_text(),
-        KeyCode::Char('o') if is_ctrl(key_event) => app.open_action_dialog(),
-        KeyCode::Backspace => app.backspace_search_text(),
-        KeyCode::Char(c) => app.type_search_text(c),
+/// Opens help, except that a character key like `?` is typed into a search that has started.
+pub fn on_show_help(app: &mut App, key_event: KeyEvent) {
+    let typing: bool = app.window_focus == WindowFocus::Tree
+        && !app.filter_text.is_empty()
+        && Key::from_event(key_event).is_printable();
+    match typing {
+        true => on_unbound_key(app, key_event),
+        false => app.open_help(),
+    }
+}
+
+/// Characters that aren't bound to any command are typed into the input of the window.
+fn on_unbound_key(app: &mut App, key_event: KeyEvent) {
+    match (app.window_focus, key_event.code) {
+        (WindowFocus::Tree, KeyCode::Char(c)) => app.type_search_text(c),
+        (WindowFocus::ActionMenuStep2, KeyCode::Char(c)) => app.action_menu_input_append(c),
+        (WindowFocus::Bookmarks, KeyCode::Char(c)) => app.type_bookmarks_filter(c),
+        (WindowFocus::History, KeyCode::Char(c)) => app.type_history_filter(c),
+        (WindowFocus::Help, KeyCode::Char(c)) => app.type_help_filter(c),
         _ => log(format!("Unknown key event: {:?}", key_event).as_str()),
     };
 }
 
-pub fn on_key_action_menu(app: &mut App, key_event: KeyEvent, tui: &mut Tui) {
-    match key_event.code {
-        KeyCode::Esc => app.close_action_dialog(),
-        KeyCode::Down => app.move_cursor(1),
-        KeyCode::Up => app.move_cursor(-1),
-        KeyCode::PageDown => app.move_cursor(20),
-        KeyCode::PageUp => app.move_cursor(-20),
-        KeyCode::Home => app.move_cursor(-(app.known_menu_actions.len() as i32)),
-        KeyCode::End => app.move_cursor(app.known_menu_actions.len() as i32),
-        KeyCode::Enter => app.execute_dialog_action(tui),
-        _ => log(format!("Unknown key event: {:?}", key_event).as_str()),
+/// Handles characters of the vim normal mode and leaving the insert mode.
+/// Returns false for keys left to the regular bindings of the tree.
+fn on_key_vim(app: &mut App, key_event: KeyEvent) -> bool {
+    let key = Key::from_event(key_event);
+    if !app.is_vim_normal_mode() {
+        if key.code == KeyCode::Esc {
+            app.enter_vim_normal_mode();
+            return true;
+        }
+        return false;
+    }
+    let c: char = match key.code {
+        KeyCode::Char(c) if key.is_printable() => c,
+        KeyCode::Esc if app.has_vim_pending_keys() => {
+            app.clear_vim_pending_keys();
+            return true;
+        }
+        _ => {
+            app.clear_vim_pending_keys();
+            return false;
+        }
+    };
+    let first_key = !app.has_vim_pending_keys();
+    match app.push_vim_key(c) {
+        VimKeys::Pending => {}
+        VimKeys::Complete { count, action } => on_vim_action(app, count, action),
+        // single characters bound in the keymap still work, e.g. `>`
+        VimKeys::Unknown if first_key => match app.keymap.command_for(WindowFocus::Tree, key_event)
+        {
+            Some(command) => on_command_tree(app, command),
+            None => log(format!("Unknown key event: {:?}", key_event).as_str()),
+        },
+        VimKeys::Unknown => {}
+    }
+    true
+}
+
+/// Runs a complete normal mode sequence, repeating motions by the count.
+pub fn on_vim_action(app: &mut App, count: Option<usize>, action: VimAction) {
+    let repeat = count.unwrap_or(1);
+    match action {
+        VimAction::Insert => app.enter_vim_insert_mode(),
+        VimAction::Search => {
+            app.clear_search_text();
+            app.enter_vim_insert_mode();
+        }
+        VimAction::Run(Command::MoveDown) => app.move_cursor(repeat as i32),
+        VimAction::Run(Command::MoveUp) => app.move_cursor(-(repeat as i32)),
+        VimAction::Run(Command::MoveTop | Command::MoveBottom) if count.is_some() => {
+            app.go_to_entry_number(repeat)
+        }
+        VimAction::Run(
+            command @ (Command::GoUp | Command::GoInto | Command::GoBack | Command::GoForward),
+        ) => {
+            for _ in 0..repeat {
+                let path_before: String = app.get_current_string_path();
+                on_command_tree(app, command);
+                if app.get_current_string_path() == path_before {
+                    break; // nowhere further to go
+                }
+            }
+        }
+        VimAction::Run(command) => on_command_tree(app, command),
+    }
+}
+
+fn on_unavailable_command(command: Command) {
+    log(format!("Command {} is not available in this window", command.name()).as_str());
+}
+
+pub fn on_command_tree(app: &mut App, command: Command) {
+    match command {
+        Command::Cancel if !app.filter_text.is_empty() => app.clear_search_text(),
+        Command::Cancel => app.quit(),
+        Command::MoveDown => app.move_cursor(1),
+        Command::MoveUp => app.move_cursor(-1),
+        Command::GoBack => app.go_back(),
+        Command::GoForward => app.go_forward(),
+        Command::Complete if app.is_path_input_mode() => app.complete_path_input(),
+        Command::Confirm if app.is_path_input_mode() => app.jump_to_typed_path(),
+        Command::GoUp => app.go_up(),
+        Command::SelectAncestor => app.open_breadcrumbs(),
+        Command::GoInto | Command::Complete => app.go_into(),
+        Command::ActionMenu => app.open_action_dialog(),
+        Command::Confirm => app.enter_selected_node(),
+        Command::PickCurrentDir => app.pick_current_dir(),
+        Command::Rename => app.rename_selected_node(),
+        Command::Refresh => app.populate_current_child_nodes(),
+        Command::PageDown => app.move_cursor(20),
+        Command::PageUp => app.move_cursor(-20),
+        Command::MoveTop => app.move_cursor(-(app.child_file_nodes.len() as i32)),
+        Command::MoveBottom => app.move_cursor(app.child_file_nodes.len() as i32),
+        Command::MoveToTrash => app.trash_selected_node(),
+        Command::DeletePermanently => app.delete_selected_node_confirm(),
+        Command::ShowTrash => app.open_trash_dialog(),
+        Command::ShowHelp => app.open_help(),
+        Command::ClearSearch => app.clear_search_text(),
+        Command::Backspace => app.backspace_search_text(),
+        Command::ToggleMark => app.toggle_mark_selected_node(),
+        Command::ToggleRecursive => app.toggle_recursive_search(),
+        Command::ToggleMatchMode => app.toggle_match_mode(),
+        Command::TogglePreview => app.toggle_preview(),
+        Command::CycleSort => app.cycle_sort_mode(),
+        Command::ReverseSort => app.toggle_sort_reverse(),
+        Command::Yank => app.register_transfer(TransferMode::Copy),
+        Command::Cut => app.register_transfer(TransferMode::Move),
+        Command::Paste => app.paste_transfer_register(),
+        Command::Undo => app.undo_last_change(),
+        Command::AddBookmark => app.add_bookmark_prompt(),
+        Command::ShowBookmarks => app.open_bookmarks_dialog(),
+        Command::ShowHistory => app.open_history_dialog(),
+        Command::ToggleLongListing => app.toggle_long_listing(),
+        Command::ToggleHidden => app.toggle_hide_dotfiles(),
+        Command::ToggleIgnored => app.toggle_respect_ignore_files(),
+        _ => on_unavailable_command(command),
+    };
+}
+
+pub fn on_command_action_menu(app: &mut App, command: Command, tui: &mut Tui) {
+    match command {
+        Command::Cancel => app.close_action_dialog(),
+        Command::MoveDown => app.move_cursor(1),
+        Command::MoveUp => app.move_cursor(-1),
+        Command::PageDown => app.move_cursor(20),
+        Command::PageUp => app.move_cursor(-20),
+        Command::MoveTop => app.move_cursor(-(app.menu_actions.len() as i32)),
+        Command::MoveBottom => app.move_cursor(app.menu_actions.len() as i32),
+        Command::Confirm => app.execute_dialog_action(tui),
+        _ => on_unavailable_command(command),
     };
 }
 
-pub fn on_key_action_menu_step2(app: &mut App, key_event: KeyEvent, tui: &mut Tui) {
+pub fn on_command_bookmarks(app: &mut App, command: Command) {
+    match command {
+        Command::Cancel => app.close_bookmarks_dialog(),
+        Command::MoveDown => app.move_cursor(1),
+        Command::MoveUp => app.move_cursor(-1),
+        Command::PageDown => app.move_cursor(20),
+        Command::PageUp => app.move_cursor(-20),
+        Command::Confirm => app.jump_to_selected_bookmark(),
+        Command::DeleteEntry => app.delete_selected_bookmark(),
+        Command::Backspace => app.backspace_bookmarks_filter(),
+        _ => on_unavailable_command(command),
+    };
+}
+
+pub fn on_command_history(app: &mut App, command: Command) {
+    match command {
+        Command::Cancel => app.close_history_dialog(),
+        Command::MoveDown => app.move_cursor(1),
+        Command::MoveUp => app.move_cursor(-1),
+        Command::PageDown => app.move_cursor(20),
+        Command::PageUp => app.move_cursor(-20),
+        Command::Confirm => app.jump_to_selected_history_entry(),
+        Command::Backspace => app.backspace_history_filter(),
+        _ => on_unavailable_command(command),
+    };
+}
+
+pub fn on_command_trash(app: &mut App, command: Command) {
+    match command {
+        Command::Cancel => app.close_trash_dialog(),
+        Command::MoveDown => app.move_cursor(1),
+        Command::MoveUp => app.move_cursor(-1),
+        Command::PageDown => app.move_cursor(20),
+        Command::PageUp => app.move_cursor(-20),
+        Command::Confirm => app.restore_selected_trash_entry(),
+        _ => on_unavailable_command(command),
+    };
+}
+
+pub fn on_command_breadcrumbs(app: &mut App, command: Command) {
+    match command {
+        Command::Cancel => app.close_breadcrumbs(),
+        Command::Confirm => app.jump_to_selected_breadcrumb(),
+        Command::CursorLeft => app.move_breadcrumb_cursor(-1),
+        Command::CursorRight => app.move_breadcrumb_cursor(1),
+        Command::LineStart => app.select_breadcrumb(0),
+        Command::LineEnd => app.select_breadcrumb(usize::MAX),
+        _ => on_unavailable_command(command),
+    };
+}
+
+pub fn on_command_help(app: &mut App, command: Command) {
+    match command {
+        Command::Cancel => app.close_help(),
+        Command::MoveDown => app.move_cursor(1),
+        Command::MoveUp => app.move_cursor(-1),
+        Command::PageDown => app.move_cursor(20),
+        Command::PageUp => app.move_cursor(-20),
+        Command::Backspace => app.backspace_help_filter(),
+        _ => on_unavailable_command(command),
+    };
+}
+
+/// The search and goto-line prompts of the viewer only take text.
+pub fn on_key_viewer_prompt(app: &mut App, key_event: KeyEvent) {
     match key_event.code {
-        KeyCode::Esc => app.close_action_dialog(),
-        KeyCode::Enter => app.execute_dialog_action_step2(tui),
-        KeyCode::Char('u') if is_ctrl(key_event) => app.action_menu_input_clear_backwards(),
-        KeyCode::Char('k') if is_ctrl(key_event) => app.action_menu_input_clear_forward(),
-        KeyCode::Char('w') if is_ctrl(key_event) => app.action_menu_input_backspace_word(),
-        KeyCode::Backspace if is_ctrl(key_event) => app.action_menu_input_backspace_word(),
-        KeyCode::Backspace if is_alt(key_event) => app.action_menu_input_backspace_word(),
-        KeyCode::Delete if is_ctrl(key_event) => app.action_menu_input_delete_word(),
-        KeyCode::Backspace => app.action_menu_input_backspace(),
-        KeyCode::Delete => app.action_menu_input_delete(),
-        KeyCode::Char(c) => app.action_menu_input_append(c),
-        KeyCode::Left if is_ctrl(key_event) => app.action_menu_input_left_word(),
-        KeyCode::Right if is_ctrl(key_event) => app.action_menu_input_right_word(),
-        KeyCode::Left => app.action_menu_input_left(),
-        KeyCode::Right => app.action_menu_input_right(),
-        KeyCode::Home => app.action_menu_input_home(),
-        KeyCode::End => app.action_menu_input_end(),
+        KeyCode::Esc => app.close_viewer_prompt(),
+        KeyCode::Enter => app.submit_viewer_prompt(),
+        KeyCode::Backspace => app.backspace_viewer_prompt(),
+        KeyCode::Char(c) => app.type_viewer_prompt(c),
         _ => log(format!("Unknown key event: {:?}", key_event).as_str()),
     };
 }
 
-fn is_ctrl(key_event: KeyEvent) -> bool {
-    key_event.modifiers == KeyModifiers::CONTROL
+pub fn on_command_viewer(app: &mut App, command: Command) {
+    match command {
+        Command::Cancel => app.close_viewer(),
+        Command::MoveDown => app.scroll_viewer(1),
+        Command::MoveUp => app.scroll_viewer(-1),
+        Command::ScrollRight => app.scroll_viewer_horizontally(8),
+        Command::ScrollLeft => app.scroll_viewer_horizontally(-8),
+        Command::PageDown => app.scroll_viewer_page(1),
+        Command::PageUp => app.scroll_viewer_page(-1),
+        Command::MoveTop => app.viewer_go_to_top(),
+        Command::MoveBottom => app.viewer_go_to_bottom(),
+        Command::Search => app.open_viewer_prompt(ViewerPrompt::Search),
+        Command::GotoLine => app.open_viewer_prompt(ViewerPrompt::GotoLine),
+        Command::NextMatch => app.viewer_search_next(true),
+        Command::PreviousMatch => app.viewer_search_next(false),
+        _ => on_unavailable_command(command),
+    };
+}
+
+pub fn on_command_action_menu_step2(app: &mut App, command: Command, tui: &mut Tui) {
+    match command {
+        Command::Cancel => app.close_action_dialog(),
+        Command::Confirm => app.execute_dialog_action_step2(tui),
+        Command::ClearBackwards => app.action_menu_input_clear_backwards(),
+        Command::ClearForward => app.action_menu_input_clear_forward(),
+        Command::BackspaceWord => app.action_menu_input_backspace_word(),
+        Command::DeleteWord => app.action_menu_input_delete_word(),
+        Command::Backspace => app.action_menu_input_backspace(),
+        Command::DeleteChar => app.action_menu_input_delete(),
+        Command::WordLeft => app.action_menu_input_left_word(),
+        Command::WordRight => app.action_menu_input_right_word(),
+        Command::CursorLeft => app.action_menu_input_left(),
+        Command::CursorRight => app.action_menu_input_right(),
+        Command::LineStart => app.action_menu_input_home(),
+        Command::LineEnd => app.action_menu_input_end(),
+        _ => on_unavailable_command(command),
+    };
 }
 
-fn is_alt(key_event: KeyEvent) -> bool {
-    key_event.modifiers == KeyModifiers::ALT
+fn is_ctrl(key_event: KeyEvent) -> bool {
+    key_event.modifiers == KeyModifiers::CONTROL
 }
@@ -0,0 +1,782 @@
+use anyhow::{anyhow, Context, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::appdata::WindowFocus;
+
+/// Action triggered by a key. What it does depends on the focused window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+    Cancel, // closes the popup, clears the search text or quits
+    Confirm,
+    MoveUp,
+    MoveDown,
+    PageUp,
+    PageDown,
+    MoveTop,
+    MoveBottom,
+    GoBack,
+    GoForward,
+    GoUp,
+    GoInto,
+    SelectAncestor, // focuses the path bar to jump to an ancestor directory
+    Complete,       // completes the typed path, or goes into the selected directory
+    ActionMenu,
+    PickCurrentDir,
+    Rename,
+    Refresh,
+    MoveToTrash,
+    DeletePermanently,
+    ShowTrash,
+    ClearSearch,
+    Backspace,
+    ToggleMark,
+    ToggleRecursive,
+    ToggleMatchMode,
+    TogglePreview,
+    CycleSort,
+    ReverseSort,
+    Yank,
+    Cut,
+    Paste,
+    Undo,
+    AddBookmark,
+    ShowBookmarks,
+    ShowHistory,
+    ToggleLongListing,
+    ToggleHidden,
+    ToggleIgnored,
+    DeleteEntry,
+    DeleteChar,
+    BackspaceWord,
+    DeleteWord,
+    ClearBackwards,
+    ClearForward,
+    CursorLeft,
+    CursorRight,
+    WordLeft,
+    WordRight,
+    LineStart,
+    LineEnd,
+    ScrollLeft,
+    ScrollRight,
+    Search,
+    GotoLine,
+    NextMatch,
+    PreviousMatch,
+    ShowHelp,
+}
+
+/// Every command, in the order of declaration. A test checks that none is missing.
+pub const COMMANDS: [Command; 58] = [
+    Command::Cancel,
+    Command::Confirm,
+    Command::MoveUp,
+    Command::MoveDown,
+    Command::PageUp,
+    Command::PageDown,
+    Command::MoveTop,
+    Command::MoveBottom,
+    Command::GoBack,
+    Command::GoForward,
+    Command::GoUp,
+    Command::GoInto,
+    Command::SelectAncestor,
+    Command::Complete,
+    Command::ActionMenu,
+    Command::PickCurrentDir,
+    Command::Rename,
+    Command::Refresh,
+    Command::MoveToTrash,
+    Command::DeletePermanently,
+    Command::ShowTrash,
+    Command::ClearSearch,
+    Command::Backspace,
+    Command::ToggleMark,
+    Command::ToggleRecursive,
+    Command::ToggleMatchMode,
+    Command::TogglePreview,
+    Command::CycleSort,
+    Command::ReverseSort,
+    Command::Yank,
+    Command::Cut,
+    Command::Paste,
+    Command::Undo,
+    Command::AddBookmark,
+    Command::ShowBookmarks,
+    Command::ShowHistory,
+    Command::ToggleLongListing,
+    Command::ToggleHidden,
+    Command::ToggleIgnored,
+    Command::DeleteEntry,
+    Command::DeleteChar,
+    Command::BackspaceWord,
+    Command::DeleteWord,
+    Command::ClearBackwards,
+    Command::ClearForward,
+    Command::CursorLeft,
+    Command::CursorRight,
+    Command::WordLeft,
+    Command::WordRight,
+    Command::LineStart,
+    Command::LineEnd,
+    Command::ScrollLeft,
+    Command::ScrollRight,
+    Command::Search,
+    Command::GotoLine,
+    Command::NextMatch,
+    Command::PreviousMatch,
+    Command::ShowHelp,
+];
+
+impl Command {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Cancel => "cancel",
+            Command::Confirm => "confirm",
+            Command::MoveUp => "move_up",
+            Command::MoveDown => "move_down",
+            Command::PageUp => "page_up",
+            Command::PageDown => "page_down",
+            Command::MoveTop => "move_top",
+            Command::MoveBottom => "move_bottom",
+            Command::GoBack => "go_back",
+            Command::GoForward => "go_forward",
+            Command::GoUp => "go_up",
+            Command::GoInto => "go_into",
+            Command::SelectAncestor => "select_ancestor",
+            Command::Complete => "complete",
+            Command::ActionMenu => "action_menu",
+            Command::PickCurrentDir => "pick_current_dir",
+            Command::Rename => "rename",
+            Command::Refresh => "refresh",
+            Command::MoveToTrash => "move_to_trash",
+            Command::DeletePermanently => "delete_permanently",
+            Command::ShowTrash => "show_trash",
+            Command::ClearSearch => "clear_search",
+            Command::Backspace => "backspace",
+            Command::ToggleMark => "toggle_mark",
+            Command::ToggleRecursive => "toggle_recursive",
+            Command::ToggleMatchMode => "toggle_match_mode",
+            Command::TogglePreview => "toggle_preview",
+            Command::CycleSort => "cycle_sort",
+            Command::ReverseSort => "reverse_sort",
+            Command::Yank => "yank",
+            Command::Cut => "cut",
+            Command::Paste => "paste",
+            Command::Undo => "undo",
+            Command::AddBookmark => "add_bookmark",
+            Command::ShowBookmarks => "show_bookmarks",
+            Command::ShowHistory => "show_history",
+            Command::ToggleLongListing => "toggle_long_listing",
+            Command::ToggleHidden => "toggle_hidden",
+            Command::ToggleIgnored => "toggle_ignored",
+            Command::DeleteEntry => "delete_entry",
+            Command::DeleteChar => "delete_char",
+            Command::BackspaceWord => "backspace_word",
+            Command::DeleteWord => "delete_word",
+            Command::ClearBackwards => "clear_backwards",
+            Command::ClearForward => "clear_forward",
+            Command::CursorLeft => "cursor_left",
+            Command::CursorRight => "cursor_right",
+            Command::WordLeft => "word_left",
+            Command::WordRight => "word_right",
+            Command::LineStart => "line_start",
+            Command::LineEnd => "line_end",
+            Command::ScrollLeft => "scroll_left",
+            Command::ScrollRight => "scroll_right",
+            Command::Search => "search",
+            Command::GotoLine => "goto_line",
+            Command::NextMatch => "next_match",
+            Command::PreviousMatch => "previous_match",
+            Command::ShowHelp => "show_help",
+        }
+    }
+
+    /// What the command does in the window, as listed in the help.
+    pub fn description(&self, focus: WindowFocus) -> &'static str {
+        match (focus, self) {
+            (WindowFocus::Tree, Command::Cancel) => "Clear the search or exit",
+            (WindowFocus::Help, Command::Cancel) => "Close the key bindings",
+            (WindowFocus::Viewer, Command::Cancel) => "Close the viewer",
+            (_, Command::Cancel) => "Close the window",
+            (WindowFocus::Tree, Command::Confirm) => "Pick or enter the selected entry",
+            (WindowFocus::ActionMenu, Command::Confirm) => "Run the selected action",
+            (WindowFocus::ActionMenuStep2, Command::Confirm) => "Accept the typed text",
+            (WindowFocus::Bookmarks, Command::Confirm) => "Go to the selected bookmark",
+            (WindowFocus::History, Command::Confirm) => "Go to the selected path",
+            (WindowFocus::Trash, Command::Confirm) => "Restore the selected entry",
+            (WindowFocus::Breadcrumbs, Command::Confirm) => "Go to the selected directory",
+            (_, Command::Confirm) => "Confirm",
+            (WindowFocus::Viewer, Command::MoveUp) => "Scroll up",
+            (WindowFocus::Viewer, Command::MoveDown) => "Scroll down",
+            (WindowFocus::Viewer, Command::PageUp) => "Scroll a page up",
+            (WindowFocus::Viewer, Command::PageDown) => "Scroll a page down",
+            (WindowFocus::Breadcrumbs, Command::CursorLeft) => "Select the parent directory",
+            (WindowFocus::Breadcrumbs, Command::CursorRight) => "Select the child directory",
+            (WindowFocus::Breadcrumbs, Command::LineStart) => "Select the root directory",
+            (WindowFocus::Breadcrumbs, Command::LineEnd) => "Select the current directory",
+            (WindowFocus::Bookmarks, Command::DeleteEntry) => "Remove the selected bookmark",
+            (_, command) => command.generic_description(),
+        }
+    }
+
+    fn generic_description(&self) -> &'static str {
+        match self {
+            Command::Cancel => "Close the window",
+            Command::Confirm => "Confirm",
+            Command::MoveUp => "Move up",
+            Command::MoveDown => "Move down",
+            Command::PageUp => "Move a page up",
+            Command::PageDown => "Move a page down",
+            Command::MoveTop => "Move to the top",
+            Command::MoveBottom => "Move to the bottom",
+            Command::GoBack => "Go back to the previously visited directory",
+            Command::GoForward => "Go forward through visited directories",
+            Command::GoUp => "Go up to the parent directory",
+            Command::GoInto => "Enter the selected directory",
+            Command::SelectAncestor => "Select an ancestor directory in the path bar",
+            Command::Complete => "Complete the typed path or enter the selected directory",
+            Command::ActionMenu => "Open the action menu",
+            Command::PickCurrentDir => "Pick the current directory",
+            Command::Rename => "Rename the selected entry",
+            Command::Refresh => "Reload the directory",
+            Command::MoveToTrash => "Move the selected entry to the trash",
+            Command::DeletePermanently => "Delete the selected entry permanently",
+            Command::ShowTrash => "Show the trash",
+            Command::ClearSearch => "Clear the search",
+            Command::Backspace => "Delete the last character of the filter",
+            Command::ToggleMark => "Mark or unmark the selected entry",
+            Command::ToggleRecursive => "Search recursively in subdirectories",
+            Command::ToggleMatchMode => "Switch between fuzzy and exact matching",
+            Command::TogglePreview => "Show or hide the preview panel",
+            Command::CycleSort => "Change the sort order",
+            Command::ReverseSort => "Reverse the sort order",
+            Command::Yank => "Copy the selected entries",
+            Command::Cut => "Cut the selected entries",
+            Command::Paste => "Paste the copied or cut entries",
+            Command::Undo => "Undo the last change",
+            Command::AddBookmark => "Bookmark the current directory",
+            Command::ShowBookmarks => "Show bookmarks",
+            Command::ShowHistory => "Show recently visited directories",
+            Command::ToggleLongListing => "Show or hide size, date and permissions",
+            Command::ToggleHidden => "Show or hide hidden entries",
+            Command::ToggleIgnored => "Show or hide git-ignored entries",
+            Command::DeleteEntry => "Delete the selected entry",
+            Command::DeleteChar => "Delete the character under the cursor",
+            Command::BackspaceWord => "Delete the previous word",
+            Command::DeleteWord => "Delete the next word",
+            Command::ClearBackwards => "Delete everything before the cursor",
+            Command::ClearForward => "Delete everything after the cursor",
+            Command::CursorLeft => "Move the cursor left",
+            Command::CursorRight => "Move the cursor right",
+            Command::WordLeft => "Move the cursor a word left",
+            Command::WordRight => "Move the cursor a word right",
+            Command::LineStart => "Move the cursor to the start",
+            Command::LineEnd => "Move the cursor to the end",
+            Command::ScrollLeft => "Scroll left",
+            Command::ScrollRight => "Scroll right",
+            Command::Search => "Search in the file",
+            Command::GotoLine => "Go to a line number",
+            Command::NextMatch => "Go to the next match",
+            Command::PreviousMatch => "Go to the previous match",
+            Command::ShowHelp => "Show the key bindings",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Command> {
+        COMMANDS.into_iter().find(|command| command.name() == name)
+    }
+}
+
+const TREE_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("enter", Command::Confirm),
+    ("up", Command::MoveUp),
+    ("down", Command::MoveDown),
+    ("pageup", Command::PageUp),
+    ("pagedown", Command::PageDown),
+    ("home", Command::MoveTop),
+    ("end", Command::MoveBottom),
+    ("alt+left", Command::GoBack),
+    ("alt+right", Command::GoForward),
+    ("left", Command::GoUp),
+    ("right", Command::GoInto),
+    ("alt+up", Command::SelectAncestor),
+    ("tab", Command::Complete),
+    ("alt+enter", Command::ActionMenu),
+    ("shift+enter", Command::ActionMenu),
+    ("super+enter", Command::ActionMenu),
+    ("meta+enter", Command::ActionMenu),
+    ("ctrl+enter", Command::ActionMenu),
+    ("ctrl+o", Command::ActionMenu),
+    ("f4", Command::ActionMenu),
+    (">", Command::PickCurrentDir),
+    ("f2", Command::Rename),
+    ("ctrl+r", Command::Rename),
+    ("f5", Command::Refresh),
+    ("ctrl+d", Command::MoveToTrash),
+    ("delete", Command::MoveToTrash),
+    ("alt+d", Command::DeletePermanently),
+    ("alt+t", Command::ShowTrash),
+    ("ctrl+u", Command::ClearSearch),
+    ("backspace", Command::Backspace),
+    ("ctrl+w", Command::Backspace),
+    ("ctrl+t", Command::ToggleMark),
+    ("insert", Command::ToggleMark),
+    ("ctrl+f", Command::ToggleRecursive),
+    ("ctrl+e", Command::ToggleMatchMode),
+    ("ctrl+p", Command::TogglePreview),
+    ("ctrl+s", Command::CycleSort),
+    ("alt+s", Command::ReverseSort),
+    ("ctrl+y", Command::Yank),
+    ("ctrl+x", Command::Cut),
+    ("ctrl+v", Command::Paste),
+    ("ctrl+z", Command::Undo),
+    ("ctrl+b", Command::AddBookmark),
+    ("alt+b", Command::ShowBookmarks),
+    ("alt+r", Command::ShowHistory),
+    ("alt+l", Command::ToggleLongListing),
+    ("alt+h", Command::ToggleHidden),
+    ("alt+i", Command::ToggleIgnored),
+    ("f1", Command::ShowHelp),
+    ("?", Command::ShowHelp),
+];
+
+const ACTION_MENU_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("enter", Command::Confirm),
+    ("up", Command::MoveUp),
+    ("down", Command::MoveDown),
+    ("pageup", Command::PageUp),
+    ("pagedown", Command::PageDown),
+    ("home", Command::MoveTop),
+    ("end", Command::MoveBottom),
+    ("f1", Command::ShowHelp),
+    ("?", Command::ShowHelp),
+];
+
+const PROMPT_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("enter", Command::Confirm),
+    ("ctrl+u", Command::ClearBackwards),
+    ("ctrl+k", Command::ClearForward),
+    ("ctrl+w", Command::BackspaceWord),
+    ("ctrl+backspace", Command::BackspaceWord),
+    ("alt+backspace", Command::BackspaceWord),
+    ("ctrl+delete", Command::DeleteWord),
+    ("backspace", Command::Backspace),
+    ("delete", Command::DeleteChar),
+    ("ctrl+left", Command::WordLeft),
+    ("ctrl+right", Command::WordRight),
+    ("left", Command::CursorLeft),
+    ("right", Command::CursorRight),
+    ("home", Command::LineStart),
+    ("end", Command::LineEnd),
+    ("f1", Command::ShowHelp),
+];
+
+const BOOKMARKS_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("enter", Command::Confirm),
+    ("up", Command::MoveUp),
+    ("down", Command::MoveDown),
+    ("pageup", Command::PageUp),
+    ("pagedown", Command::PageDown),
+    ("delete", Command::DeleteEntry),
+    ("ctrl+d", Command::DeleteEntry),
+    ("backspace", Command::Backspace),
+    ("f1", Command::ShowHelp),
+];
+
+const HISTORY_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("enter", Command::Confirm),
+    ("up", Command::MoveUp),
+    ("down", Command::MoveDown),
+    ("pageup", Command::PageUp),
+    ("pagedown", Command::PageDown),
+    ("backspace", Command::Backspace),
+    ("f1", Command::ShowHelp),
+];
+
+const TRASH_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("enter", Command::Confirm),
+    ("up", Command::MoveUp),
+    ("down", Command::MoveDown),
+    ("pageup", Command::PageUp),
+    ("pagedown", Command::PageDown),
+    ("f1", Command::ShowHelp),
+    ("?", Command::ShowHelp),
+];
+
+const BREADCRUMBS_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("enter", Command::Confirm),
+    ("left", Command::CursorLeft),
+    ("right", Command::CursorRight),
+    ("home", Command::LineStart),
+    ("end", Command::LineEnd),
+    ("f1", Command::ShowHelp),
+    ("?", Command::ShowHelp),
+];
+
+const HELP_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("enter", Command::Cancel),
+    ("f1", Command::Cancel),
+    ("up", Command::MoveUp),
+    ("down", Command::MoveDown),
+    ("pageup", Command::PageUp),
+    ("pagedown", Command::PageDown),
+    ("backspace", Command::Backspace),
+];
+
+const VIEWER_BINDINGS: &[(&str, Command)] = &[
+    ("esc", Command::Cancel),
+    ("q", Command::Cancel),
+    ("down", Command::MoveDown),
+    ("j", Command::MoveDown),
+    ("up", Command::MoveUp),
+    ("k", Command::MoveUp),
+    ("right", Command::ScrollRight),
+    ("l", Command::ScrollRight),
+    ("left", Command::ScrollLeft),
+    ("h", Command::ScrollLeft),
+    ("pagedown", Command::PageDown),
+    ("space", Command::PageDown),
+    ("pageup", Command::PageUp),
+    ("b", Command::PageUp),
+    ("ctrl+g", Command::GotoLine),
+    ("home", Command::MoveTop),
+    ("g", Command::MoveTop),
+    ("end", Command::MoveBottom),
+    ("G", Command::MoveBottom),
+    ("/", Command::Search),
+    (":", Command::GotoLine),
+    ("n", Command::NextMatch),
+    ("N", Command::PreviousMatch),
+    ("f1", Command::ShowHelp),
+    ("?", Command::ShowHelp),
+];
+
+pub const KEYMAP_FOCUSES: [WindowFocus; 9] = [
+    WindowFocus::Tree,
+    WindowFocus::ActionMenu,
+    WindowFocus::ActionMenuStep2,
+    WindowFocus::Bookmarks,
+    WindowFocus::History,
+    WindowFocus::Trash,
+    WindowFocus::Viewer,
+    WindowFocus::Breadcrumbs,
+    WindowFocus::Help,
+];
+
+/// Name of the keymap file section configuring the window.
+pub fn focus_name(focus: WindowFocus) -> &'static str {
+    match focus {
+        WindowFocus::Tree => "tree",
+        WindowFocus::ActionMenu => "action_menu",
+        WindowFocus::ActionMenuStep2 => "prompt",
+        WindowFocus::Bookmarks => "bookmarks",
+        WindowFocus::History => "history",
+        WindowFocus::Trash => "trash",
+        WindowFocus::Viewer => "viewer",
+        WindowFocus::Breadcrumbs => "breadcrumbs",
+        WindowFocus::Help => "help",
+    }
+}
+
+/// Windows where unbound characters are typed into a search or prompt input.
+pub fn is_typing_focus(focus: WindowFocus) -> bool {
+    matches!(
+        focus,
+        WindowFocus::Tree
+            | WindowFocus::ActionMenuStep2
+            | WindowFocus::Bookmarks
+            | WindowFocus::History
+            | WindowFocus::Help
+    )
+}
+
+fn default_bindings(focus: WindowFocus) -> &'static [(&'static str, Command)] {
+    match focus {
+        WindowFocus::Tree => TREE_BINDINGS,
+        WindowFocus::ActionMenu => ACTION_MENU_BINDINGS,
+        WindowFocus::ActionMenuStep2 => PROMPT_BINDINGS,
+        WindowFocus::Bookmarks => BOOKMARKS_BINDINGS,
+        WindowFocus::History => HISTORY_BINDINGS,
+        WindowFocus::Trash => TRASH_BINDINGS,
+        WindowFocus::Viewer => VIEWER_BINDINGS,
+        WindowFocus::Breadcrumbs => BREADCRUMBS_BINDINGS,
+        WindowFocus::Help => HELP_BINDINGS,
+    }
+}
+
+/// Key with its modifiers. Shift is left out for characters, as it's already in the character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn from_event(key_event: KeyEvent) -> Key {
+        let mut modifiers = key_event.modifiers;
+        if let KeyCode::Char(_) = key_event.code {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Key {
+            code: key_event.code,
+            modifiers,
+        }
+    }
+
+    /// Parses a key like `ctrl+r`, `alt+enter`, `f2`, `G` or `space`.
+    pub fn parse(text: &str) -> Option<Key> {
+        let (modifier_names, key_name) = match text.strip_suffix("++") {
+            Some(modifier_names) => (modifier_names, "+"),
+            None => match text.rsplit_once('+') {
+                Some((modifier_names, key_name)) if !key_name.is_empty() => {
+                    (modifier_names, key_name)
+                }
+                _ => ("", text),
+            },
+        };
+        let mut modifiers = KeyModifiers::NONE;
+        for name in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match name.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                "super" => KeyModifiers::SUPER,
+                "meta" => KeyModifiers::META,
+                _ => return None,
+            };
+        }
+        let mut chars = key_name.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key_name.to_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "space" => KeyCode::Char(' '),
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return None,
+                },
+            },
+        };
+        if let KeyCode::Char(c) = code {
+            if modifiers.contains(KeyModifiers::SHIFT) && c.is_ascii_lowercase() {
+                return Some(Key {
+                    code: KeyCode::Char(c.to_ascii_uppercase()),
+                    modifiers: modifiers - KeyModifiers::SHIFT,
+                });
+            }
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Some(Key { code, modifiers })
+    }
+
+    /// Name of the key in the keymap file format.
+    pub fn name(&self) -> String {
+        let mut name = String::new();
+        for (modifier, modifier_name) in [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+            (KeyModifiers::SUPER, "super+"),
+            (KeyModifiers::META, "meta+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                name.push_str(modifier_name);
+            }
+        }
+        match self.code {
+            KeyCode::Char(' ') => name.push_str("space"),
+            KeyCode::Char(c) => name.push(c),
+            KeyCode::F(n) => name.push_str(&format!("f{}", n)),
+            code => name.push_str(&format!("{:?}", code).to_lowercase()),
+        }
+        name
+    }
+
+    /// Plain character keys are typed into the search or prompt input when they're not bound.
+    pub fn is_printable(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_)) && self.modifiers.is_empty()
+    }
+}
+
+/// Key bindings of every window, in the order they're listed in help.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    pub bindings: HashMap<WindowFocus, Vec<(Key, Command)>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = KEYMAP_FOCUSES
+            .into_iter()
+            .map(|focus| {
+                let focus_bindings = default_bindings(focus)
+                    .iter()
+                    .map(|(key_name, command)| (Key::parse(key_name).unwrap(), *command))
+                    .collect();
+                (focus, focus_bindings)
+            })
+            .collect();
+        Keymap { bindings }
+    }
+}
+
+/// Content of the keymap file: a section per window, mapping keys to command names.
+/// Command `none` unbinds the key.
+pub type KeymapConfig = BTreeMap<String, BTreeMap<String, String>>;
+
+pub const UNBOUND_COMMAND: &str = "none";
+
+impl Keymap {
+    pub fn command_for(&self, focus: WindowFocus, key_event: KeyEvent) -> Option<Command> {
+        self.command_for_key(focus, Key::from_event(key_event))
+    }
+
+    fn command_for_key(&self, focus: WindowFocus, key: Key) -> Option<Command> {
+        self.bindings
+            .get(&focus)?
+            .iter()
+            .find(|(bound_key, _)| *bound_key == key)
+            .map(|(_, command)| *command)
+    }
+
+    pub fn keys_for(&self, focus: WindowFocus, command: Command) -> Vec<Key> {
+        self.bindings
+            .get(&focus)
+            .map(|bindings| {
+                bindings
+                    .iter()
+                    .filter(|(_, bound_command)| *bound_command == command)
+                    .map(|(key, _)| *key)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Binds the key to the command, replacing the previous binding of the key.
+    pub fn bind(&mut self, focus: WindowFocus, key: Key, command: Command) {
+        let bindings = self.bindings.entry(focus).or_default();
+        match bindings.iter_mut().find(|(bound_key, _)| *bound_key == key) {
+            Some(binding) => binding.1 = command,
+            None => bindings.push((key, command)),
+        }
+    }
+
+    pub fn unbind(&mut self, focus: WindowFocus, key: Key) {
+        if let Some(bindings) = self.bindings.get_mut(&focus) {
+            bindings.retain(|(bound_key, _)| *bound_key != key);
+        }
+    }
+
+    /// Applies the user bindings on top of the current ones.
+    /// Invalid entries fail the whole keymap, while conflicts are only reported:
+    /// the same key given twice, or a command left without any key by rebinding.
+    pub fn apply_config(&mut self, config: &KeymapConfig) -> Result<Vec<String>> {
+        let mut conflicts: Vec<String> = Vec::new();
+        for (section, entries) in config {
+            let focus: WindowFocus = KEYMAP_FOCUSES
+                .into_iter()
+                .find(|focus| focus_name(*focus) == section)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "unknown keymap section [{}], available sections: {}",
+                        section,
+                        KEYMAP_FOCUSES.map(focus_name).join(", ")
+                    )
+                })?;
+            let available_commands: Vec<Command> = default_bindings(focus)
+                .iter()
+                .map(|(_, command)| *command)
+                .collect();
+            let mut assigned: HashMap<Key, &String> = HashMap::new();
+            let mut displaced: Vec<(Key, Command)> = Vec::new();
+            for (key_name, command_name) in entries {
+                let key = Key::parse(key_name)
+                    .ok_or_else(|| anyhow!("invalid key '{}' in [{}]", key_name, section))?;
+                if let Some(previous_name) = assigned.insert(key, key_name) {
+                    conflicts.push(format!(
+                        "[{}] '{}' and '{}' are the same key, '{}' is ignored",
+                        section, previous_name, key_name, previous_name
+                    ));
+                }
+                if command_name == UNBOUND_COMMAND {
+                    self.unbind(focus, key);
+                    continue;
+                }
+                let command = Command::parse(command_name)
+                    .filter(|command| available_commands.contains(command))
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "unknown command '{}' for key '{}' in [{}]",
+                            command_name,
+                            key_name,
+                            section
+                        )
+                    })?;
+                if let Some(replaced) = self.command_for_key(focus, key) {
+                    displaced.push((key, replaced));
+                }
+                self.bind(focus, key, command);
+                if key.is_printable() && is_typing_focus(focus) {
+                    conflicts.push(format!(
+                        "[{}] '{}' is bound to '{}', so it can't be typed anymore",
+                        section, key_name, command_name
+                    ));
+                }
+            }
+            for (key, command) in displaced {
+                if self.keys_for(focus, command).is_empty() {
+                    conflicts.push(format!(
+                        "[{}] '{}' was the only key of '{}', which is no longer bound",
+                        section,
+                        key.name(),
+                        command.name()
+                    ));
+                }
+            }
+        }
+        Ok(conflicts)
+    }
+}
+
+/// Reads the keymap file, a missing file leaves the default bindings.
+/// Returns the keymap and the conflicts found in the file.
+pub fn load_keymap(path: &Path) -> Result<(Keymap, Vec<String>)> {
+    let mut keymap = Keymap::default();
+    if !path.exists() {
+        return Ok((keymap, vec![]));
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read keymap file '{}'", path.to_string_lossy()))?;
+    let config: KeymapConfig = toml::from_str(&content)
+        .with_context(|| format!("invalid keymap file '{}'", path.to_string_lossy()))?;
+    let conflicts = keymap
+        .apply_config(&config)
+        .with_context(|| format!("invalid keymap file '{}'", path.to_string_lossy()))?;
+    Ok((keymap, conflicts))
+}
@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::appdata::WindowFocus;
+    use crate::keymap::{Command, Key, Keymap, KeymapConfig, COMMANDS, KEYMAP_FOCUSES};
+
+    fn key_event(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn parse_config(content: &str) -> KeymapConfig {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn test_parse_key() {
+        let key = |code, modifiers| Some(Key { code, modifiers });
+        assert_eq!(
+            Key::parse("ctrl+r"),
+            key(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            Key::parse("Alt+Enter"),
+            key(KeyCode::Enter, KeyModifiers::ALT)
+        );
+        assert_eq!(Key::parse("f2"), key(KeyCode::F(2), KeyModifiers::NONE));
+        assert_eq!(Key::parse("G"), key(KeyCode::Char('G'), KeyModifiers::NONE));
+        assert_eq!(
+            Key::parse("shift+g"),
+            key(KeyCode::Char('G'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            Key::parse("space"),
+            key(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(Key::parse("+"), key(KeyCode::Char('+'), KeyModifiers::NONE));
+        assert_eq!(
+            Key::parse("ctrl++"),
+            key(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(Key::parse("hyper+x"), None);
+        assert_eq!(Key::parse("f99"), None);
+        assert_eq!(Key::parse("enterr"), None);
+    }
+
+    #[test]
+    fn test_key_names_round_trip() {
+        for focus in KEYMAP_FOCUSES {
+            for (key, _) in &Keymap::default().bindings[&focus] {
+                assert_eq!(Key::parse(&key.name()), Some(*key), "{}", key.name());
+            }
+        }
+        for command in COMMANDS {
+            assert_eq!(Command::parse(command.name()), Some(command));
+        }
+    }
+
+    /// Position of the command in `COMMANDS`. The match fails to compile on a new command,
+    /// until it's listed here and in `COMMANDS` as well.
+    fn command_index(command: Command) -> usize {
+        match command {
+            Command::Cancel => 0,
+            Command::Confirm => 1,
+            Command::MoveUp => 2,
+            Command::MoveDown => 3,
+            Command::PageUp => 4,
+            Command::PageDown => 5,
+            Command::MoveTop => 6,
+            Command::MoveBottom => 7,
+            Command::GoBack => 8,
+            Command::GoForward => 9,
+            Command::GoUp => 10,
+            Command::GoInto => 11,
+            Command::SelectAncestor => 12,
+            Command::Complete => 13,
+            Command::ActionMenu => 14,
+            Command::PickCurrentDir => 15,
+            Command::Rename => 16,
+            Command::Refresh => 17,
+            Command::MoveToTrash => 18,
+            Command::DeletePermanently => 19,
+            Command::ShowTrash => 20,
+            Command::ClearSearch => 21,
+            Command::Backspace => 22,
+            Command::ToggleMark => 23,
+            Command::ToggleRecursive => 24,
+            Command::ToggleMatchMode => 25,
+            Command::TogglePreview => 26,
+            Command::CycleSort => 27,
+            Command::ReverseSort => 28,
+            Command::Yank => 29,
+            Command::Cut => 30,
+            Command::Paste => 31,
+            Command::Undo => 32,
+            Command::AddBookmark => 33,
+            Command::ShowBookmarks => 34,
+            Command::ShowHistory => 35,
+            Command::ToggleLongListing => 36,
+            Command::ToggleHidden => 37,
+            Command::ToggleIgnored => 38,
+            Command::DeleteEntry => 39,
+            Command::DeleteChar => 40,
+            Command::BackspaceWord => 41,
+            Command::DeleteWord => 42,
+            Command::ClearBackwards => 43,
+            Command::ClearForward => 44,
+            Command::CursorLeft => 45,
+            Command::CursorRight => 46,
+            Command::WordLeft => 47,
+            Command::WordRight => 48,
+            Command::LineStart => 49,
+            Command::LineEnd => 50,
+            Command::ScrollLeft => 51,
+            Command::ScrollRight => 52,
+            Command::Search => 53,
+            Command::GotoLine => 54,
+            Command::NextMatch => 55,
+            Command::PreviousMatch => 56,
+            Command::ShowHelp => 57,
+        }
+    }
+
+    #[test]
+    fn test_commands_list_every_variant() {
+        for (index, command) in COMMANDS.into_iter().enumerate() {
+            assert_eq!(command_index(command), index, "{:?}", command);
+        }
+    }
+
+    #[test]
+    fn test_default_bindings() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.command_for(
+                WindowFocus::Tree,
+                key_event(KeyCode::Char('r'), KeyModifiers::CONTROL)
+            ),
+            Some(Command::Rename)
+        );
+        // shift is part of the character
+        assert_eq!(
+            keymap.command_for(
+                WindowFocus::Viewer,
+                key_event(KeyCode::Char('G'), KeyModifiers::SHIFT)
+            ),
+            Some(Command::MoveBottom)
+        );
+        assert_eq!(
+            keymap.command_for(
+                WindowFocus::Tree,
+                key_event(KeyCode::Char('j'), KeyModifiers::NONE)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_apply_config() {
+        let mut keymap = Keymap::default();
+        let config = parse_config(
+            r#"
+            [tree]
+            "ctrl+n" = "rename"
+            "f2" = "none"
+            [viewer]
+            "ctrl+d" = "page_down"
+            "#,
+        );
+        let conflicts = keymap.apply_config(&config).unwrap();
+        assert!(conflicts.is_empty(), "{:?}", conflicts);
+        assert_eq!(
+            keymap.keys_for(WindowFocus::Tree, Command::Rename),
+            vec![Key::parse("ctrl+r").unwrap(), Key::parse("ctrl+n").unwrap()]
+        );
+        assert_eq!(
+            keymap.command_for(
+                WindowFocus::Viewer,
+                key_event(KeyCode::Char('d'), KeyModifiers::CONTROL)
+            ),
+            Some(Command::PageDown)
+        );
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let mut keymap = Keymap::default();
+        let config = parse_config(
+            r#"
+            [tree]
+            "ctrl+e" = "undo"
+            "control+e" = "paste"
+            "/" = "go_up"
+            "#,
+        );
+        let conflicts = keymap.apply_config(&config).unwrap();
+        assert_eq!(conflicts.len(), 3, "{:?}", conflicts);
+        // entries are applied in the order of keys
+        assert!(conflicts[0].contains("'/' is bound to 'go_up', so it can't be typed"));
+        assert!(conflicts[1].contains("'control+e' and 'ctrl+e' are the same key"));
+        assert!(conflicts[2].contains("'toggle_match_mode', which is no longer bound"));
+        assert_eq!(
+            keymap.command_for(
+                WindowFocus::Tree,
+                key_event(KeyCode::Char('e'), KeyModifiers::CONTROL)
+            ),
+            Some(Command::Undo)
+        );
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let mut keymap = Keymap::default();
+        for content in [
+            "[nowhere]\n\"a\" = \"undo\"",
+            "[tree]\n\"ctrl+\" = \"undo\"",
+            "[tree]\n\"ctrl+a\" = \"teleport\"",
+            "[viewer]\n\"ctrl+a\" = \"paste\"", // not available in the viewer
+        ] {
+            assert!(
+                keymap.apply_config(&parse_config(content)).is_err(),
+                "{}",
+                content
+            );
+        }
+    }
+}
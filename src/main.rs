@@ -1,18 +1,65 @@
 mod action_menu;
+mod action_menu_test;
 mod app;
 mod app_logic;
 mod appdata;
 mod background;
+mod bookmarks;
+mod bookmarks_test;
+mod breadcrumbs;
+mod breadcrumbs_test;
+mod config;
+mod config_test;
 mod errors;
 mod event;
 mod filesystem;
+mod filesystem_test;
+mod fuzzy;
+mod fuzzy_test;
+mod glob;
+mod glob_test;
+mod help;
+mod help_test;
+mod highlight;
+mod highlight_test;
+mod history;
+mod history_test;
+mod ignore;
+mod ignore_test;
 mod keyboard;
+mod keymap;
+mod keymap_test;
 mod logs;
+mod mouse;
+mod mouse_test;
+mod multi_select_test;
+mod navigation_test;
 mod numbers;
 mod numbers_test;
+mod path_input;
+mod path_input_test;
+mod preview;
+mod preview_test;
+mod shell;
+mod shell_test;
+mod sorting;
+mod sorting_test;
+mod storage;
+mod testing;
+mod transfer;
+mod transfer_test;
+mod trash;
+mod trash_test;
 mod tree;
+mod tree_test;
 mod tui;
 mod ui;
+mod undo;
+mod undo_test;
+mod viewer;
+mod viewer_test;
+mod vim;
+mod vim_test;
 
 use std::process::ExitCode;
 
@@ -0,0 +1,99 @@
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Position, Rect};
+use std::time::{Duration, Instant};
+
+use crate::{app::App, appdata::WindowFocus, tui::Tui};
+
+pub const WHEEL_SCROLL_LINES: i32 = 3;
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Bordered list as last rendered, for finding the clicked item.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ListArea {
+    pub area: Rect,    // including borders
+    pub offset: usize, // index of the first visible item
+}
+
+impl ListArea {
+    pub fn contains(&self, column: u16, row: u16) -> bool {
+        self.area.contains(Position::new(column, row))
+    }
+
+    pub fn item_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inner: Rect = self.area.inner(Margin::new(1, 1));
+        if !inner.contains(Position::new(column, row)) {
+            return None;
+        }
+        Some(self.offset + (row - inner.y) as usize)
+    }
+}
+
+/// Position and time of the last click, to recognize double clicks.
+#[derive(Debug, Clone, Copy)]
+pub struct Click {
+    pub column: u16,
+    pub row: u16,
+    pub time: Instant,
+}
+
+impl Click {
+    pub fn is_repeated_by(&self, next: &Click) -> bool {
+        self.column == next.column
+            && self.row == next.row
+            && next.time.duration_since(self.time) <= DOUBLE_CLICK_INTERVAL
+    }
+}
+
+pub fn update_on_mouse(app: &mut App, mouse_event: MouseEvent, tui: &mut Tui) {
+    match mouse_event.kind {
+        MouseEventKind::ScrollDown => on_wheel(app, WHEEL_SCROLL_LINES),
+        MouseEventKind::ScrollUp => on_wheel(app, -WHEEL_SCROLL_LINES),
+        MouseEventKind::Down(MouseButton::Left) => {
+            on_left_click(app, mouse_event.column, mouse_event.row, tui)
+        }
+        _ => {}
+    }
+}
+
+fn on_wheel(app: &mut App, delta: i32) {
+    match app.window_focus {
+        WindowFocus::Viewer if !app.has_info() => app.scroll_viewer(delta as i64),
+        _ => app.move_cursor(delta),
+    }
+}
+
+fn on_left_click(app: &mut App, column: u16, row: u16, tui: &mut Tui) {
+    if app.has_error() {
+        app.clear_error();
+        return;
+    }
+    if app.has_info() {
+        app.clear_info();
+        return;
+    }
+    let double_click = app.register_click(column, row);
+    if matches!(
+        app.window_focus,
+        WindowFocus::Tree | WindowFocus::Breadcrumbs
+    ) && app.click_breadcrumb(column, row)
+    {
+        return;
+    }
+    match app.window_focus {
+        WindowFocus::Tree => {
+            if let Some(index) = app.tree_list_area.item_at(column, row) {
+                app.click_tree_node(index, double_click);
+            }
+        }
+        WindowFocus::ActionMenu => match app.action_menu_list_area.item_at(column, row) {
+            Some(index) if index < app.menu_actions.len() => {
+                app.action_menu_cursor_y = index;
+                app.execute_dialog_action(tui);
+            }
+            Some(_) => {}
+            None if app.action_menu_list_area.contains(column, row) => {}
+            None => app.close_action_dialog(),
+        },
+        _ => {}
+    }
+}
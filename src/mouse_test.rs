@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use std::time::{Duration, Instant};
+
+    use crate::app::App;
+    use crate::mouse::{Click, ListArea};
+
+    #[test]
+    fn test_list_area_item_at() {
+        let list_area = ListArea {
+            area: Rect::new(10, 5, 20, 6), // 4 rows inside the borders
+            offset: 7,
+        };
+        assert_eq!(list_area.item_at(11, 6), Some(7));
+        assert_eq!(list_area.item_at(28, 9), Some(10));
+        assert_eq!(list_area.item_at(10, 6), None); // left border
+        assert_eq!(list_area.item_at(11, 5), None); // title
+        assert_eq!(list_area.item_at(11, 10), None); // bottom border
+        assert!(list_area.contains(11, 5));
+        assert!(!list_area.contains(30, 5));
+        assert_eq!(ListArea::default().item_at(0, 0), None);
+    }
+
+    #[test]
+    fn test_double_click() {
+        let time = Instant::now();
+        let click = Click {
+            column: 3,
+            row: 4,
+            time,
+        };
+        let next = |column, row, millis| Click {
+            column,
+            row,
+            time: time + Duration::from_millis(millis),
+        };
+        assert!(click.is_repeated_by(&next(3, 4, 100)));
+        assert!(!click.is_repeated_by(&next(3, 5, 100)));
+        assert!(!click.is_repeated_by(&next(3, 4, 1000)));
+
+        let mut app = App::new();
+        assert!(!app.register_click(1, 1));
+        assert!(app.register_click(1, 1));
+        assert!(!app.register_click(1, 1)); // third click starts over
+        assert!(!app.register_click(2, 1));
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::app::App;
+    use crate::filesystem::get_path_file_nodes;
+    use crate::testing::{path_string, TempDir};
+
+    fn select_by_name(app: &mut App, name: &str) {
+        let index = app
+            .child_tree_nodes
+            .iter()
+            .position(|it| it.name() == name)
+            .unwrap();
+        app.set_dir_cursor(index);
+    }
+
+    fn marked_names(app: &App) -> Vec<&str> {
+        app.child_tree_nodes
+            .iter()
+            .filter(|it| it.marked)
+            .map(|it| it.name())
+            .collect()
+    }
+
+    fn multi_select_app(dir: &TempDir) -> App {
+        let dir_path = path_string(&fs::canonicalize(dir).unwrap());
+        let mut app = App::new();
+        app.multi_select = true;
+        app.starting_dir = dir_path.clone();
+        app.parent_file_nodes = get_path_file_nodes(&dir_path).unwrap();
+        app.starting_dir_nodes = app.parent_file_nodes.clone();
+        app.populate_current_child_nodes();
+        app
+    }
+
+    #[test]
+    fn test_marks_survive_filter_and_navigation() {
+        let dir = TempDir::new("multi-select-marks");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        for name in ["a.txt", "b.txt", "sub/c.txt"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let mut app = multi_select_app(&dir);
+
+        app.type_search_text('a');
+        select_by_name(&mut app, "a.txt");
+        app.toggle_mark_selected_node();
+        app.clear_search_text();
+        assert_eq!(marked_names(&app), vec!["a.txt"]);
+
+        select_by_name(&mut app, "sub");
+        app.go_into();
+        select_by_name(&mut app, "c.txt");
+        app.toggle_mark_selected_node();
+        assert_eq!(marked_names(&app), vec!["c.txt"]);
+        app.go_up();
+        assert_eq!(marked_names(&app), vec!["a.txt"]);
+
+        app.pick_selected_node(None);
+        assert_eq!(app.picked_paths, vec!["a.txt", "sub/c.txt"]);
+        assert!(app.should_quit);
+
+        app.pick_selected_node(Some(false));
+        assert_eq!(
+            app.picked_paths,
+            vec![
+                format!("{}/a.txt", app.starting_dir),
+                format!("{}/sub/c.txt", app.starting_dir)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toggle_mark() {
+        let dir = TempDir::new("multi-select-toggle");
+        fs::write(dir.join("a.txt"), "").unwrap();
+        let mut app = multi_select_app(&dir);
+        select_by_name(&mut app, "a.txt");
+        app.toggle_mark_selected_node();
+        select_by_name(&mut app, "a.txt");
+        app.toggle_mark_selected_node();
+        assert!(app.marked_routes.is_empty());
+        assert!(marked_names(&app).is_empty());
+
+        app.multi_select = false;
+        app.toggle_mark_selected_node();
+        assert!(app.marked_routes.is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::app::App;
+    use crate::filesystem::get_path_file_nodes;
+    use crate::testing::TempDir;
+
+    #[test]
+    fn test_go_back_and_forward() {
+        let dir = TempDir::new("navigation");
+        fs::create_dir_all(dir.join("a/nested")).unwrap();
+        fs::create_dir_all(dir.join("b")).unwrap();
+        let dir_path = dir.to_string_lossy().to_string();
+
+        let mut app = App::new();
+        app.parent_file_nodes = get_path_file_nodes(&dir_path).unwrap();
+        app.populate_current_child_nodes();
+        app.type_search_text('b');
+        app.set_dir_cursor(0);
+        app.go_into();
+        assert_eq!(app.get_current_string_path(), format!("{}/b", dir_path));
+        app.go_to_root();
+        assert_eq!(app.get_current_string_path(), "/");
+
+        app.go_back();
+        assert_eq!(app.get_current_string_path(), format!("{}/b", dir_path));
+        app.go_back();
+        assert_eq!(app.get_current_string_path(), dir_path);
+        assert_eq!(app.filter_text, "b");
+        assert_eq!(app.dir_cursor, 0);
+        app.go_back(); // nothing more to go back to
+        assert_eq!(app.get_current_string_path(), dir_path);
+
+        app.go_forward();
+        app.go_forward();
+        assert_eq!(app.get_current_string_path(), "/");
+        app.go_back();
+        app.go_up(); // new navigation drops the forward history
+        app.go_forward();
+        assert_eq!(app.get_current_string_path(), dir_path);
+    }
+
+    #[test]
+    fn test_restore_directory_state_on_reenter() {
+        let dir = TempDir::new("dir-state");
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        for name in ["x1", "x2", "x3", "y"] {
+            fs::write(dir.join("sub").join(name), "").unwrap();
+        }
+        let dir_path = dir.to_string_lossy().to_string();
+
+        let mut app = App::new();
+        app.parent_file_nodes = get_path_file_nodes(&dir_path).unwrap();
+        app.populate_current_child_nodes();
+        app.set_dir_cursor(1);
+        app.go_into();
+        app.type_search_text('x');
+        app.set_dir_cursor(2);
+        app.go_up();
+        assert_eq!(app.filter_text, "");
+        assert_eq!(app.dir_cursor, 1);
+
+        app.go_into();
+        assert_eq!(app.get_current_string_path(), format!("{}/sub", dir_path));
+        assert_eq!(app.filter_text, "x");
+        assert_eq!(app.dir_cursor, 2);
+        assert_eq!(app.get_selected_tree_node().unwrap().name(), "x3");
+
+        app.go_to_root();
+        app.go_to_path(&format!("{}/sub", dir_path));
+        assert_eq!(app.filter_text, "x");
+        assert_eq!(app.dir_cursor, 2);
+    }
+}
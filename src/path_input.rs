@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use crate::filesystem::{list_files, FileNode};
+use crate::sorting::{sort_file_nodes, SortOrder};
+
+/// Tells whether the search text is a path to jump to rather than a name filter.
+pub fn is_path_input(text: &str) -> bool {
+    text.starts_with('/')
+        || text == "~"
+        || text.starts_with("~/")
+        || text.starts_with("./")
+        || text.starts_with("../")
+}
+
+/// Turns the typed path into an absolute one, resolving `~` and paths relative to the current directory.
+pub fn expand_path_input(text: &str, current_dir: &str, home_dir: Option<&str>) -> String {
+    if text == "~" || text.starts_with("~/") {
+        if let Some(home_dir) = home_dir {
+            return format!("{}{}", home_dir.trim_end_matches('/'), &text[1..]);
+        }
+    }
+    if text.starts_with('/') {
+        return text.to_string();
+    }
+    format!("{}/{}", current_dir.trim_end_matches('/'), text)
+}
+
+/// Splits the path into the directory to list and the beginning of the entry name typed so far.
+pub fn split_path_input(path: &str) -> (&str, &str) {
+    match path.rfind('/') {
+        Some(0) => ("/", &path[1..]),
+        Some(pos) => (&path[..pos], &path[pos + 1..]),
+        None => (".", path),
+    }
+}
+
+/// Replaces the entry name at the end of the typed path with the completed one.
+pub fn replace_typed_name(text: &str, completed_name: &str) -> String {
+    let (_, typed_name) = split_path_input(text);
+    let dir_part = &text[..text.len() - typed_name.len()];
+    format!("{}{}", dir_part, completed_name)
+}
+
+/// Lists the entries of the directory starting with the given prefix (ignoring case).
+/// Dotfiles are listed only when the prefix starts with a dot.
+pub fn find_path_candidates(dir_path: &str, name_prefix: &str) -> Vec<FileNode> {
+    let lowercase_prefix = name_prefix.to_lowercase();
+    let mut candidates: Vec<FileNode> = list_files(Path::new(dir_path), false)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|node| node.lowercase_name.starts_with(&lowercase_prefix))
+        .filter(|node| name_prefix.starts_with('.') || !node.name.starts_with('.'))
+        .collect();
+    sort_file_nodes(&mut candidates, SortOrder::default());
+    candidates
+}
+
+/// Extends the typed name to the longest prefix shared by all candidates,
+/// adding a slash when a single directory is left.
+pub fn complete_name(name_prefix: &str, candidates: &[FileNode]) -> Option<String> {
+    let first: &FileNode = candidates.first()?;
+    if candidates.len() == 1 {
+        return match first.is_directory {
+            true => Some(format!("{}/", first.name)),
+            false => Some(first.name.clone()),
+        };
+    }
+    let mut common: Vec<char> = first.name.chars().collect();
+    for candidate in &candidates[1..] {
+        let shared = common
+            .iter()
+            .zip(candidate.name.chars())
+            .take_while(|(a, b)| **a == *b)
+            .count();
+        common.truncate(shared);
+    }
+    let common: String = common.into_iter().collect();
+    match common.chars().count() > name_prefix.chars().count() {
+        true => Some(common),
+        false => None,
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::app::App;
+    use crate::filesystem::FileNode;
+    use crate::path_input::{
+        complete_name, expand_path_input, find_path_candidates, is_path_input, replace_typed_name,
+        split_path_input,
+    };
+    use crate::testing::{path_string, TempDir};
+
+    #[test]
+    fn test_is_path_input() {
+        assert!(is_path_input("/usr"));
+        assert!(is_path_input("~"));
+        assert!(is_path_input("~/projects"));
+        assert!(is_path_input("./src"));
+        assert!(is_path_input("../"));
+        assert!(!is_path_input("~user"));
+        assert!(!is_path_input(".gitignore"));
+        assert!(!is_path_input("main.rs"));
+    }
+
+    #[test]
+    fn test_expand_path_input() {
+        let home = Some("/home/user");
+        assert_eq!(expand_path_input("~", "/tmp", home), "/home/user");
+        assert_eq!(expand_path_input("~/src", "/tmp", home), "/home/user/src");
+        assert_eq!(expand_path_input("/etc/", "/tmp", home), "/etc/");
+        assert_eq!(expand_path_input("./a", "/tmp", home), "/tmp/./a");
+        assert_eq!(expand_path_input("../a", "/", home), "/../a");
+    }
+
+    #[test]
+    fn test_split_path_input() {
+        assert_eq!(split_path_input("/"), ("/", ""));
+        assert_eq!(split_path_input("/us"), ("/", "us"));
+        assert_eq!(split_path_input("/usr/lo"), ("/usr", "lo"));
+        assert_eq!(split_path_input("/usr/"), ("/usr", ""));
+    }
+
+    #[test]
+    fn test_complete_path() {
+        let dir = TempDir::new("path-input");
+        fs::create_dir_all(dir.join("project-alpha")).unwrap();
+        fs::create_dir_all(dir.join("project-beta")).unwrap();
+        fs::create_dir_all(dir.join(".hidden")).unwrap();
+        fs::write(dir.join("Readme.md"), "").unwrap();
+        let dir_path = dir.to_string_lossy().to_string();
+
+        let candidates: Vec<FileNode> = find_path_candidates(&dir_path, "pro");
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(
+            complete_name("pro", &candidates),
+            Some("project-".to_string())
+        );
+        let candidates = find_path_candidates(&dir_path, "project-a");
+        assert_eq!(
+            complete_name("project-a", &candidates),
+            Some("project-alpha/".to_string())
+        );
+        let candidates = find_path_candidates(&dir_path, "read");
+        assert_eq!(
+            complete_name("read", &candidates),
+            Some("Readme.md".to_string())
+        );
+        assert_eq!(find_path_candidates(&dir_path, "").len(), 3);
+        assert_eq!(find_path_candidates(&dir_path, ".").len(), 1);
+        assert!(complete_name("x", &find_path_candidates(&dir_path, "x")).is_none());
+    }
+
+    #[test]
+    fn test_replace_typed_name() {
+        assert_eq!(replace_typed_name("~/pro", "project-"), "~/project-");
+        assert_eq!(replace_typed_name("~/", "src/"), "~/src/");
+        assert_eq!(replace_typed_name("/usr/lo", "local/"), "/usr/local/");
+        assert_eq!(replace_typed_name("/zażó", "zażółć"), "/zażółć");
+    }
+
+    #[test]
+    fn test_complete_path_input() {
+        let mut app = App::new();
+        app.type_search_text('~');
+        app.complete_path_input();
+        assert_eq!(app.filter_text, "~/");
+        for c in "fpick-no-such-entry".chars() {
+            app.type_search_text(c);
+        }
+        app.complete_path_input();
+        assert_eq!(app.filter_text, "~/fpick-no-such-entry");
+
+        let dir = TempDir::new("path-input-app");
+        fs::create_dir_all(dir.join("zażółć-dir")).unwrap();
+        app.filter_text.clear();
+        for c in format!("{}/zaż", path_string(&dir)).chars() {
+            app.type_search_text(c);
+        }
+        app.complete_path_input();
+        assert_eq!(
+            app.filter_text,
+            format!("{}/zażółć-dir/", path_string(&dir))
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Context, Result};
+use ratatui::text::Line;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+use crate::action_menu::human_readable_size;
+use crate::filesystem::{list_files, FileNode, FileType};
+use crate::highlight::{highlight_lines, HighlightSettings};
+
+pub const DEFAULT_PREVIEW_MAX_LINES: usize = 200;
+pub const DEFAULT_VIEW_MAX_BYTES: u64 = 16 * 1024 * 1024;
+const PREVIEW_READ_LIMIT: u64 = 64 * 1024;
+const HEX_DUMP_BYTES: usize = 512;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewContent {
+    Text {
+        lines: Vec<String>,
+        truncated: bool,
+        highlighted: Option<Vec<Line<'static>>>, // same lines with syntax colors
+    },
+    Directory {
+        entries: Vec<FileNode>,
+        total: usize,
+    },
+    Binary {
+        size: u64,
+        hex_lines: Vec<String>,
+    },
+    Error(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview {
+    pub path: String,
+    pub content: PreviewContent,
+}
+
+/// Entry to preview, sent to the thread loading previews.
+#[derive(Debug, Clone)]
+pub struct PreviewRequest {
+    pub path: String,
+    pub max_lines: usize,
+    pub highlight: HighlightSettings,
+}
+
+/// Loads a short summary of the entry: the first lines of a text file,
+/// the listing of a directory or a hex dump of a binary file.
+/// Reads no more than a small chunk of the file, so it's safe for huge files.
+/// Special files like FIFOs or devices aren't opened, as reading them could block or never end.
+pub fn load_preview(path: &str, max_lines: usize, highlight: &HighlightSettings) -> PreviewContent {
+    let result = fs::metadata(path)
+        .context("failed to read file metadata")
+        .and_then(|metadata| match metadata.file_type() {
+            file_type if file_type.is_dir() => load_directory_preview(path, max_lines),
+            file_type if file_type.is_file() => {
+                load_file_preview(path, metadata.len(), max_lines, highlight)
+            }
+            _ => Err(anyhow!("not a regular file")),
+        });
+    result.unwrap_or_else(|e| PreviewContent::Error(e.to_string()))
+}
+
+fn load_directory_preview(path: &str, max_lines: usize) -> Result<PreviewContent> {
+    let mut entries: Vec<FileNode> = list_files(Path::new(path), false)?;
+    entries.sort_by(|a, b| {
+        let a_dir = a.file_type == FileType::Directory;
+        let b_dir = b.file_type == FileType::Directory;
+        b_dir
+            .cmp(&a_dir)
+            .then(a.lowercase_name.cmp(&b.lowercase_name))
+    });
+    let total = entries.len();
+    entries.truncate(max_lines);
+    Ok(PreviewContent::Directory { entries, total })
+}
+
+fn load_file_preview(
+    path: &str,
+    size: u64,
+    max_lines: usize,
+    highlight: &HighlightSettings,
+) -> Result<PreviewContent> {
+    let mut buffer: Vec<u8> = Vec::new();
+    File::open(path)
+        .context("failed to open file")?
+        .take(PREVIEW_READ_LIMIT)
+        .read_to_end(&mut buffer)
+        .context("failed to read file")?;
+
+    if is_binary(&buffer) {
+        return Ok(PreviewContent::Binary {
+            size,
+            hex_lines: hex_dump(&buffer[..buffer.len().min(HEX_DUMP_BYTES)]),
+        });
+    }
+    let text = String::from_utf8_lossy(&buffer);
+    let mut lines: Vec<String> = text
+        .lines()
+        .take(max_lines + 1)
+        .map(|line| line.replace('\t', "    "))
+        .collect();
+    let truncated = lines.len() > max_lines || size > buffer.len() as u64;
+    lines.truncate(max_lines);
+    let highlighted = highlight_lines(path, &lines, highlight);
+    Ok(PreviewContent::Text {
+        lines,
+        truncated,
+        highlighted,
+    })
+}
+
+/// Treats content as binary when it contains NUL bytes or isn't valid UTF-8
+/// (ignoring a character possibly cut at the end of the chunk).
+pub fn is_binary(buffer: &[u8]) -> bool {
+    if buffer.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(buffer) {
+        Ok(_) => false,
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    hex_dump_at(bytes, 0)
+}
+
+/// Hex dump rows of bytes located at `start_offset` in the file.
+pub fn hex_dump_at(bytes: &[u8], start_offset: usize) -> Vec<String> {
+    bytes
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|b| match b.is_ascii_graphic() || *b == b' ' {
+                    true => *b as char,
+                    false => '.',
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<47}  {}",
+                start_offset + i * 16,
+                hex.join(" "),
+                ascii
+            )
+        })
+        .collect()
+}
+
+pub fn binary_summary(size: u64) -> String {
+    format!("Binary file, {}", human_readable_size(size))
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    use crate::app::App;
+    use crate::filesystem::get_path_file_nodes;
+
+    use crate::highlight::HighlightSettings;
+    use crate::preview::{hex_dump, is_binary, load_preview, PreviewContent};
+    use crate::testing::{path_string, TempDir};
+
+    #[test]
+    fn test_is_binary() {
+        assert!(!is_binary(b"plain text\nwith lines"));
+        assert!(!is_binary("zażółć".as_bytes()));
+        assert!(!is_binary(&"zażółć".as_bytes()[..3])); // char cut at the end of a chunk
+        assert!(is_binary(b"ELF\x00\x01"));
+        assert!(is_binary(b"\xff\xfe invalid"));
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let lines = hex_dump(b"0123456789abcdefXYZ\n");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0],
+            "00000000  30 31 32 33 34 35 36 37 38 39 61 62 63 64 65 66  0123456789abcdef"
+        );
+        assert_eq!(lines[1], format!("00000010  {:<47}  XYZ.", "58 59 5a 0a"));
+    }
+
+    #[test]
+    fn test_load_preview() {
+        let dir = TempDir::new("preview");
+        fs::create_dir_all(dir.join("subdir")).unwrap();
+        let lines: Vec<String> = (0..10).map(|i| format!("line {}", i)).collect();
+        fs::write(dir.join("text.txt"), lines.join("\n")).unwrap();
+        fs::write(dir.join("binary.bin"), [0u8, 1, 2, 3]).unwrap();
+
+        let text_path = dir.join("text.txt").to_string_lossy().to_string();
+        match load_preview(&text_path, 3, &HighlightSettings::default()) {
+            PreviewContent::Text {
+                lines, truncated, ..
+            } => {
+                assert_eq!(lines, vec!["line 0", "line 1", "line 2"]);
+                assert!(truncated);
+            }
+            other => panic!("unexpected preview: {:?}", other),
+        }
+
+        let binary_path = dir.join("binary.bin").to_string_lossy().to_string();
+        match load_preview(&binary_path, 3, &HighlightSettings::default()) {
+            PreviewContent::Binary { size, hex_lines } => {
+                assert_eq!(size, 4);
+                assert_eq!(hex_lines.len(), 1);
+            }
+            other => panic!("unexpected preview: {:?}", other),
+        }
+
+        let dir_path = dir.to_string_lossy().to_string();
+        match load_preview(&dir_path, 2, &HighlightSettings::default()) {
+            PreviewContent::Directory { entries, total } => {
+                assert_eq!(total, 3);
+                assert_eq!(entries.len(), 2);
+                assert_eq!(entries[0].name, "subdir");
+            }
+            other => panic!("unexpected preview: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_preview_skips_special_files() {
+        let dir = TempDir::new("preview-fifo");
+        let fifo_path = dir.join("fifo");
+        let status = Command::new("mkfifo").arg(&fifo_path).status().unwrap();
+        assert!(status.success());
+        let content = load_preview(
+            &fifo_path.to_string_lossy(),
+            3,
+            &HighlightSettings::default(),
+        );
+        assert_eq!(
+            content,
+            PreviewContent::Error("not a regular file".to_string())
+        );
+    }
+
+    #[test]
+    fn test_preview_worker_loads_latest_request() {
+        let dir = TempDir::new("preview-worker");
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+        let mut app = App::new();
+        app.parent_file_nodes = get_path_file_nodes(&path_string(&dir)).unwrap();
+        app.populate_current_child_nodes();
+        app.toggle_preview();
+        for _ in 0..3 {
+            app.move_cursor(1);
+        }
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while app.preview.is_none() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+            app.check_background_events();
+        }
+        let preview = app.preview.unwrap();
+        assert_eq!(preview.path, path_string(&dir.join("c.txt")));
+        match preview.content {
+            PreviewContent::Text { lines, .. } => assert_eq!(lines, vec!["c.txt"]),
+            other => panic!("unexpected preview: {:?}", other),
+        }
+    }
+}
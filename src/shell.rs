@@ -0,0 +1,80 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Quotes a value so that `sh` treats it as a single literal word,
+/// no matter what characters it contains.
+pub fn shell_quote(value: &str) -> String {
+    if !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-+,:@%=".contains(c))
+    {
+        return value.to_string();
+    }
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Placeholders of command templates and the positional arguments they refer to.
+const PLACEHOLDERS: [(&str, usize); 4] = [("{}", 1), ("{path}", 1), ("{name}", 2), ("{dir}", 3)];
+
+/// Turns a command template into a `sh` script referring to the selected entry
+/// through positional arguments (see `command_template_args`):
+/// - `{}` or `{path}` - absolute path of the selected entry,
+/// - `{name}` - its file name,
+/// - `{dir}` - its parent directory.
+///
+/// Values never become part of the script, so file names can't break out of quotes.
+/// A placeholder can stand alone or inside quotes, e.g. `vim "{}:1"` or `echo 'dir: {dir}'`.
+pub fn render_command_template(template: &str) -> String {
+    let mut result = String::new();
+    let mut quote: Option<char> = None;
+    let mut rest: &str = template;
+    while let Some(c) = rest.chars().next() {
+        if let Some((placeholder, position)) =
+            PLACEHOLDERS.iter().find(|(it, _)| rest.starts_with(it))
+        {
+            let reference = format!("${{{}}}", position);
+            match quote {
+                None => result.push_str(&format!("\"{}\"", reference)),
+                Some('"') => result.push_str(&reference),
+                Some(_) => result.push_str(&format!("'\"{}\"'", reference)), // reopens the single quotes
+            }
+            rest = &rest[placeholder.len()..];
+            continue;
+        }
+        let mut length = c.len_utf8();
+        match (quote, c) {
+            (None, '\'' | '"') => quote = Some(c),
+            (Some(open), _) if open == c => quote = None,
+            (None | Some('"'), '\\') => {
+                // the escaped character is copied as is
+                length += rest[length..].chars().next().map_or(0, |it| it.len_utf8());
+            }
+            _ => {}
+        }
+        result.push_str(&rest[..length]);
+        rest = &rest[length..];
+    }
+    result
+}
+
+/// Values of the positional arguments used by `render_command_template`.
+pub fn command_template_args(abs_path: &str) -> Vec<String> {
+    let path = Path::new(abs_path);
+    let name: String = match path.file_name() {
+        Some(name) => name.to_string_lossy().to_string(),
+        None => abs_path.to_string(),
+    };
+    let dir: String = match path.parent() {
+        Some(dir) => dir.to_string_lossy().to_string(),
+        None => abs_path.to_string(),
+    };
+    vec![abs_path.to_string(), name, dir]
+}
+
+/// `sh` running the script with the given positional arguments.
+pub fn shell_command(script: &str, args: &[String]) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(script).arg("fpick").args(args);
+    command
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::shell::{
+        command_template_args, render_command_template, shell_command, shell_quote,
+    };
+
+    const HOSTILE_NAMES: [&str; 12] = [
+        "plain.txt",
+        "with space.txt",
+        "double\"quote",
+        "single'quote",
+        "$(touch pwned)",
+        "`touch pwned`",
+        "$HOME",
+        "semi;colon && rm -rf x",
+        "back\\slash",
+        "new\nline",
+        "-rf",
+        "*",
+    ];
+
+    fn sh_output(cmd: &str, args: &[String]) -> String {
+        let output = shell_command(cmd, args).output().unwrap();
+        assert!(output.status.success(), "command failed: {}", cmd);
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn test_shell_quote_simple() {
+        assert_eq!(shell_quote("/home/user/file.txt"), "/home/user/file.txt");
+        assert_eq!(shell_quote("with space"), "'with space'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[test]
+    fn test_shell_quote_hostile_names() {
+        for name in HOSTILE_NAMES {
+            let cmd = format!("printf '%s' {}", shell_quote(name));
+            assert_eq!(sh_output(&cmd, &[]), name);
+        }
+    }
+
+    #[test]
+    fn test_render_command_template() {
+        assert_eq!(render_command_template("vim {}"), "vim \"${1}\"");
+        assert_eq!(render_command_template("vim \"{}\""), "vim \"${1}\"");
+        assert_eq!(render_command_template("vim \"{}:1\""), "vim \"${1}:1\"");
+        assert_eq!(
+            render_command_template("echo 'dir: {dir}'"),
+            "echo 'dir: '\"${3}\"''"
+        );
+        assert_eq!(
+            render_command_template("cp {path} {dir}/{name}.bak"),
+            "cp \"${1}\" \"${3}\"/\"${2}\".bak"
+        );
+        assert_eq!(render_command_template("echo \\' {}"), "echo \\' \"${1}\"");
+        assert_eq!(render_command_template("echo {other}"), "echo {other}");
+        assert_eq!(
+            command_template_args("/tmp/x/c.txt"),
+            vec!["/tmp/x/c.txt", "c.txt", "/tmp/x"]
+        );
+    }
+
+    #[test]
+    fn test_render_command_template_hostile_names() {
+        let names = HOSTILE_NAMES
+            .iter()
+            .copied()
+            .chain(["x\"$(touch pwned)\"", "x'$(touch pwned)'"]);
+        for name in names {
+            let path = format!("/tmp/{}", name);
+            let args = command_template_args(&path);
+            for (template, expected) in [
+                ("printf '%s' {}", path.clone()),
+                ("printf '%s' \"{}\"", path.clone()),
+                ("printf '%s' '{}'", path.clone()),
+                ("printf '%s' \"{}:1\"", format!("{}:1", path)),
+                ("printf '%s' \"dir: {}\"", format!("dir: {}", path)),
+                ("printf '%s' 'dir: {}'", format!("dir: {}", path)),
+                ("printf '%s|%s' {dir} {name}", format!("/tmp|{}", name)),
+            ] {
+                let script = render_command_template(template);
+                assert_eq!(sh_output(&script, &args), expected, "{}", template);
+            }
+        }
+        assert!(!Path::new("pwned").exists());
+    }
+}
@@ -0,0 +1,142 @@
+use serde::Deserialize;
+use std::cmp::Ordering;
+
+use crate::filesystem::FileNode;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortMode {
+    #[default]
+    Name,
+    Natural, // numbers inside names are compared by value, so file2 goes before file10
+    Size,    // largest first
+    #[serde(rename = "mtime")]
+    Modified, // newest first
+    #[serde(rename = "ext")]
+    Extension,
+}
+
+/// Order of the directory listing. Directories are always listed before files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SortOrder {
+    pub mode: SortMode,
+    pub reverse: bool,
+}
+
+const SORT_MODES: [SortMode; 5] = [
+    SortMode::Name,
+    SortMode::Natural,
+    SortMode::Size,
+    SortMode::Modified,
+    SortMode::Extension,
+];
+
+impl SortMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SortMode::Name => "name",
+            SortMode::Natural => "natural",
+            SortMode::Size => "size",
+            SortMode::Modified => "mtime",
+            SortMode::Extension => "ext",
+        }
+    }
+
+    pub fn needs_details(&self) -> bool {
+        matches!(self, SortMode::Size | SortMode::Modified)
+    }
+
+    pub fn parse(name: &str) -> Option<SortMode> {
+        SORT_MODES.into_iter().find(|mode| mode.name() == name)
+    }
+
+    pub fn next(&self) -> SortMode {
+        let index = SORT_MODES.iter().position(|mode| mode == self).unwrap_or(0);
+        SORT_MODES[(index + 1) % SORT_MODES.len()]
+    }
+}
+
+impl SortOrder {
+    pub fn describe(&self) -> String {
+        match self.reverse {
+            true => format!("sort: {}, reversed", self.mode.name()),
+            false => format!("sort: {}", self.mode.name()),
+        }
+    }
+}
+
+pub fn sort_file_nodes(nodes: &mut [FileNode], order: SortOrder) {
+    nodes.sort_by(|a, b| compare_file_nodes(a, b, order));
+}
+
+pub fn compare_file_nodes(a: &FileNode, b: &FileNode, order: SortOrder) -> Ordering {
+    let by_mode = match order.mode {
+        SortMode::Name => a.lowercase_name.cmp(&b.lowercase_name),
+        SortMode::Natural => natural_cmp(&a.lowercase_name, &b.lowercase_name),
+        SortMode::Size => b
+            .size()
+            .cmp(&a.size())
+            .then_with(|| a.lowercase_name.cmp(&b.lowercase_name)),
+        SortMode::Modified => b
+            .modified()
+            .cmp(&a.modified())
+            .then_with(|| a.lowercase_name.cmp(&b.lowercase_name)),
+        SortMode::Extension => file_extension(&a.lowercase_name)
+            .cmp(file_extension(&b.lowercase_name))
+            .then_with(|| natural_cmp(&a.lowercase_name, &b.lowercase_name)),
+    };
+    let by_mode = match order.reverse {
+        true => by_mode.reverse(),
+        false => by_mode,
+    };
+    b.is_directory.cmp(&a.is_directory).then(by_mode)
+}
+
+/// Extension of a file name, empty for names without one and for dotfiles like `.bashrc`.
+pub fn file_extension(name: &str) -> &str {
+    match name.rfind('.') {
+        Some(0) | None => "",
+        Some(pos) => &name[pos + 1..],
+    }
+}
+
+/// Compares strings treating runs of digits as numbers.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_rest: &str = a;
+    let mut b_rest: &str = b;
+    loop {
+        let (a_chunk, a_tail) = split_chunk(a_rest);
+        let (b_chunk, b_tail) = split_chunk(b_rest);
+        if a_chunk.is_empty() || b_chunk.is_empty() {
+            return a_chunk.len().cmp(&b_chunk.len());
+        }
+        let a_digits = a_chunk.starts_with(|c: char| c.is_ascii_digit());
+        let b_digits = b_chunk.starts_with(|c: char| c.is_ascii_digit());
+        let chunk_cmp = match (a_digits, b_digits) {
+            (true, true) => {
+                let a_number = a_chunk.trim_start_matches('0');
+                let b_number = b_chunk.trim_start_matches('0');
+                a_number
+                    .len()
+                    .cmp(&b_number.len())
+                    .then_with(|| a_number.cmp(b_number))
+                    .then_with(|| a_chunk.len().cmp(&b_chunk.len()))
+            }
+            _ => a_chunk.cmp(b_chunk),
+        };
+        if chunk_cmp != Ordering::Equal {
+            return chunk_cmp;
+        }
+        a_rest = a_tail;
+        b_rest = b_tail;
+    }
+}
+
+/// Splits off the leading run of either digits or non-digits.
+fn split_chunk(text: &str) -> (&str, &str) {
+    let digits = text.starts_with(|c: char| c.is_ascii_digit());
+    let end = text
+        .find(|c: char| c.is_ascii_digit() != digits)
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use std::time::{Duration, SystemTime};
+
+    use crate::filesystem::{FileDetails, FileNode, FileType};
+    use crate::sorting::{file_extension, natural_cmp, sort_file_nodes, SortMode, SortOrder};
+
+    fn node(name: &str, is_directory: bool, size: u64, age_secs: u64) -> FileNode {
+        FileNode {
+            name: name.to_string(),
+            file_type: match is_directory {
+                true => FileType::Directory,
+                false => FileType::Regular,
+            },
+            lowercase_name: name.to_lowercase(),
+            is_symlink: false,
+            is_directory,
+            details: Some(FileDetails {
+                size,
+                modified: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000 - age_secs)),
+                mode: 0o644,
+                uid: 0,
+            }),
+        }
+    }
+
+    fn sorted_names(mode: SortMode, reverse: bool) -> Vec<String> {
+        let mut nodes = vec![
+            node("file10.txt", false, 300, 30),
+            node("File2.rs", false, 100, 10),
+            node("dir", true, 0, 50),
+            node("file1.txt", false, 200, 20),
+            node(".hidden", false, 50, 40),
+        ];
+        sort_file_nodes(&mut nodes, SortOrder { mode, reverse });
+        nodes.into_iter().map(|it| it.name).collect()
+    }
+
+    #[test]
+    fn test_sort_modes() {
+        assert_eq!(
+            sorted_names(SortMode::Name, false),
+            vec!["dir", ".hidden", "file1.txt", "file10.txt", "File2.rs"]
+        );
+        assert_eq!(
+            sorted_names(SortMode::Natural, false),
+            vec!["dir", ".hidden", "file1.txt", "File2.rs", "file10.txt"]
+        );
+        assert_eq!(
+            sorted_names(SortMode::Size, false),
+            vec!["dir", "file10.txt", "file1.txt", "File2.rs", ".hidden"]
+        );
+        assert_eq!(
+            sorted_names(SortMode::Modified, false),
+            vec!["dir", "File2.rs", "file1.txt", "file10.txt", ".hidden"]
+        );
+        assert_eq!(
+            sorted_names(SortMode::Extension, false),
+            vec!["dir", ".hidden", "File2.rs", "file1.txt", "file10.txt"]
+        );
+    }
+
+    #[test]
+    fn test_reverse_keeps_directories_first() {
+        assert_eq!(
+            sorted_names(SortMode::Natural, true),
+            vec!["dir", "file10.txt", "File2.rs", "file1.txt", ".hidden"]
+        );
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file010", "file9"), Ordering::Greater);
+        assert_eq!(natural_cmp("file01", "file1"), Ordering::Greater);
+        assert_eq!(natural_cmp("a", "a1"), Ordering::Less);
+        assert_eq!(natural_cmp("v1.2.10", "v1.2.9"), Ordering::Greater);
+        assert_eq!(natural_cmp("same", "same"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_sort_mode_names() {
+        assert_eq!(SortMode::parse("mtime"), Some(SortMode::Modified));
+        assert_eq!(SortMode::parse("bogus"), None);
+        assert_eq!(SortMode::Extension.next(), SortMode::Name);
+        assert_eq!(file_extension("archive.tar.gz"), "gz");
+        assert_eq!(file_extension(".bashrc"), "");
+        assert_eq!(file_extension("Makefile"), "");
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory for files written by fpick: `$XDG_DATA_HOME/fpick`, usually `~/.local/share/fpick`.
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_data_home().map(|data_home| data_home.join("fpick"))
+}
+
+/// `$XDG_DATA_HOME`, defaulting to `~/.local/share`.
+pub fn xdg_data_home() -> Option<PathBuf> {
+    match std::env::var("XDG_DATA_HOME") {
+        Ok(data_home) if !data_home.is_empty() => Some(PathBuf::from(data_home)),
+        _ => std::env::var("HOME")
+            .ok()
+            .map(|home| PathBuf::from(home).join(".local/share")),
+    }
+}
+
+/// Reads a TOML data file, a missing file gives the default value.
+pub fn load_data_file<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("failed to read file '{}'", path.to_string_lossy()))?;
+    toml::from_str(&content)
+        .with_context(|| format!("invalid data file '{}'", path.to_string_lossy()))
+}
+
+/// Writes a TOML data file through a temporary file, so a failed write doesn't lose the old content.
+pub fn save_data_file<T: Serialize>(path: &Path, data: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| {
+            format!("failed to create directory '{}'", parent.to_string_lossy())
+        })?;
+    }
+    let content = toml::to_string(data).context("failed to serialize data")?;
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, content)
+        .with_context(|| format!("failed to write '{}'", tmp_path.to_string_lossy()))?;
+    fs::rename(&tmp_path, path)
+        .with_context(|| format!("failed to write '{}'", path.to_string_lossy()))?;
+    Ok(())
+}
@@ -0,0 +1,398 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::io::ErrorKind;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+
+use crate::logs::log;
+use crate::trash::{restore_trash_entry, trash_path, TrashEntry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferMode {
+    Copy,
+    Move,
+}
+
+impl TransferMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransferMode::Copy => "yanked",
+            TransferMode::Move => "cut",
+        }
+    }
+
+    pub fn progress_verb(&self) -> &'static str {
+        match self {
+            TransferMode::Copy => "copying",
+            TransferMode::Move => "moving",
+        }
+    }
+}
+
+/// Paths registered by yank or cut, waiting to be pasted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferRegister {
+    pub mode: TransferMode,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferItem {
+    pub source: String,
+    pub target: String,
+    pub overwrite: bool, // replace the existing target
+}
+
+/// Paste waiting for the user to resolve name conflicts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingTransfer {
+    pub mode: TransferMode,
+    pub target_dir: String,
+    pub items: Vec<TransferItem>,
+    pub conflict_index: usize, // items before it have been checked
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TransferProgress {
+    pub done_files: u64,
+    pub total_files: u64,
+    pub done_bytes: u64,
+    pub total_bytes: u64,
+}
+
+impl TransferProgress {
+    pub fn percent(&self) -> u64 {
+        match self.total_bytes {
+            0 => 100 * self.done_files / self.total_files.max(1),
+            total => 100 * self.done_bytes / total,
+        }
+    }
+}
+
+/// Pairs the sources with their targets in `target_dir`, keeping the file names.
+pub fn plan_transfer(paths: &[String], target_dir: &str) -> Result<Vec<TransferItem>> {
+    paths
+        .iter()
+        .map(|source| {
+            let name = Path::new(source)
+                .file_name()
+                .ok_or_else(|| anyhow!("can't paste '{}'", source))?;
+            let target: PathBuf = Path::new(target_dir).join(name);
+            if Path::new(target_dir).starts_with(source) {
+                return Err(anyhow!("can't paste directory '{}' into itself", source));
+            }
+            Ok(TransferItem {
+                source: source.clone(),
+                target: target.to_string_lossy().to_string(),
+                overwrite: false,
+            })
+        })
+        .collect()
+}
+
+/// Whether anything (including a broken symlink) occupies the path.
+pub fn path_exists(path: &str) -> bool {
+    fs::symlink_metadata(path).is_ok()
+}
+
+/// Finds a name that's not taken in the directory, e.g. `notes (1).txt` for `notes.txt`.
+pub fn suggest_free_name(dir: &str, name: &str) -> String {
+    let (stem, extension) = match name.rfind('.') {
+        Some(dot) if dot > 0 => (&name[..dot], &name[dot..]),
+        _ => (name, ""),
+    };
+    (1..)
+        .map(|i| format!("{} ({}){}", stem, i, extension))
+        .find(|candidate| !path_exists(&format!("{}/{}", dir, candidate)))
+        .unwrap()
+}
+
+/// Counts the files and bytes to transfer, without following symlinks.
+pub fn measure_transfer(items: &[TransferItem]) -> TransferProgress {
+    let mut progress = TransferProgress::default();
+    for item in items {
+        measure_path(Path::new(&item.source), &mut progress);
+    }
+    progress
+}
+
+fn measure_path(path: &Path, progress: &mut TransferProgress) {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return,
+    };
+    if metadata.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                measure_path(&entry.path(), progress);
+            }
+        }
+        return;
+    }
+    progress.total_files += 1;
+    progress.total_bytes += metadata.len();
+}
+
+/// Result of a paste, with what's needed to undo it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransferOutcome {
+    pub done_items: Vec<TransferItem>,
+    pub replaced: Vec<TrashEntry>, // overwritten targets, moved to the trash
+    pub errors: Vec<String>,
+}
+
+/// Copies or moves the items one by one, reporting progress after every file.
+/// Failed items don't stop the others, their errors are returned.
+/// Overwritten targets are moved to the trash, so without a trash directory they're left alone.
+pub fn run_transfer<F>(
+    items: &[TransferItem],
+    mode: TransferMode,
+    trash_dir: Option<&Path>,
+    progress: &mut TransferProgress,
+    on_progress: &mut F,
+) -> TransferOutcome
+where
+    F: FnMut(&TransferProgress),
+{
+    let mut outcome = TransferOutcome::default();
+    for item in items {
+        if item.source == item.target {
+            continue;
+        }
+        match transfer_item(item, mode, trash_dir, progress, on_progress) {
+            Ok(replaced) => {
+                outcome.done_items.push(item.clone());
+                outcome.replaced.extend(replaced);
+            }
+            Err(e) => outcome.errors.push(format!("{:#}", e)),
+        }
+    }
+    outcome
+}
+
+/// Transfers the item, returning the trashed target it replaced.
+fn transfer_item<F>(
+    item: &TransferItem,
+    mode: TransferMode,
+    trash_dir: Option<&Path>,
+    progress: &mut TransferProgress,
+    on_progress: &mut F,
+) -> Result<Option<TrashEntry>>
+where
+    F: FnMut(&TransferProgress),
+{
+    let source = Path::new(&item.source);
+    let target = Path::new(&item.target);
+    log(format!(
+        "{} {} to {}",
+        mode.progress_verb(),
+        item.source,
+        item.target
+    )
+    .as_str());
+    if !path_exists(&item.target) {
+        match mode {
+            TransferMode::Copy => copy_new_path(source, target, progress, on_progress)?,
+            TransferMode::Move => match rename_path(source, target)? {
+                true => count_moved(target, progress, on_progress),
+                false => {
+                    copy_new_path(source, target, progress, on_progress)?;
+                    remove_path(source)?;
+                }
+            },
+        }
+        return Ok(None);
+    }
+    if !item.overwrite {
+        return Err(anyhow!("'{}' already exists", item.target));
+    }
+    if source.starts_with(target) {
+        return Err(anyhow!(
+            "can't overwrite '{}' containing the pasted path",
+            item.target
+        ));
+    }
+    let trash_dir: &Path =
+        trash_dir.ok_or_else(|| anyhow!("can't overwrite '{}' without a trash", item.target))?;
+    replace_path(item, mode, trash_dir, progress, on_progress).map(Some)
+}
+
+/// Puts the source beside the target under a temporary name, then swaps them,
+/// so that the target is left intact when the transfer fails.
+fn replace_path<F>(
+    item: &TransferItem,
+    mode: TransferMode,
+    trash_dir: &Path,
+    progress: &mut TransferProgress,
+    on_progress: &mut F,
+) -> Result<TrashEntry>
+where
+    F: FnMut(&TransferProgress),
+{
+    let source = Path::new(&item.source);
+    let target = Path::new(&item.target);
+    let staged: PathBuf = staging_path(target);
+    let renamed: bool = match mode {
+        TransferMode::Move => rename_path(source, &staged)?,
+        TransferMode::Copy => false,
+    };
+    match renamed {
+        true => count_moved(&staged, progress, on_progress),
+        false => copy_new_path(source, &staged, progress, on_progress)?,
+    }
+    let unstage = |staged: &Path| match renamed {
+        true => fs::rename(staged, source).ok(),
+        false => remove_path(staged).ok(),
+    };
+    let replaced: TrashEntry = match trash_path(trash_dir, &item.target) {
+        Ok(entry) => entry,
+        Err(e) => {
+            unstage(&staged);
+            return Err(e);
+        }
+    };
+    if let Err(e) = fs::rename(&staged, target) {
+        restore_trash_entry(&replaced).ok();
+        unstage(&staged);
+        return Err(e).with_context(|| format!("failed to replace '{}'", item.target));
+    }
+    if mode == TransferMode::Move && !renamed {
+        remove_path(source)?;
+    }
+    Ok(replaced)
+}
+
+/// Free hidden name next to the target, e.g. `.notes.txt.part` for `notes.txt`.
+fn staging_path(target: &Path) -> PathBuf {
+    let dir: &Path = target.parent().unwrap_or(Path::new("/"));
+    let name: String = target
+        .file_name()
+        .map(|it| it.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let staged_name = format!(".{}.part", name);
+    match path_exists(&dir.join(&staged_name).to_string_lossy()) {
+        true => dir.join(suggest_free_name(&dir.to_string_lossy(), &staged_name)),
+        false => dir.join(staged_name),
+    }
+}
+
+fn count_moved<F>(path: &Path, progress: &mut TransferProgress, on_progress: &mut F)
+where
+    F: FnMut(&TransferProgress),
+{
+    let mut moved = TransferProgress::default();
+    measure_path(path, &mut moved);
+    progress.done_files += moved.total_files;
+    progress.done_bytes += moved.total_bytes;
+    on_progress(progress);
+}
+
+/// Copies to a path that didn't exist, deleting the partial copy when it fails.
+fn copy_new_path<F>(
+    source: &Path,
+    target: &Path,
+    progress: &mut TransferProgress,
+    on_progress: &mut F,
+) -> Result<()>
+where
+    F: FnMut(&TransferProgress),
+{
+    copy_path(source, target, progress, on_progress).inspect_err(|_| {
+        remove_path(target).ok();
+    })
+}
+
+fn copy_path<F>(
+    source: &Path,
+    target: &Path,
+    progress: &mut TransferProgress,
+    on_progress: &mut F,
+) -> Result<()>
+where
+    F: FnMut(&TransferProgress),
+{
+    let metadata = fs::symlink_metadata(source)
+        .with_context(|| format!("failed to read '{}'", source.to_string_lossy()))?;
+    if metadata.is_symlink() {
+        let link = fs::read_link(source)
+            .with_context(|| format!("failed to read link '{}'", source.to_string_lossy()))?;
+        symlink(&link, target)
+            .with_context(|| format!("failed to create link '{}'", target.to_string_lossy()))?;
+    } else if metadata.is_dir() {
+        fs::create_dir(target).with_context(|| {
+            format!("failed to create directory '{}'", target.to_string_lossy())
+        })?;
+        let entries = fs::read_dir(source)
+            .with_context(|| format!("failed to read directory '{}'", source.to_string_lossy()))?;
+        for entry in entries {
+            let entry = entry.context("failed to list a file")?;
+            copy_path(
+                &entry.path(),
+                &target.join(entry.file_name()),
+                progress,
+                on_progress,
+            )?;
+        }
+        // applied last, a read-only directory would reject its own content
+        fs::set_permissions(target, metadata.permissions()).ok();
+        return Ok(());
+    } else {
+        fs::copy(source, target).with_context(|| {
+            format!(
+                "failed to copy '{}' to '{}'",
+                source.to_string_lossy(),
+                target.to_string_lossy()
+            )
+        })?;
+    }
+    progress.done_files += 1;
+    progress.done_bytes += metadata.len();
+    on_progress(progress);
+    Ok(())
+}
+
+/// Renames the path, returning false when the target is on another filesystem,
+/// so that it has to be copied and deleted instead.
+fn rename_path(source: &Path, target: &Path) -> Result<bool> {
+    match fs::rename(source, target) {
+        Ok(()) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::CrossesDevices => Ok(false),
+        Err(e) => Err(e).with_context(|| {
+            format!(
+                "failed to move '{}' to '{}'",
+                source.to_string_lossy(),
+                target.to_string_lossy()
+            )
+        }),
+    }
+}
+
+/// Renames the path, copying and deleting it when it's on another filesystem.
+pub fn move_path(source: &Path, target: &Path) -> Result<()> {
+    if rename_path(source, target)? {
+        return Ok(());
+    }
+    let target_existed = path_exists(&target.to_string_lossy());
+    let copied = copy_path(
+        source,
+        target,
+        &mut TransferProgress::default(),
+        &mut |_| {},
+    );
+    if let Err(e) = copied {
+        if !target_existed {
+            remove_path(target).ok(); // partial copy
+        }
+        return Err(e);
+    }
+    remove_path(source)
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    match metadata.is_dir() {
+        true => fs::remove_dir_all(path),
+        false => fs::remove_file(path),
+    }
+    .with_context(|| format!("failed to delete '{}'", path.to_string_lossy()))
+}
@@ -0,0 +1,246 @@
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, Permissions};
+    use std::os::unix::fs::{symlink, PermissionsExt};
+    use std::os::unix::net::UnixListener;
+    use std::path::{Path, PathBuf};
+
+    use crate::testing::{path_string, TempDir};
+    use crate::transfer::{
+        measure_transfer, move_path, plan_transfer, run_transfer, suggest_free_name, TransferItem,
+        TransferMode, TransferProgress,
+    };
+    use crate::undo::{revert_change, UndoRecord};
+
+    /// src/tree/{a.txt, sub/b.txt, link -> a.txt}, src/single.txt and an empty dst/
+    fn create_sources(dir: &Path) {
+        fs::create_dir_all(dir.join("src/tree/sub")).unwrap();
+        fs::create_dir_all(dir.join("dst")).unwrap();
+        fs::write(dir.join("src/tree/a.txt"), "aaa").unwrap();
+        fs::write(dir.join("src/tree/sub/b.txt"), "bb").unwrap();
+        symlink("a.txt", dir.join("src/tree/link")).unwrap();
+        fs::write(dir.join("src/single.txt"), "single").unwrap();
+    }
+
+    fn transfer(
+        items: &[TransferItem],
+        mode: TransferMode,
+        trash_dir: &Path,
+    ) -> (Vec<String>, TransferProgress) {
+        let mut progress = measure_transfer(items);
+        let outcome = run_transfer(items, mode, Some(trash_dir), &mut progress, &mut |_| {});
+        (outcome.errors, progress)
+    }
+
+    #[test]
+    fn test_copy_tree() {
+        let dir = TempDir::new("transfer-copy");
+        create_sources(&dir);
+        let sources = vec![
+            path_string(&dir.join("src/tree")),
+            path_string(&dir.join("src/single.txt")),
+        ];
+        let items = plan_transfer(&sources, &path_string(&dir.join("dst"))).unwrap();
+        assert_eq!(items[0].target, path_string(&dir.join("dst/tree")));
+
+        let (errors, progress) = transfer(&items, TransferMode::Copy, &dir.join("Trash"));
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(progress.total_files, 4);
+        assert_eq!(progress.done_files, 4);
+        assert_eq!(progress.percent(), 100);
+        assert_eq!(
+            fs::read_to_string(dir.join("dst/tree/sub/b.txt")).unwrap(),
+            "bb"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("dst/single.txt")).unwrap(),
+            "single"
+        );
+        assert_eq!(
+            fs::read_link(dir.join("dst/tree/link")).unwrap(),
+            PathBuf::from("a.txt")
+        );
+        assert!(dir.join("src/tree/a.txt").exists());
+    }
+
+    #[test]
+    fn test_copy_read_only_directory() {
+        let dir = TempDir::new("transfer-read-only");
+        create_sources(&dir);
+        let tree = dir.join("src/tree");
+        let copied_tree = dir.join("dst/tree");
+        fs::set_permissions(&tree, Permissions::from_mode(0o555)).unwrap();
+        let items = plan_transfer(&[path_string(&tree)], &path_string(&dir.join("dst"))).unwrap();
+
+        let (errors, _) = transfer(&items, TransferMode::Copy, &dir.join("Trash"));
+        let copied_mode = fs::metadata(&copied_tree).unwrap().permissions().mode();
+        for path in [&tree, &copied_tree] {
+            fs::set_permissions(path, Permissions::from_mode(0o755)).unwrap();
+        }
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(copied_mode & 0o777, 0o555);
+        assert!(copied_tree.join("sub/b.txt").exists());
+    }
+
+    #[test]
+    fn test_move_and_conflicts() {
+        let dir = TempDir::new("transfer-move");
+        create_sources(&dir);
+        fs::write(dir.join("dst/single.txt"), "old").unwrap();
+        let sources = vec![
+            path_string(&dir.join("src/tree")),
+            path_string(&dir.join("src/single.txt")),
+        ];
+        let mut items = plan_transfer(&sources, &path_string(&dir.join("dst"))).unwrap();
+
+        let (errors, _) = transfer(&items, TransferMode::Move, &dir.join("Trash"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("already exists"));
+        assert!(dir.join("dst/tree/sub/b.txt").exists());
+        assert!(!dir.join("src/tree").exists());
+        assert_eq!(
+            fs::read_to_string(dir.join("dst/single.txt")).unwrap(),
+            "old"
+        );
+
+        items.remove(0);
+        items[0].overwrite = true;
+        let (errors, _) = transfer(&items, TransferMode::Move, &dir.join("Trash"));
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(
+            fs::read_to_string(dir.join("dst/single.txt")).unwrap(),
+            "single"
+        );
+        assert!(!dir.join("src/single.txt").exists());
+    }
+
+    #[test]
+    fn test_overwrite_and_undo() {
+        let dir = TempDir::new("transfer-overwrite");
+        let trash_dir = dir.join("Trash");
+        create_sources(&dir);
+        fs::create_dir_all(dir.join("dst/tree")).unwrap();
+        fs::write(dir.join("dst/tree/old.txt"), "old").unwrap();
+        let mut items = plan_transfer(
+            &[path_string(&dir.join("src/tree"))],
+            &path_string(&dir.join("dst")),
+        )
+        .unwrap();
+        items[0].overwrite = true;
+
+        let mut progress = measure_transfer(&items);
+        let outcome = run_transfer(
+            &items,
+            TransferMode::Copy,
+            Some(&trash_dir),
+            &mut progress,
+            &mut |_| {},
+        );
+        assert!(outcome.errors.is_empty(), "{:?}", outcome.errors);
+        assert_eq!(outcome.done_items, items);
+        assert_eq!(outcome.replaced.len(), 1);
+        assert!(dir.join("dst/tree/sub/b.txt").exists());
+        assert!(!dir.join("dst/tree/old.txt").exists());
+        assert_eq!(fs::read_dir(dir.join("dst")).unwrap().count(), 1); // nothing staged is left
+
+        let record = UndoRecord::Transfer {
+            mode: TransferMode::Copy,
+            items: outcome.done_items,
+            replaced: outcome.replaced,
+        };
+        revert_change(&record, &trash_dir).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("dst/tree/old.txt")).unwrap(),
+            "old"
+        );
+        assert!(!dir.join("dst/tree/sub").exists());
+    }
+
+    #[test]
+    fn test_failed_overwrite_keeps_target() {
+        let dir = TempDir::new("transfer-overwrite-failed");
+        create_sources(&dir);
+        let _socket = UnixListener::bind(dir.join("src/tree/socket")).unwrap(); // can't be copied
+        fs::create_dir_all(dir.join("dst/tree")).unwrap();
+        fs::write(dir.join("dst/tree/old.txt"), "old").unwrap();
+        let mut items = plan_transfer(
+            &[path_string(&dir.join("src/tree"))],
+            &path_string(&dir.join("dst")),
+        )
+        .unwrap();
+        items[0].overwrite = true;
+
+        let (errors, _) = transfer(&items, TransferMode::Copy, &dir.join("Trash"));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            fs::read_to_string(dir.join("dst/tree/old.txt")).unwrap(),
+            "old"
+        );
+        assert_eq!(fs::read_dir(dir.join("dst")).unwrap().count(), 1);
+        assert!(!dir.join("Trash/files/tree").exists());
+    }
+
+    #[test]
+    fn test_failed_copy_leaves_no_partial_tree() {
+        let dir = TempDir::new("transfer-copy-failed");
+        create_sources(&dir);
+        // tests run as root, which reads any file, but a socket can't be copied either
+        let _socket = UnixListener::bind(dir.join("src/tree/sub/socket")).unwrap();
+        let items = plan_transfer(
+            &[path_string(&dir.join("src/tree"))],
+            &path_string(&dir.join("dst")),
+        )
+        .unwrap();
+
+        let (errors, _) = transfer(&items, TransferMode::Copy, &dir.join("Trash"));
+        assert_eq!(errors.len(), 1);
+        assert!(!dir.join("dst/tree").exists());
+        // retrying isn't blocked by leftovers
+        fs::remove_file(dir.join("src/tree/sub/socket")).unwrap();
+        let (errors, _) = transfer(&items, TransferMode::Copy, &dir.join("Trash"));
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(dir.join("dst/tree/sub/b.txt").exists());
+    }
+
+    #[test]
+    fn test_paste_into_itself() {
+        let dir = TempDir::new("transfer-itself");
+        create_sources(&dir);
+        let sources = vec![path_string(&dir.join("src/tree"))];
+        assert!(plan_transfer(&sources, &path_string(&dir.join("src/tree"))).is_err());
+        assert!(plan_transfer(&sources, &path_string(&dir.join("src/tree/sub"))).is_err());
+        assert!(plan_transfer(&sources, &path_string(&dir.join("src/tree2"))).is_ok());
+
+        // overwriting a directory that contains the pasted path
+        let items = vec![TransferItem {
+            source: path_string(&dir.join("src/tree/sub")),
+            target: path_string(&dir.join("src/tree")),
+            overwrite: true,
+        }];
+        let (errors, _) = transfer(&items, TransferMode::Copy, &dir.join("Trash"));
+        assert_eq!(errors.len(), 1);
+        assert!(dir.join("src/tree/sub/b.txt").exists());
+    }
+
+    #[test]
+    fn test_move_fails_without_copying() {
+        let dir = TempDir::new("transfer-move-error");
+        create_sources(&dir);
+        let tree = dir.join("src/tree");
+        assert!(move_path(&tree, &tree.join("sub/tree")).is_err());
+        assert!(!tree.join("sub/tree").exists());
+        assert!(tree.join("sub/b.txt").exists());
+        assert!(move_path(&dir.join("src/missing"), &dir.join("dst/missing")).is_err());
+    }
+
+    #[test]
+    fn test_suggest_free_name() {
+        let dir = TempDir::new("transfer-names");
+        fs::write(dir.join("notes.txt"), "").unwrap();
+        fs::write(dir.join("notes (1).txt"), "").unwrap();
+        let dir_str = path_string(&dir);
+        assert_eq!(suggest_free_name(&dir_str, "notes.txt"), "notes (2).txt");
+        assert_eq!(suggest_free_name(&dir_str, "Makefile"), "Makefile (1)");
+        assert_eq!(suggest_free_name(&dir_str, ".bashrc"), ".bashrc (1)");
+    }
+}
@@ -0,0 +1,322 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{Local, NaiveDateTime, Timelike};
+use std::cmp::Ordering;
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+use std::path::{Path, PathBuf};
+
+use crate::logs::log;
+use crate::storage::xdg_data_home;
+use crate::transfer::{move_path, path_exists};
+
+const TRASH_INFO_EXTENSION: &str = ".trashinfo";
+const DELETION_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Item in the trash, described by its `.trashinfo` file
+/// (see the freedesktop.org Trash specification).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashEntry {
+    pub trash_dir: PathBuf, // the home trash or the one of the filesystem the item was on
+    pub name: String,       // name in the `files` and `info` directories
+    pub original_path: String,
+    pub deletion_date: Option<NaiveDateTime>,
+}
+
+/// Home trash: `$XDG_DATA_HOME/Trash` or `~/.local/share/Trash`.
+pub fn default_trash_dir() -> Option<PathBuf> {
+    xdg_data_home().map(|dir| dir.join("Trash"))
+}
+
+/// Moves the path to the trash, recording where it came from.
+/// Returns the entry, so that it can be restored later.
+/// Paths on other filesystems than the home trash go to the trash at the top of their own.
+pub fn trash_path(home_trash: &Path, abs_path: &str) -> Result<TrashEntry> {
+    let trash_dir: PathBuf = select_trash_dir(home_trash, abs_path)?;
+    let files_dir = trash_dir.join("files");
+    let info_dir = trash_dir.join("info");
+    for dir in [&files_dir, &info_dir] {
+        fs::create_dir_all(dir).with_context(|| {
+            format!(
+                "failed to create trash directory '{}'",
+                dir.to_string_lossy()
+            )
+        })?;
+    }
+    let file_name: String = Path::new(abs_path)
+        .file_name()
+        .ok_or_else(|| anyhow!("can't move '{}' to trash", abs_path))?
+        .to_string_lossy()
+        .to_string();
+    // whole seconds, like in the info file
+    let deletion_date = Local::now().naive_local().with_nanosecond(0).unwrap();
+    let info_content = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        percent_encode_path(abs_path),
+        deletion_date.format(DELETION_DATE_FORMAT)
+    );
+
+    // creating the info file first reserves the name, as the specification requires
+    let mut counter = 1;
+    let name: String = loop {
+        let name = match counter {
+            1 => file_name.clone(),
+            _ => format!("{}.{}", file_name, counter),
+        };
+        let info_path = info_dir.join(format!("{}{}", name, TRASH_INFO_EXTENSION));
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(mut info_file) if !path_exists(&files_dir.join(&name).to_string_lossy()) => {
+                info_file
+                    .write_all(info_content.as_bytes())
+                    .context("failed to write trash info file")?;
+                break name;
+            }
+            Ok(_) => {
+                fs::remove_file(&info_path).ok();
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e).context("failed to create trash info file"),
+        }
+        counter += 1;
+    };
+
+    log(format!("Moving {} to trash as {}", abs_path, name).as_str());
+    if let Err(e) = move_path(Path::new(abs_path), &files_dir.join(&name)) {
+        fs::remove_file(info_file_path(&trash_dir, &name)).ok();
+        return Err(e.context(format!("failed to move '{}' to trash", abs_path)));
+    }
+    Ok(TrashEntry {
+        trash_dir,
+        name,
+        original_path: abs_path.to_string(),
+        deletion_date: Some(deletion_date),
+    })
+}
+
+/// Trash for the path, so that trashing it never copies:
+/// the home trash when they share a filesystem,
+/// otherwise `$topdir/.Trash-$uid` at the top of the path's filesystem, created when missing.
+pub fn select_trash_dir(home_trash: &Path, abs_path: &str) -> Result<PathBuf> {
+    let path = Path::new(abs_path);
+    let device: u64 = fs::symlink_metadata(path)
+        .with_context(|| format!("failed to read '{}'", abs_path))?
+        .dev();
+    let home_device: Option<u64> = home_trash
+        .ancestors()
+        .find_map(|dir| fs::metadata(dir).ok())
+        .map(|metadata| metadata.dev());
+    if home_device == Some(device) {
+        return Ok(home_trash.to_path_buf());
+    }
+    let topdir: &Path = path
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| fs::metadata(dir).ok().map(|it| it.dev()) == Some(device))
+        .last()
+        .ok_or_else(|| anyhow!("can't move mount point '{}' to trash", abs_path))?;
+    let uid: u32 = current_uid()?;
+    let trash_dir: PathBuf = topdir.join(format!(".Trash-{}", uid));
+    match fs::symlink_metadata(&trash_dir) {
+        Ok(metadata) if metadata.is_dir() && metadata.uid() == uid => {}
+        Ok(_) => {
+            return Err(anyhow!(
+                "'{}' is not a trash directory of the current user",
+                trash_dir.to_string_lossy()
+            ))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            DirBuilder::new()
+                .mode(0o700)
+                .create(&trash_dir)
+                .with_context(|| {
+                    format!(
+                        "failed to create trash directory '{}'",
+                        trash_dir.to_string_lossy()
+                    )
+                })?;
+        }
+        Err(e) => return Err(e).context("failed to read trash directory"),
+    }
+    Ok(trash_dir)
+}
+
+/// Effective user id, the owner of the process's own `/proc` entry.
+fn current_uid() -> Result<u32> {
+    fs::metadata("/proc/self")
+        .map(|metadata| metadata.uid())
+        .context("failed to determine the user id")
+}
+
+/// Home trash followed by the trashes of the current user found at the top of mounted filesystems.
+pub fn list_trash_dirs(home_trash: &Path) -> Vec<PathBuf> {
+    let mut trash_dirs: Vec<PathBuf> = vec![home_trash.to_path_buf()];
+    let uid: u32 = match current_uid() {
+        Ok(uid) => uid,
+        Err(_) => return trash_dirs,
+    };
+    let mounts: String = fs::read_to_string("/proc/self/mounts").unwrap_or_default();
+    for mount_point in mounts.lines().filter_map(|line| line.split(' ').nth(1)) {
+        let trash_dir: PathBuf =
+            Path::new(&unescape_mount_point(mount_point)).join(format!(".Trash-{}", uid));
+        if trash_dir.is_dir() && !trash_dirs.contains(&trash_dir) {
+            trash_dirs.push(trash_dir);
+        }
+    }
+    trash_dirs
+}
+
+/// Decodes the octal escapes, like `\040` for a space, of a mount point in `/proc/self/mounts`.
+pub fn unescape_mount_point(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes
+            .get(i + 1..i + 4)
+            .and_then(|octal| std::str::from_utf8(octal).ok())
+            .and_then(|octal| u8::from_str_radix(octal, 8).ok());
+        match (bytes[i], octal) {
+            (b'\\', Some(byte)) => {
+                decoded.push(byte);
+                i += 4;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+/// Lists the items of all the trashes, most recently deleted first.
+pub fn list_all_trash(home_trash: &Path) -> Result<Vec<TrashEntry>> {
+    let mut entries: Vec<TrashEntry> = Vec::new();
+    for trash_dir in list_trash_dirs(home_trash) {
+        entries.extend(list_trash(&trash_dir)?);
+    }
+    entries.sort_by(by_deletion_date);
+    Ok(entries)
+}
+
+/// Lists the trashed items, most recently deleted first.
+/// Info files that can't be parsed are skipped.
+pub fn list_trash(trash_dir: &Path) -> Result<Vec<TrashEntry>> {
+    let info_dir = trash_dir.join("info");
+    let dir_entries = match fs::read_dir(&info_dir) {
+        Ok(dir_entries) => dir_entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e).context("failed to read trash directory"),
+    };
+    let mut entries: Vec<TrashEntry> = dir_entries
+        .filter_map(|dir_entry| {
+            let file_name = dir_entry.ok()?.file_name().to_string_lossy().to_string();
+            let name = file_name.strip_suffix(TRASH_INFO_EXTENSION)?.to_string();
+            let content = fs::read_to_string(info_dir.join(&file_name)).ok()?;
+            parse_trash_info(trash_dir, name, &content)
+        })
+        .collect();
+    entries.sort_by(by_deletion_date);
+    Ok(entries)
+}
+
+fn by_deletion_date(a: &TrashEntry, b: &TrashEntry) -> Ordering {
+    b.deletion_date
+        .cmp(&a.deletion_date)
+        .then(a.name.cmp(&b.name))
+}
+
+pub fn parse_trash_info(trash_dir: &Path, name: String, content: &str) -> Option<TrashEntry> {
+    let mut lines = content.lines().map(|line| line.trim());
+    if lines.next()? != "[Trash Info]" {
+        return None;
+    }
+    let mut original_path: Option<String> = None;
+    let mut deletion_date: Option<NaiveDateTime> = None;
+    for line in lines {
+        if let Some(value) = line.strip_prefix("Path=") {
+            let path = percent_decode(value);
+            // relative paths are allowed in the trash at the top of a filesystem
+            original_path = match path.starts_with('/') {
+                true => Some(path),
+                false => trash_dir
+                    .parent()
+                    .map(|topdir| topdir.join(&path).to_string_lossy().to_string()),
+            };
+        } else if let Some(value) = line.strip_prefix("DeletionDate=") {
+            deletion_date = NaiveDateTime::parse_from_str(value, DELETION_DATE_FORMAT).ok();
+        }
+    }
+    Some(TrashEntry {
+        trash_dir: trash_dir.to_path_buf(),
+        name,
+        original_path: original_path?,
+        deletion_date,
+    })
+}
+
+/// Moves the item back to its original location, recreating missing parent directories.
+pub fn restore_trash_entry(entry: &TrashEntry) -> Result<()> {
+    if path_exists(&entry.original_path) {
+        return Err(anyhow!("'{}' already exists", entry.original_path));
+    }
+    let original_path = Path::new(&entry.original_path);
+    if let Some(parent) = original_path.parent() {
+        fs::create_dir_all(parent).with_context(|| {
+            format!("failed to create directory '{}'", parent.to_string_lossy())
+        })?;
+    }
+    log(format!("Restoring {} from trash", entry.original_path).as_str());
+    move_path(
+        &entry.trash_dir.join("files").join(&entry.name),
+        original_path,
+    )
+    .with_context(|| format!("failed to restore '{}'", entry.original_path))?;
+    fs::remove_file(info_file_path(&entry.trash_dir, &entry.name))
+        .context("failed to delete trash info file")
+}
+
+fn info_file_path(trash_dir: &Path, name: &str) -> PathBuf {
+    trash_dir
+        .join("info")
+        .join(format!("{}{}", name, TRASH_INFO_EXTENSION))
+}
+
+/// Escapes the path like a URL path, leaving slashes and unreserved characters as they are.
+pub fn percent_encode_path(path: &str) -> String {
+    path.bytes()
+        .map(
+            |b| match b.is_ascii_alphanumeric() || b"/-_.~".contains(&b) {
+                true => (b as char).to_string(),
+                false => format!("%{:02X}", b),
+            },
+        )
+        .collect()
+}
+
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::Path;
+
+    use crate::testing::TempDir;
+    use crate::trash::{
+        list_all_trash, list_trash, parse_trash_info, percent_decode, percent_encode_path,
+        restore_trash_entry, trash_path, unescape_mount_point,
+    };
+
+    #[test]
+    fn test_trash_and_restore() {
+        let dir = TempDir::new("trash-restore");
+        let trash_dir = dir.join("Trash");
+        fs::create_dir_all(dir.join("work/sub dir")).unwrap();
+        let file_path = dir.join("work/sub dir/notes.txt");
+        let file_path_str = file_path.to_string_lossy().to_string();
+        fs::write(&file_path, "first").unwrap();
+
+        let first = trash_path(&trash_dir, &file_path_str).unwrap();
+        assert_eq!(first.name, "notes.txt");
+        assert!(!file_path.exists());
+        assert!(trash_dir.join("files/notes.txt").exists());
+        let info = fs::read_to_string(trash_dir.join("info/notes.txt.trashinfo")).unwrap();
+        assert!(info.starts_with("[Trash Info]\n"));
+        assert!(info.contains("sub%20dir/notes.txt\n"));
+        assert!(info.contains("DeletionDate="));
+
+        // same name trashed again gets a unique name
+        fs::write(&file_path, "second").unwrap();
+        let second = trash_path(&trash_dir, &file_path_str).unwrap();
+        assert_eq!(second.name, "notes.txt.2");
+
+        let entries = list_trash(&trash_dir).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|it| it.original_path == file_path_str));
+
+        restore_trash_entry(&second).unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "second");
+        assert!(!trash_dir.join("info/notes.txt.2.trashinfo").exists());
+        // restoring over an existing file is refused
+        assert!(restore_trash_entry(&first).is_err());
+
+        // missing parent directories are recreated
+        fs::remove_dir_all(dir.join("work")).unwrap();
+        restore_trash_entry(&first).unwrap();
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "first");
+        assert!(list_trash(&trash_dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_trash_on_other_filesystem() {
+        let dir = TempDir::new("trash-device");
+        let other_fs = Path::new("/dev/shm"); // mounted on its own
+        let on_other_fs = |path: &Path| {
+            fs::metadata(path).map(|it| it.dev()).ok()
+                != fs::metadata(&*dir).map(|it| it.dev()).ok()
+        };
+        if !other_fs.is_dir() || !on_other_fs(other_fs) {
+            return; // no second filesystem to test with
+        }
+        let home_trash = dir.join("Trash");
+        let uid = fs::metadata("/proc/self").unwrap().uid();
+        let mount_trash = other_fs.join(format!(".Trash-{}", uid));
+        let mount_trash_existed = mount_trash.exists();
+        let file_path = other_fs.join(format!("fpick-test-{}-trash-device", std::process::id()));
+        fs::write(&file_path, "content").unwrap();
+
+        let entry = trash_path(&home_trash, &file_path.to_string_lossy()).unwrap();
+        let trashed = entry.trash_dir.join("files").join(&entry.name);
+        let listed = list_all_trash(&home_trash).unwrap().contains(&entry);
+        let restored = restore_trash_entry(&entry).is_ok();
+        let content = fs::read_to_string(&file_path).ok();
+        fs::remove_file(&file_path).ok();
+        if !mount_trash_existed {
+            for sub_dir in ["files", "info", ""] {
+                fs::remove_dir(mount_trash.join(sub_dir)).ok();
+            }
+        }
+        assert_eq!(entry.trash_dir, mount_trash);
+        assert!(!trashed.exists());
+        assert!(listed);
+        assert!(restored);
+        assert_eq!(content.as_deref(), Some("content"));
+        assert!(!home_trash.join("files").exists());
+    }
+
+    #[test]
+    fn test_trash_directory() {
+        let dir = TempDir::new("trash-dir");
+        let trash_dir = dir.join("Trash");
+        fs::create_dir_all(dir.join("project/src")).unwrap();
+        fs::write(dir.join("project/src/main.rs"), "").unwrap();
+
+        let entry = trash_path(&trash_dir, &dir.join("project").to_string_lossy()).unwrap();
+        assert!(!dir.join("project").exists());
+        assert!(trash_dir.join("files/project/src/main.rs").exists());
+        restore_trash_entry(&entry).unwrap();
+        assert!(dir.join("project/src/main.rs").exists());
+    }
+
+    #[test]
+    fn test_parse_trash_info() {
+        let trash_dir = Path::new("/media/usb/.Trash-1000");
+        let entry = parse_trash_info(
+            trash_dir,
+            "a.txt".to_string(),
+            "[Trash Info]\nPath=/home/u/%C5%BC%20a.txt\nDeletionDate=2024-03-01T10:20:30\n",
+        )
+        .unwrap();
+        assert_eq!(entry.original_path, "/home/u/ż a.txt");
+        assert_eq!(
+            entry.deletion_date.unwrap().to_string(),
+            "2024-03-01 10:20:30"
+        );
+        assert_eq!(entry.trash_dir, trash_dir);
+        let relative = parse_trash_info(trash_dir, "b".to_string(), "[Trash Info]\nPath=docs/b\n");
+        assert_eq!(relative.unwrap().original_path, "/media/usb/docs/b");
+        assert!(parse_trash_info(trash_dir, "a".to_string(), "Path=/a\n").is_none());
+        assert!(
+            parse_trash_info(trash_dir, "a".to_string(), "[Trash Info]\nDeletionDate=x\n")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_percent_encoding() {
+        assert_eq!(percent_encode_path("/a b/ż%.txt"), "/a%20b/%C5%BC%25.txt");
+        assert_eq!(percent_decode("/a%20b/%C5%BC%25.txt"), "/a b/ż%.txt");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+    }
+
+    #[test]
+    fn test_unescape_mount_point() {
+        assert_eq!(
+            unescape_mount_point("/media/My\\040Drive"),
+            "/media/My Drive"
+        );
+        assert_eq!(unescape_mount_point("/a\\134b"), "/a\\b");
+        assert_eq!(unescape_mount_point("/a\\9"), "/a\\9");
+    }
+}
@@ -1,35 +1,64 @@
+use chrono::{DateTime, Local};
 use ratatui::{
     style::{Style, Stylize},
     text::{Line, Span},
     widgets::ListItem,
 };
+use std::cmp::Ordering;
 
-use crate::filesystem::FileNode;
+use crate::action_menu::short_readable_size;
+use crate::config::Theme;
+use crate::filesystem::{format_mode, owner_name, FileDetails, FileNode, SubtreeEntry};
+use crate::fuzzy::{match_words, MatchMode, TextMatch};
+
+const OWNER_COLUMN_WIDTH: usize = 8;
+const DETAIL_COLUMNS_WIDTH: usize = 10 + 1 + OWNER_COLUMN_WIDTH + 1 + 10 + 1 + 16 + 2;
 
 #[derive(Debug, Clone)]
 pub struct TreeNode {
     pub relevance: i32,
     pub kind: TreeNodeType,
+    pub marked: bool,
+    pub matched_indices: Vec<usize>, // positions of characters matching the filter
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TreeNodeType {
     FileNode(FileNode),
     SelfReference,
+    SubtreeNode(SubtreeEntry), // entry found by recursive search below the current directory
 }
 
 impl TreeNode {
-    pub fn render_list_item(&self) -> ListItem {
-        match &self.kind {
-            TreeNodeType::FileNode(file_node) => self.render_file_node(file_node),
-            TreeNodeType::SelfReference => self.render_self_reference(),
+    pub fn render_list_item(&self, theme: &Theme, long_listing: bool) -> ListItem<'static> {
+        let mut line: Line = match &self.kind {
+            TreeNodeType::FileNode(file_node) => self.render_file_node(file_node, theme),
+            TreeNodeType::SelfReference => self.render_self_reference(theme),
+            TreeNodeType::SubtreeNode(entry) => self.render_subtree_node(entry, theme),
+        };
+        if long_listing {
+            let file_node: Option<&FileNode> = match &self.kind {
+                TreeNodeType::FileNode(file_node) => Some(file_node),
+                TreeNodeType::SelfReference => None,
+                TreeNodeType::SubtreeNode(entry) => Some(entry.file_node()),
+            };
+            let columns = render_detail_columns(file_node);
+            let columns_style = Style::default().fg(theme.details);
+            line.spans.insert(0, Span::styled(columns, columns_style));
+        }
+        if self.marked {
+            let marker_style = Style::default().fg(theme.marked).bold();
+            line.spans.insert(0, Span::styled("* ", marker_style));
+            line = line.italic();
         }
+        line.into()
     }
 
     pub fn indexed_name(&self) -> &str {
         match &self.kind {
             TreeNodeType::FileNode(file_node) => file_node.lowercase_name.as_str(),
             TreeNodeType::SelfReference => ".",
+            TreeNodeType::SubtreeNode(entry) => entry.lowercase_path.as_str(),
         }
     }
 
@@ -37,93 +66,164 @@ impl TreeNode {
         match &self.kind {
             TreeNodeType::FileNode(file_node) => file_node.name.as_str(),
             TreeNodeType::SelfReference => ".",
+            TreeNodeType::SubtreeNode(entry) => entry.file_node().name.as_str(),
         }
     }
 
-    pub fn is_directory(&self) -> bool {
-        match &self.kind {
-            TreeNodeType::FileNode(file_node) => file_node.is_directory,
-            TreeNodeType::SelfReference => true,
-        }
+    pub fn render_file_node(&self, file_node: &FileNode, theme: &Theme) -> Line<'static> {
+        render_file_name(file_node, &self.matched_indices, theme)
     }
 
-    pub fn render_file_node(&self, file_node: &FileNode) -> ListItem {
-        let display: String = file_node.name.clone();
-        let mut suffix = String::new();
-        let mut style = Style::default();
-        if file_node.is_symlink {
-            suffix = format!("{suffix}@");
-            style = Style::default().fg(ratatui::style::Color::LightCyan).bold();
-        }
-        if file_node.is_directory {
-            suffix = format!("{suffix}/");
-            style = Style::default().fg(ratatui::style::Color::LightBlue).bold();
+    pub fn render_subtree_node(&self, entry: &SubtreeEntry, theme: &Theme) -> Line<'static> {
+        let file_node = entry.file_node();
+        let prefix_len = entry.relative_path.chars().count() - file_node.name.chars().count();
+        let name_indices: Vec<usize> = self
+            .matched_indices
+            .iter()
+            .filter(|i| **i >= prefix_len)
+            .map(|i| i - prefix_len)
+            .collect();
+        let mut line = render_file_name(file_node, &name_indices, theme);
+        if prefix_len > 0 {
+            let dir_prefix: String = entry.relative_path.chars().take(prefix_len).collect();
+            let prefix_style = Style::default().fg(theme.subtree_prefix);
+            let prefix_spans =
+                highlight_matches(dir_prefix, prefix_style, &self.matched_indices, theme);
+            line.spans.splice(0..0, prefix_spans);
         }
-        Line::from(vec![Span::styled(display, style), Span::raw(suffix)]).into()
+        line
+    }
+
+    pub fn render_self_reference(&self, theme: &Theme) -> Line<'static> {
+        let style = Style::default().fg(theme.self_reference).bold();
+        Line::from(vec![Span::styled(".", style)])
+    }
+}
+
+/// Aligned columns with permissions, owner, size and modification time.
+/// Columns are blank when the details weren't collected.
+pub fn render_detail_columns(file_node: Option<&FileNode>) -> String {
+    let (file_node, details): (&FileNode, FileDetails) = match file_node {
+        Some(file_node) => match file_node.details {
+            Some(details) => (file_node, details),
+            None => return " ".repeat(DETAIL_COLUMNS_WIDTH),
+        },
+        None => return " ".repeat(DETAIL_COLUMNS_WIDTH),
+    };
+    let mode = format_mode(details.mode, file_node.is_directory, file_node.is_symlink);
+    let mut owner = owner_name(details.uid);
+    if owner.chars().count() > OWNER_COLUMN_WIDTH {
+        owner = owner.chars().take(OWNER_COLUMN_WIDTH - 1).collect();
+        owner.push('…');
     }
+    let size = match file_node.is_directory {
+        true => "-".to_string(),
+        false => short_readable_size(details.size),
+    };
+    let modified: String = match details.modified {
+        Some(modified) => {
+            let dt: DateTime<Local> = modified.into();
+            dt.format("%Y-%m-%d %H:%M").to_string()
+        }
+        None => "-".to_string(),
+    };
+    format!(
+        "{} {:<owner_w$} {:>10} {:<16}  ",
+        mode,
+        owner,
+        size,
+        modified,
+        owner_w = OWNER_COLUMN_WIDTH
+    )
+}
 
-    pub fn render_self_reference(&self) -> ListItem {
-        let style = Style::default()
-            .fg(ratatui::style::Color::LightYellow)
-            .bold();
-        Line::from(vec![Span::styled(".", style)]).into()
+fn render_file_name<'a>(
+    file_node: &FileNode,
+    matched_indices: &[usize],
+    theme: &Theme,
+) -> Line<'a> {
+    let mut suffix = String::new();
+    let mut style = Style::default();
+    if file_node.is_symlink {
+        suffix = format!("{suffix}@");
+        style = Style::default().fg(theme.symlink).bold();
+    }
+    if file_node.is_directory {
+        suffix = format!("{suffix}/");
+        style = Style::default().fg(theme.directory).bold();
     }
+    let mut spans = highlight_matches(file_node.name.clone(), style, matched_indices, theme);
+    spans.push(Span::raw(suffix));
+    Line::from(spans)
 }
 
-pub fn evaluate_relevance(text: &str, words: &Vec<String>) -> i32 {
-    if words.is_empty() {
-        return 0;
+/// Splits the text into spans, emphasizing the characters at matched positions.
+pub fn highlight_matches<'a>(
+    text: String,
+    style: Style,
+    matched_indices: &[usize],
+    theme: &Theme,
+) -> Vec<Span<'a>> {
+    if matched_indices.is_empty() {
+        return vec![Span::styled(text, style)];
     }
-    let mut relevance = 0;
-    for word in words {
-        if text.contains(word) {
-            relevance += 1;
-        } else {
-            return 0;
+    let highlight_style = style.fg(theme.match_highlight).bold().underlined();
+    let mut spans: Vec<Span> = Vec::new();
+    let mut chunk = String::new();
+    let mut chunk_highlighted = false;
+    for (i, c) in text.chars().enumerate() {
+        let highlighted = matched_indices.contains(&i);
+        if highlighted != chunk_highlighted && !chunk.is_empty() {
+            let chunk_style = match chunk_highlighted {
+                true => highlight_style,
+                false => style,
+            };
+            spans.push(Span::styled(std::mem::take(&mut chunk), chunk_style));
         }
+        chunk_highlighted = highlighted;
+        chunk.push(c);
     }
-    let firs_word = words.first().unwrap();
-    if text.starts_with(firs_word) {
-        relevance += 10;
+    if !chunk.is_empty() {
+        let chunk_style = match chunk_highlighted {
+            true => highlight_style,
+            false => style,
+        };
+        spans.push(Span::styled(chunk, chunk_style));
     }
-    relevance
+    spans
 }
 
-pub fn render_tree_nodes(child_nodes: &Vec<FileNode>, filter_text: &str) -> Vec<TreeNode> {
-    let filter_words: Vec<String> = filter_text
+fn split_filter_words(filter_text: &str) -> Vec<String> {
+    filter_text
         .to_lowercase()
         .split_whitespace()
         .map(|it| it.to_lowercase())
-        .collect();
+        .collect()
+}
+
+pub fn render_tree_nodes(
+    child_nodes: &[FileNode],
+    filter_text: &str,
+    match_mode: MatchMode,
+) -> Vec<TreeNode> {
+    let filter_words: Vec<String> = split_filter_words(filter_text);
 
     let mut current_tree_nodes: Vec<TreeNode> = child_nodes
         .iter()
-        .map(|it: &FileNode| TreeNode {
-            relevance: 0,
-            kind: TreeNodeType::FileNode(it.clone()),
-        })
-        .collect();
-    current_tree_nodes = current_tree_nodes
-        .iter()
-        .map(|it: &TreeNode| TreeNode {
-            relevance: evaluate_relevance(it.indexed_name(), &filter_words),
-            kind: it.kind.clone(),
+        .filter_map(|it: &FileNode| {
+            let text_match = match_words(&it.name, &filter_words, match_mode)?;
+            Some(TreeNode {
+                relevance: text_match.score,
+                kind: TreeNodeType::FileNode(it.clone()),
+                marked: false,
+                matched_indices: text_match.indices,
+            })
         })
         .collect();
 
-    if !filter_text.is_empty() {
-        current_tree_nodes = current_tree_nodes
-            .into_iter()
-            .filter(|it| it.relevance > 0)
-            .collect();
-    }
-
-    current_tree_nodes.sort_by(|a: &TreeNode, b: &TreeNode| {
-        let first_cmp = a.relevance.cmp(&b.relevance).reverse();
-        first_cmp
-            .then(a.is_directory().cmp(&b.is_directory()).reverse())
-            .then(a.indexed_name().cmp(b.indexed_name()))
-    });
+    // stable sort keeps the listing order among equally relevant nodes
+    current_tree_nodes
+        .sort_by(|a: &TreeNode, b: &TreeNode| a.relevance.cmp(&b.relevance).reverse());
 
     if filter_text.is_empty() {
         current_tree_nodes.insert(
@@ -131,9 +231,92 @@ pub fn render_tree_nodes(child_nodes: &Vec<FileNode>, filter_text: &str) -> Vec<
             TreeNode {
                 relevance: 0,
                 kind: TreeNodeType::SelfReference,
+                marked: false,
+                matched_indices: vec![],
             },
         );
     }
 
     current_tree_nodes
 }
+
+/// Best matches of the recursive search, the most relevant first.
+/// Only the kept `max_results` entries are sorted and cloned.
+pub fn render_subtree_nodes(
+    entries: &[SubtreeEntry],
+    filter_text: &str,
+    match_mode: MatchMode,
+    max_results: usize,
+) -> Vec<TreeNode> {
+    let filter_words: Vec<String> = split_filter_words(filter_text);
+
+    let mut matches: Vec<(&SubtreeEntry, TextMatch)> = entries
+        .iter()
+        .filter_map(|it: &SubtreeEntry| {
+            let text_match = match_words(&it.relative_path, &filter_words, match_mode)?;
+            Some((it, text_match))
+        })
+        .collect();
+    let by_relevance = |a: &(&SubtreeEntry, TextMatch), b: &(&SubtreeEntry, TextMatch)| {
+        compare_subtree_relevance(
+            (a.1.score, &a.0.lowercase_path),
+            (b.1.score, &b.0.lowercase_path),
+        )
+    };
+    if matches.len() > max_results {
+        matches.select_nth_unstable_by(max_results, by_relevance);
+        matches.truncate(max_results);
+    }
+    matches.sort_by(by_relevance);
+
+    matches
+        .into_iter()
+        .map(|(entry, text_match)| TreeNode {
+            relevance: text_match.score,
+            kind: TreeNodeType::SubtreeNode(entry.clone()),
+            marked: false,
+            matched_indices: text_match.indices,
+        })
+        .collect()
+}
+
+/// Adds matches among newly found entries to the already ranked nodes,
+/// so that a batch of results doesn't re-rank everything found before.
+pub fn merge_subtree_nodes(
+    ranked_nodes: Vec<TreeNode>,
+    new_entries: &[SubtreeEntry],
+    filter_text: &str,
+    match_mode: MatchMode,
+    max_results: usize,
+) -> Vec<TreeNode> {
+    let new_nodes = render_subtree_nodes(new_entries, filter_text, match_mode, max_results);
+    let mut ranked_nodes = ranked_nodes.into_iter().peekable();
+    let mut new_nodes = new_nodes.into_iter().peekable();
+    let mut merged: Vec<TreeNode> = Vec::new();
+    while merged.len() < max_results {
+        let take_new = match (ranked_nodes.peek(), new_nodes.peek()) {
+            (Some(ranked), Some(new)) => {
+                compare_subtree_relevance(
+                    (new.relevance, new.indexed_name()),
+                    (ranked.relevance, ranked.indexed_name()),
+                ) == Ordering::Less
+            }
+            (Some(_), None) => false,
+            (None, Some(_)) => true,
+            (None, None) => break,
+        };
+        let next = match take_new {
+            true => new_nodes.next(),
+            false => ranked_nodes.next(),
+        };
+        merged.extend(next);
+    }
+    merged
+}
+
+/// Better score first, then shorter and alphabetically earlier paths.
+fn compare_subtree_relevance(a: (i32, &str), b: (i32, &str)) -> Ordering {
+    b.0.cmp(&a.0)
+        .then(a.1.len().cmp(&b.1.len()))
+        .then(a.1.cmp(b.1))
+}
@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::atomic::Ordering;
+    use std::time::{Duration, Instant};
+
+    use crate::app::App;
+    use crate::filesystem::{
+        get_path_file_nodes, FileNode, FileType, SubtreeEntry, MAX_SUBTREE_ENTRIES,
+    };
+    use crate::fuzzy::MatchMode;
+    use crate::testing::{path_string, TempDir};
+    use crate::tree::{merge_subtree_nodes, render_subtree_nodes, TreeNode};
+
+    fn entry(path: &str) -> SubtreeEntry {
+        let route = path
+            .split('/')
+            .map(|name| FileNode {
+                name: name.to_string(),
+                file_type: FileType::Regular,
+                lowercase_name: name.to_lowercase(),
+                is_symlink: false,
+                is_directory: false,
+                details: None,
+            })
+            .collect();
+        SubtreeEntry::new(route)
+    }
+
+    fn paths(nodes: &[TreeNode]) -> Vec<&str> {
+        nodes.iter().map(|it| it.indexed_name()).collect()
+    }
+
+    #[test]
+    fn test_render_subtree_nodes() {
+        let entries: Vec<SubtreeEntry> = ["src/tree.rs", "src/app/tree_view.rs", "README.md", "tr"]
+            .into_iter()
+            .map(entry)
+            .collect();
+        let nodes = render_subtree_nodes(&entries, "tr", MatchMode::Substring, 10);
+        assert_eq!(
+            paths(&nodes),
+            vec!["tr", "src/tree.rs", "src/app/tree_view.rs"]
+        );
+        let nodes = render_subtree_nodes(&entries, "tr", MatchMode::Substring, 2);
+        assert_eq!(paths(&nodes), vec!["tr", "src/tree.rs"]);
+        assert_eq!(
+            render_subtree_nodes(&entries, "", MatchMode::Fuzzy, 0).len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_merge_subtree_nodes_matches_full_ranking() {
+        let entries: Vec<SubtreeEntry> = (0..300)
+            .map(|i| entry(&format!("dir{}/file{}.rs", i % 7, i)))
+            .collect();
+        for max_results in [5, 50, 1000] {
+            let mut ranked: Vec<TreeNode> = vec![];
+            for batch in entries.chunks(64) {
+                ranked = merge_subtree_nodes(ranked, batch, "fi 1", MatchMode::Fuzzy, max_results);
+            }
+            let expected = render_subtree_nodes(&entries, "fi 1", MatchMode::Fuzzy, max_results);
+            assert_eq!(paths(&ranked), paths(&expected));
+        }
+    }
+
+    fn wait_for_subtree_search(app: &mut App) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while app.subtree_search_running && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+            app.check_background_events();
+        }
+        assert!(!app.subtree_search_running);
+    }
+
+    #[test]
+    fn test_recursive_search() {
+        let dir = TempDir::new("recursive-search");
+        fs::create_dir_all(dir.join("src/app_logic")).unwrap();
+        fs::write(dir.join("src/app_logic/logic_action_menu.rs"), "").unwrap();
+        fs::write(dir.join("src/main.rs"), "").unwrap();
+        let dir_path = path_string(&dir);
+
+        let mut app = App::new();
+        app.parent_file_nodes = get_path_file_nodes(&dir_path).unwrap();
+        app.populate_current_child_nodes();
+        app.toggle_recursive_search();
+        wait_for_subtree_search(&mut app);
+        assert_eq!(app.subtree_entries.len(), 4);
+
+        for c in "lgam".chars() {
+            app.type_search_text(c);
+        }
+        assert_eq!(
+            paths(&app.child_tree_nodes),
+            vec!["src/app_logic/logic_action_menu.rs"]
+        );
+
+        // a finished command refreshes the list without walking the tree again
+        app.refresh_visible_nodes();
+        assert!(!app.subtree_search_running);
+        assert_eq!(app.subtree_entries.len(), 4);
+
+        app.go_into(); // opens the directory of the found file
+        assert!(!app.recursive_search);
+        assert_eq!(
+            app.get_current_string_path(),
+            format!("{}/src/app_logic", dir_path)
+        );
+        assert_eq!(
+            app.get_selected_tree_node().unwrap().name(),
+            "logic_action_menu.rs"
+        );
+    }
+
+    #[test]
+    fn test_recursive_search_stops_at_entries_limit() {
+        let dir = TempDir::new("recursive-limit");
+        let mut app = App::new();
+        app.parent_file_nodes = get_path_file_nodes(&path_string(&dir)).unwrap();
+        app.populate_current_child_nodes();
+        app.toggle_recursive_search();
+        wait_for_subtree_search(&mut app);
+        app.subtree_search_running = true; // still walking a huge tree
+        let generation = app.subtree_search_generation;
+        let entries: Vec<SubtreeEntry> = (0..MAX_SUBTREE_ENTRIES + 10)
+            .map(|i| entry(&format!("dir{}/file{}", i % 100, i)))
+            .collect();
+        for batch in entries.chunks(1000) {
+            app.on_subtree_entries_found(generation, batch.to_vec());
+        }
+        assert_eq!(app.subtree_entries.len(), MAX_SUBTREE_ENTRIES);
+        assert!(!app.subtree_search_running);
+        assert!(app.subtree_search_cancelled.load(Ordering::Relaxed));
+        let status = app.get_subtree_search_status().unwrap();
+        assert!(status.ends_with(&format!("{}+ entries, limit reached", MAX_SUBTREE_ENTRIES)));
+    }
+
+    #[test]
+    fn test_recursive_search_loads_details_for_long_listing() {
+        let dir = TempDir::new("recursive-details");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+
+        let mut app = App::new();
+        app.parent_file_nodes = get_path_file_nodes(&path_string(&dir)).unwrap();
+        app.populate_current_child_nodes();
+        app.toggle_recursive_search();
+        wait_for_subtree_search(&mut app);
+        assert!(app
+            .subtree_entries
+            .iter()
+            .all(|it| it.file_node().details.is_none()));
+
+        app.toggle_long_listing();
+        wait_for_subtree_search(&mut app);
+        assert_eq!(app.subtree_entries.len(), 2);
+        assert!(app
+            .subtree_entries
+            .iter()
+            .all(|it| it.file_node().details.is_some()));
+    }
+}
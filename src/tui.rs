@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::{io, panic};
@@ -9,6 +10,7 @@ use crate::{
     app::App,
     event::{Event, EventHandler},
     keyboard::update_on_key,
+    mouse::update_on_mouse,
     ui,
 };
 
@@ -19,10 +21,10 @@ pub struct Tui {
 }
 
 impl Tui {
-    pub fn new() -> Self {
+    pub fn new(tick_rate: u64) -> Self {
         let backend = CrosstermBackend::new(std::io::stderr());
         let terminal: CrosstermTerminal = Terminal::new(backend).unwrap();
-        let event_handler: EventHandler = EventHandler::new(500).listen();
+        let event_handler: EventHandler = EventHandler::new(tick_rate).listen();
         Self {
             terminal,
             event_handler,
@@ -31,7 +33,7 @@ impl Tui {
 
     pub fn enter(&mut self) -> Result<()> {
         terminal::enable_raw_mode()?; // https://docs.rs/crossterm/latest/crossterm/terminal/index.html#raw-mode
-        crossterm::execute!(io::stderr(), EnterAlternateScreen,)?;
+        crossterm::execute!(io::stderr(), EnterAlternateScreen, EnableMouseCapture)?;
         self.event_handler.resume();
 
         let panic_hook = panic::take_hook();
@@ -55,21 +57,23 @@ impl Tui {
         match self.event_handler.next()? {
             Event::Tick => app.tick(),
             Event::Key(key_event) => update_on_key(app, key_event, self),
+            Event::Mouse(mouse_event) => update_on_mouse(app, mouse_event, self),
             Event::Resize => {}
+            Event::Background => app.check_background_events(),
         };
         Ok(())
     }
 
     fn fatal_exit() -> Result<()> {
         terminal::disable_raw_mode()?;
-        crossterm::execute!(io::stderr(), LeaveAlternateScreen,)?;
+        crossterm::execute!(io::stderr(), DisableMouseCapture, LeaveAlternateScreen)?;
         Ok(())
     }
 
     pub fn exit(&mut self) -> Result<()> {
         self.event_handler.suspend();
         terminal::disable_raw_mode()?;
-        crossterm::execute!(io::stderr(), LeaveAlternateScreen,)?;
+        crossterm::execute!(io::stderr(), DisableMouseCapture, LeaveAlternateScreen)?;
         Ok(())
     }
 }
@@ -3,17 +3,45 @@ use std::str::Chars;
 use crate::action_menu::MenuAction;
 use crate::appdata::WindowFocus;
 use crate::numbers::{ClampNumExt, MyIntExt};
+use crate::preview::{binary_summary, PreviewContent};
 use ratatui::{prelude::*, widgets::*};
 use ratatui::{
     prelude::{Alignment, Frame},
     style::{Color, Style},
     widgets::{Block, BorderType, Borders, Paragraph},
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::app::App;
-use crate::tree::TreeNode;
+use crate::breadcrumbs::{BreadcrumbArea, BreadcrumbPart};
+use crate::keymap::focus_name;
+use crate::mouse::ListArea;
+use crate::tree::{highlight_matches, TreeNode, TreeNodeType};
+use crate::viewer::{mark_matches, FileViewer, ViewerPrompt};
+
+const MIN_BREADCRUMBS_WIDTH: usize = 12; // path bar width kept when the statuses are long
 
 pub fn render(app: &mut App, frame: &mut Frame) {
+    let base_focus = match app.window_focus {
+        WindowFocus::Help => app.help_focus,
+        focus => focus,
+    };
+    match base_focus {
+        WindowFocus::Viewer => render_viewer(app, frame, frame.area()),
+        _ => render_browser(app, frame),
+    }
+    if app.window_focus == WindowFocus::Help {
+        render_help_popup(app, frame);
+    }
+    if app.info_message.is_some() {
+        render_info_popup(app, frame);
+    }
+    if app.error_message.is_some() {
+        render_error_popup(app, frame);
+    }
+}
+
+fn render_browser(app: &mut App, frame: &mut Frame) {
     let area = frame.area();
     let middle_h = area.height - 3;
 
@@ -22,41 +50,84 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         .constraints(vec![Constraint::Min(middle_h), Constraint::Max(3)])
         .split(area);
 
-    render_dir_tree(app, frame, layout[0]);
+    if app.preview_enabled && layout[0].width >= 40 {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(layout[0]);
+        render_dir_tree(app, frame, columns[0]);
+        render_preview_panel(app, frame, columns[1]);
+    } else {
+        render_dir_tree(app, frame, layout[0]);
+    }
     render_filter_panel(app, frame, layout[1]);
     if app.window_focus == WindowFocus::ActionMenu {
         render_action_popup(app, frame);
     } else if app.window_focus == WindowFocus::ActionMenuStep2 {
         render_action_popup_step2(app, frame);
-    }
-    if app.info_message.is_some() {
-        render_info_popup(app, frame);
-    }
-    if app.error_message.is_some() {
-        render_error_popup(app, frame);
+    } else if app.window_focus == WindowFocus::Bookmarks {
+        render_bookmarks_popup(app, frame);
+    } else if app.window_focus == WindowFocus::History {
+        render_history_popup(app, frame);
+    } else if app.window_focus == WindowFocus::Trash {
+        render_trash_popup(app, frame);
     }
 }
 
 fn render_dir_tree(app: &mut App, frame: &mut Frame, area: Rect) {
+    if app.is_path_input_mode() {
+        app.tree_list_area = ListArea::default();
+        app.breadcrumb_areas.clear();
+        return render_path_candidates(app, frame, area);
+    }
     let list_items: Vec<ListItem> = app
         .child_tree_nodes
         .iter()
-        .map(|it: &TreeNode| it.render_list_item())
+        .map(|it: &TreeNode| it.render_list_item(&app.theme, app.long_listing))
         .collect();
 
     let max_title_w = area.width as usize - 2;
-    let mut title_text = app.get_current_string_path();
-    if title_text.len() > max_title_w {
-        let split_pos = title_text
-            .char_indices()
-            .nth_back(max_title_w - 1 - 1)
-            .unwrap()
-            .0;
-        title_text = format!("…{}", &title_text[split_pos..]);
+    let mut statuses: Vec<String> = vec![app.sort_order.describe()];
+    if let Some(search_status) = app.get_subtree_search_status() {
+        statuses.push(search_status);
+    }
+    if let Some(hidden_status) = app.get_hidden_entries_status() {
+        statuses.push(hidden_status);
+    }
+    if let Some(transfer_status) = app.get_transfer_status() {
+        statuses.push(transfer_status);
     }
+    let status_text = format!(" ({})", statuses.join(", "));
+    let breadcrumbs_w = max_title_w
+        .saturating_sub(status_text.width())
+        .max(MIN_BREADCRUMBS_WIDTH);
+    let breadcrumbs: Vec<BreadcrumbPart> = app.get_breadcrumbs(breadcrumbs_w);
+    let selected_depth = app.get_selected_breadcrumb();
+
+    app.breadcrumb_areas.clear();
+    let mut title_spans: Vec<Span> = vec![];
+    let mut x = area.x + 1;
+    for part in &breadcrumbs {
+        let width = u16::try_from(part.text.width()).unwrap_or(u16::MAX);
+        let style = match part.depth {
+            Some(depth) if Some(depth) == selected_depth => {
+                Style::new().add_modifier(Modifier::REVERSED)
+            }
+            _ => Style::new(),
+        };
+        if let Some(depth) = part.depth {
+            app.breadcrumb_areas.push(BreadcrumbArea {
+                area: Rect::new(x, area.y, width, 1).intersection(area),
+                depth,
+            });
+        }
+        title_spans.push(Span::styled(part.text.as_str(), style));
+        x = x.saturating_add(width);
+    }
+    title_spans.push(Span::raw(status_text));
 
     let title_block = Block::default()
-        .title(title_text)
+        .title(Line::from(title_spans))
         .title_style(Style::new().bold())
         .title_alignment(Alignment::Left)
         .borders(Borders::ALL)
@@ -64,17 +135,228 @@ fn render_dir_tree(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let widget = List::new(list_items)
         .block(title_block)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(app.theme.text))
         .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
         .highlight_symbol(">> ");
 
     frame.render_stateful_widget(widget, area, &mut app.file_tree_state);
+    app.tree_list_area = ListArea {
+        area,
+        offset: app.file_tree_state.offset(),
+    };
+}
+
+fn render_path_candidates(app: &App, frame: &mut Frame, area: Rect) {
+    let prefix_len = app.path_candidates_prefix.chars().count();
+    let mut list_items: Vec<ListItem> = app
+        .path_candidates
+        .iter()
+        .map(|it| {
+            let tree_node = TreeNode {
+                relevance: 0,
+                kind: TreeNodeType::FileNode(it.clone()),
+                marked: false,
+                matched_indices: (0..prefix_len).collect(),
+            };
+            tree_node.render_list_item(&app.theme, false)
+        })
+        .collect();
+    if app.path_candidates.is_empty() {
+        list_items.push(ListItem::new("No matching entries"));
+    }
+    let title_text = format!(
+        "{} ({} matching, Tab to complete, Enter to go)",
+        app.path_candidates_dir,
+        app.path_candidates.len()
+    );
+    let title_block = Block::default()
+        .title(title_text)
+        .title_style(Style::new().bold())
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    let widget = List::new(list_items)
+        .block(title_block)
+        .style(Style::default().fg(app.theme.text));
+    frame.render_widget(widget, area);
+}
+
+fn render_preview_panel(app: &App, frame: &mut Frame, area: Rect) {
+    let title_text: String = match &app.preview {
+        Some(preview) => preview.path.rsplit('/').next().unwrap_or("").to_string(),
+        None => "Preview".to_string(),
+    };
+    let dim_style = Style::default().fg(app.theme.subtree_prefix);
+    let lines: Vec<Line> = match app.preview.as_ref().map(|it| &it.content) {
+        None => vec![Line::styled("Loading…", dim_style)],
+        Some(PreviewContent::Text {
+            lines,
+            truncated,
+            highlighted,
+        }) => {
+            let mut text_lines: Vec<Line> = match highlighted {
+                Some(highlighted) => highlighted.clone(),
+                None => lines.iter().map(|it| Line::raw(it.as_str())).collect(),
+            };
+            if *truncated {
+                text_lines.push(Line::styled("…", dim_style));
+            }
+            text_lines
+        }
+        Some(PreviewContent::Directory { entries, total }) => {
+            let mut dir_lines: Vec<Line> = entries
+                .iter()
+                .map(|it| match it.is_directory {
+                    true => Line::from(vec![
+                        Span::styled(
+                            it.name.as_str(),
+                            Style::default().fg(app.theme.directory).bold(),
+                        ),
+                        Span::raw("/"),
+                    ]),
+                    false => Line::raw(it.name.as_str()),
+                })
+                .collect();
+            if *total > entries.len() {
+                dir_lines.push(Line::styled(
+                    format!("… {} more", total - entries.len()),
+                    dim_style,
+                ));
+            }
+            if *total == 0 {
+                dir_lines.push(Line::styled("(empty directory)", dim_style));
+            }
+            dir_lines
+        }
+        Some(PreviewContent::Binary { size, hex_lines }) => {
+            let mut binary_lines: Vec<Line> = vec![
+                Line::styled(binary_summary(*size), dim_style),
+                Line::raw(""),
+            ];
+            binary_lines.extend(hex_lines.iter().map(|it| Line::raw(it.as_str())));
+            binary_lines
+        }
+        Some(PreviewContent::Error(message)) => vec![Line::styled(
+            message.as_str(),
+            Style::default().fg(app.theme.error_background),
+        )],
+    };
+
+    let title_block = Block::default()
+        .title(title_text)
+        .title_style(Style::new().bold())
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    let widget = Paragraph::new(lines)
+        .block(title_block)
+        .style(Style::default().fg(app.theme.text));
+    frame.render_widget(widget, area);
+}
+
+fn render_viewer(app: &mut App, frame: &mut Frame, area: Rect) {
+    let theme = app.theme;
+    let viewer: &mut FileViewer = match app.viewer.as_mut() {
+        Some(viewer) => viewer,
+        None => return,
+    };
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(vec![Constraint::Min(1), Constraint::Length(1)])
+        .split(area);
+    let mut title_text = viewer.path.clone();
+    if viewer.binary {
+        title_text = format!("{} (binary)", title_text);
+    }
+    let block = Block::default()
+        .title(title_text)
+        .title_style(Style::new().bold())
+        .title_alignment(Alignment::Left)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded);
+    let inner = block.inner(layout[0]);
+    frame.render_widget(block, layout[0]);
+    viewer.page_height = inner.height as usize;
+
+    let first = viewer.scroll_y.min(viewer.lines.len());
+    let last = (first + inner.height as usize).min(viewer.lines.len());
+    let gutter_width: u16 = match viewer.binary {
+        true => 0, // hex dump rows start with their offsets
+        false => last.max(1).to_string().len() as u16 + 1,
+    };
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Length(gutter_width), Constraint::Min(1)])
+        .split(inner);
+
+    let dim_style = Style::default().fg(theme.subtree_prefix);
+    if gutter_width > 0 {
+        let number_lines: Vec<Line> = (first..last)
+            .map(|i| {
+                let style = match viewer.current_match == Some(i) {
+                    true => Style::default().fg(theme.match_highlight).bold(),
+                    false => dim_style,
+                };
+                Line::styled(
+                    format!("{:>w$} ", i + 1, w = gutter_width as usize - 1),
+                    style,
+                )
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(number_lines), columns[0]);
+    }
+    let match_style = Style::default().reversed();
+    let content_lines: Vec<Line> = (first..last)
+        .map(|i| mark_matches(viewer.display_line(i), &viewer.search_query, match_style))
+        .collect();
+    let content = Paragraph::new(content_lines)
+        .scroll((0, viewer.scroll_x as u16))
+        .style(Style::default().fg(theme.text));
+    frame.render_widget(content, columns[1]);
+
+    let status_line: Line = match (viewer.prompt, &viewer.message) {
+        (Some(ViewerPrompt::Search), _) => Line::raw(format!("/{}\u{2588}", viewer.prompt_text)),
+        (Some(ViewerPrompt::GotoLine), _) => {
+            Line::raw(format!("Go to line: {}\u{2588}", viewer.prompt_text))
+        }
+        (None, Some(message)) => {
+            Line::styled(message.clone(), Style::default().fg(theme.error_background))
+        }
+        (None, None) => {
+            let mut status = format!(
+                "lines {}-{} of {}{}",
+                first + 1,
+                last,
+                viewer.lines.len(),
+                if viewer.eof { "" } else { "+" },
+            );
+            if viewer.truncated {
+                status.push_str(" (truncated)");
+            }
+            status.push_str("   / search   n/N next/previous   : go to line   q quit");
+            Line::styled(status, dim_style)
+        }
+    };
+    frame.render_widget(
+        Paragraph::new(status_line).style(Style::default().fg(theme.search_text)),
+        layout[1],
+    );
 }
 
 fn render_filter_panel(app: &App, frame: &mut Frame, area: Rect) {
     let p_text = format!("{}\u{2588}", app.filter_text);
+    let mut title_text = match app.is_path_input_mode() {
+        true => "Go to path".to_string(),
+        false => format!("Search ({})", app.match_mode.name()),
+    };
+    if app.multi_select {
+        title_text = format!("{} ({} marked)", title_text, app.marked_routes.len());
+    }
+    if let Some(indicator) = app.get_vim_mode_indicator() {
+        title_text = format!("{} {}", title_text, indicator);
+    }
     let title = Block::default()
-        .title("Search")
+        .title(title_text)
         .title_style(Style::new().bold())
         .title_alignment(Alignment::Left)
         .borders(Borders::ALL)
@@ -82,17 +364,17 @@ fn render_filter_panel(app: &App, frame: &mut Frame, area: Rect) {
 
     let widget = Paragraph::new(p_text)
         .block(title)
-        .style(Style::default().fg(Color::LightYellow))
+        .style(Style::default().fg(app.theme.search_text))
         .alignment(Alignment::Left);
 
     frame.render_widget(widget, area);
 }
 
-fn render_action_popup(app: &App, frame: &mut Frame) {
+fn render_action_popup(app: &mut App, frame: &mut Frame) {
     let list_items: Vec<ListItem> = app
-        .known_menu_actions
+        .menu_actions
         .iter()
-        .map(|it: &MenuAction| ListItem::new(it.name))
+        .map(|it: &MenuAction| ListItem::new(it.name.as_str()))
         .collect();
     let mut list_state = ListState::default().with_selected(Some(app.action_menu_cursor_y));
     let widget = List::new(list_items)
@@ -100,17 +382,21 @@ fn render_action_popup(app: &App, frame: &mut Frame) {
             Block::default()
                 .title("Run action")
                 .borders(Borders::ALL)
-                .bg(Color::DarkGray),
+                .bg(app.theme.popup_background),
+        )
+        .style(
+            Style::default()
+                .fg(app.theme.text)
+                .bg(app.theme.popup_background),
         )
-        .style(Style::default().fg(Color::White).bg(Color::DarkGray))
         .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
         .highlight_symbol(">> ");
 
-    let height = app.known_menu_actions.len() as u16 + 2;
+    let height = app.menu_actions.len() as u16 + 2;
     let width: u16 = app
-        .known_menu_actions
+        .menu_actions
         .iter()
-        .map(|it: &MenuAction| it.name.len() as u16)
+        .map(|it: &MenuAction| it.name.chars().count() as u16)
         .max()
         .unwrap_or(0)
         + 8;
@@ -118,6 +404,234 @@ fn render_action_popup(app: &App, frame: &mut Frame) {
     let buffer = frame.buffer_mut();
     Clear.render(area, buffer);
     frame.render_stateful_widget(widget, area, &mut list_state);
+    app.action_menu_list_area = ListArea {
+        area,
+        offset: list_state.offset(),
+    };
+}
+
+fn render_bookmarks_popup(app: &App, frame: &mut Frame) {
+    let name_width: usize = app
+        .bookmark_matches
+        .iter()
+        .filter_map(|it| app.bookmarks.get(it.index))
+        .map(|it| it.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let name_style = Style::default().fg(app.theme.directory).bold();
+    let path_style = Style::default().fg(app.theme.subtree_prefix);
+    let mut list_items: Vec<ListItem> = app
+        .bookmark_matches
+        .iter()
+        .filter_map(|it| {
+            let bookmark = app.bookmarks.get(it.index)?;
+            let mut spans = highlight_matches(
+                bookmark.name.clone(),
+                name_style,
+                &it.name_indices,
+                &app.theme,
+            );
+            let padding = name_width - bookmark.name.chars().count() + 2;
+            spans.push(Span::raw(" ".repeat(padding)));
+            spans.push(Span::styled(bookmark.path.clone(), path_style));
+            Some(ListItem::new(Line::from(spans)))
+        })
+        .collect();
+    if app.bookmarks.is_empty() {
+        list_items.push(ListItem::new(
+            "No bookmarks yet, press Ctrl+B to bookmark a directory",
+        ));
+    }
+    let mut list_state = ListState::default().with_selected(Some(app.bookmarks_cursor));
+    let widget = List::new(list_items)
+        .block(
+            Block::default()
+                .title(format!("Bookmarks: {}\u{2588}", app.bookmarks_filter))
+                .title_style(Style::new().bold())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .bg(app.theme.popup_background),
+        )
+        .style(
+            Style::default()
+                .fg(app.theme.text)
+                .bg(app.theme.popup_background),
+        )
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol(">> ");
+
+    let width = frame.area().width * 3 / 4;
+    let height = list_popup_height(app.bookmark_matches.len(), frame.area());
+    let area = centered_rect(width, height, frame.area());
+    Clear.render(area, frame.buffer_mut());
+    frame.render_stateful_widget(widget, area, &mut list_state);
+}
+
+fn render_trash_popup(app: &App, frame: &mut Frame) {
+    let date_style = Style::default().fg(app.theme.details);
+    let path_style = Style::default().fg(app.theme.text);
+    let mut list_items: Vec<ListItem> = app
+        .trash_entries
+        .iter()
+        .map(|entry| {
+            let date: String = match entry.deletion_date {
+                Some(date) => date.format("%Y-%m-%d %H:%M").to_string(),
+                None => "-".to_string(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:<16}  ", date), date_style),
+                Span::styled(entry.original_path.clone(), path_style),
+            ]))
+        })
+        .collect();
+    if app.trash_entries.is_empty() {
+        list_items.push(ListItem::new("Trash is empty"));
+    }
+    let mut list_state = ListState::default().with_selected(Some(app.trash_cursor));
+    let widget = List::new(list_items)
+        .block(
+            Block::default()
+                .title("Trash (Enter to restore)")
+                .title_style(Style::new().bold())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .bg(app.theme.popup_background),
+        )
+        .style(
+            Style::default()
+                .fg(app.theme.text)
+                .bg(app.theme.popup_background),
+        )
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol(">> ");
+
+    let width = frame.area().width * 3 / 4;
+    let height = list_popup_height(app.trash_entries.len(), frame.area());
+    let area = centered_rect(width, height, frame.area());
+    Clear.render(area, frame.buffer_mut());
+    frame.render_stateful_widget(widget, area, &mut list_state);
+}
+
+fn render_history_popup(app: &App, frame: &mut Frame) {
+    let dir_style = Style::default().fg(app.theme.directory).bold();
+    let file_style = Style::default().fg(app.theme.text);
+    let mut list_items: Vec<ListItem> = app
+        .history_matches
+        .iter()
+        .filter_map(|it| {
+            let entry = app.history_entries.get(it.index)?;
+            let style = match entry.is_directory {
+                true => dir_style,
+                false => file_style,
+            };
+            let spans = highlight_matches(entry.path.clone(), style, &it.indices, &app.theme);
+            Some(ListItem::new(Line::from(spans)))
+        })
+        .collect();
+    if app.history_entries.is_empty() {
+        list_items.push(ListItem::new("No history yet"));
+    }
+    let mut list_state = ListState::default().with_selected(Some(app.history_cursor));
+    let widget = List::new(list_items)
+        .block(
+            Block::default()
+                .title(format!("Recent: {}\u{2588}", app.history_filter))
+                .title_style(Style::new().bold())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .bg(app.theme.popup_background),
+        )
+        .style(
+            Style::default()
+                .fg(app.theme.text)
+                .bg(app.theme.popup_background),
+        )
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol(">> ");
+
+    let width = frame.area().width * 3 / 4;
+    let height = list_popup_height(app.history_matches.len(), frame.area());
+    let area = centered_rect(width, height, frame.area());
+    Clear.render(area, frame.buffer_mut());
+    frame.render_stateful_widget(widget, area, &mut list_state);
+}
+
+fn render_help_popup(app: &App, frame: &mut Frame) {
+    let width = frame.area().width * 3 / 4;
+    let max_keys_width = width as usize / 3;
+    let key_names: Vec<String> = app
+        .help_entries
+        .iter()
+        .map(|it| {
+            let names = it.keys.join(", ");
+            match names.chars().count() > max_keys_width {
+                true => {
+                    let head: String = names
+                        .chars()
+                        .take(max_keys_width.saturating_sub(1))
+                        .collect();
+                    format!("{}…", head)
+                }
+                false => names,
+            }
+        })
+        .collect();
+    let keys_width: usize = key_names
+        .iter()
+        .map(|it| it.chars().count())
+        .max()
+        .unwrap_or(0);
+    let keys_style = Style::default().fg(app.theme.directory).bold();
+    let description_style = Style::default().fg(app.theme.text);
+    let command_style = Style::default().fg(app.theme.details);
+    let mut list_items: Vec<ListItem> = app
+        .help_matches
+        .iter()
+        .filter_map(|it| {
+            let entry = app.help_entries.get(it.index)?;
+            let mut spans = vec![Span::styled(
+                format!("{:<width$}  ", key_names[it.index], width = keys_width),
+                keys_style,
+            )];
+            spans.extend(highlight_matches(
+                entry.description.to_string(),
+                description_style,
+                &it.description_indices,
+                &app.theme,
+            ));
+            spans.push(Span::styled(format!("  {}", entry.command), command_style));
+            Some(ListItem::new(Line::from(spans)))
+        })
+        .collect();
+    if app.help_matches.is_empty() {
+        list_items.push(ListItem::new("No matching key bindings"));
+    }
+    let mut list_state = ListState::default().with_selected(Some(app.help_cursor));
+    let widget = List::new(list_items)
+        .block(
+            Block::default()
+                .title(format!(
+                    "Keys ({}): {}\u{2588}",
+                    focus_name(app.help_focus),
+                    app.help_filter
+                ))
+                .title_style(Style::new().bold())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .bg(app.theme.popup_background),
+        )
+        .style(
+            Style::default()
+                .fg(app.theme.text)
+                .bg(app.theme.popup_background),
+        )
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol(">> ");
+
+    let height = list_popup_height(app.help_matches.len(), frame.area());
+    let area = centered_rect(width, height, frame.area());
+    Clear.render(area, frame.buffer_mut());
+    frame.render_stateful_widget(widget, area, &mut list_state);
 }
 
 fn render_action_popup_step2(app: &App, frame: &mut Frame) {
@@ -129,7 +643,7 @@ fn render_action_popup_step2(app: &App, frame: &mut Frame) {
         .title_alignment(Alignment::Left)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .bg(Color::DarkGray);
+        .bg(app.theme.popup_background);
     let widget = Paragraph::new(p_line)
         .wrap(Wrap { trim: false })
         .block(title)
@@ -154,15 +668,20 @@ fn render_error_popup(app: &App, frame: &mut Frame) {
         .title_style(Style::new().bold())
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .bg(Color::Red)
+        .bg(app.theme.error_background)
         .padding(Padding::bottom(1))
         .border_type(BorderType::Rounded);
     let error_window = Paragraph::new(error_message)
         .wrap(Wrap { trim: false })
         .block(title_block)
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(app.theme.text));
     let ok_label = Paragraph::new("OK")
-        .style(Style::default().bold().fg(Color::LightRed).bg(Color::White))
+        .style(
+            Style::default()
+                .bold()
+                .fg(app.theme.error_background)
+                .bg(app.theme.text),
+        )
         .alignment(Alignment::Center);
 
     let width: u16 = (frame.area().width as f32 * 0.75f32) as u16;
@@ -207,19 +726,19 @@ fn render_info_popup(app: &App, frame: &mut Frame) {
         .title_style(Style::new().bold())
         .title_alignment(Alignment::Center)
         .borders(Borders::ALL)
-        .bg(Color::Blue)
+        .bg(app.theme.info_background)
         .padding(Padding::bottom(1))
         .border_type(BorderType::Rounded);
     let popup_window = Paragraph::new(Text::raw(display_message))
         .wrap(Wrap { trim: false })
         .block(title_block)
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(app.theme.text));
     let ok_label = Paragraph::new("OK")
         .style(
             Style::default()
                 .bold()
-                .fg(Color::LightBlue)
-                .bg(Color::White),
+                .fg(app.theme.info_background)
+                .bg(app.theme.text),
         )
         .alignment(Alignment::Center);
 
@@ -246,6 +765,12 @@ fn centered_rect(w: u16, h: u16, r: Rect) -> Rect {
     }
 }
 
+/// Height of a bordered popup listing `rows` lines, leaving a margin on tiny terminals.
+fn list_popup_height(rows: usize, r: Rect) -> u16 {
+    let rows = u16::try_from(rows.max(1)).unwrap_or(u16::MAX);
+    rows.saturating_add(2).min(r.height.saturating_sub(2))
+}
+
 fn render_action_popup_step2_line(app: &App) -> Line {
     let cx = app.action_menu_cursor_x;
     let chars: Chars<'_> = app.action_menu_buffer.chars();
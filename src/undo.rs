@@ -0,0 +1,123 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::logs::log;
+use crate::transfer::{move_path, path_exists, TransferItem, TransferMode};
+use crate::trash::{restore_trash_entry, trash_path, TrashEntry};
+
+/// Number of changes kept in the undo journal.
+pub const UNDO_JOURNAL_LIMIT: usize = 100;
+
+/// Filesystem change recorded with everything needed to revert it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UndoRecord {
+    Rename {
+        from: String,
+        to: String,
+    },
+    Create {
+        path: String, // topmost path that didn't exist before
+    },
+    Trash {
+        entry: TrashEntry,
+    },
+    Transfer {
+        mode: TransferMode,
+        items: Vec<TransferItem>,
+        replaced: Vec<TrashEntry>, // overwritten targets, moved to the trash
+    },
+}
+
+/// Returns the outermost ancestor of the path (or the path itself) that doesn't exist yet,
+/// which is what creating the path with all its parents would add.
+pub fn first_missing_ancestor(abs_path: &str) -> Option<String> {
+    Path::new(abs_path)
+        .ancestors()
+        .take_while(|ancestor| !path_exists(&ancestor.to_string_lossy()))
+        .last()
+        .map(|ancestor| ancestor.to_string_lossy().to_string())
+}
+
+/// Reverts the change, returning a message describing what was done.
+/// Created paths and pasted copies are moved to the trash rather than deleted,
+/// entries replaced by a paste are restored from the trash.
+pub fn revert_change(record: &UndoRecord, trash_dir: &Path) -> Result<String> {
+    match record {
+        UndoRecord::Rename { from, to } => {
+            if path_exists(from) {
+                return Err(anyhow!("'{}' already exists", from));
+            }
+            log(format!("Undoing rename: {} back to {}", to, from).as_str());
+            fs::rename(to, from)
+                .with_context(|| format!("failed to rename '{}' back to '{}'", to, from))?;
+            Ok(format!(
+                "Renamed {} back to {}",
+                file_name(to),
+                file_name(from)
+            ))
+        }
+        UndoRecord::Create { path } => {
+            if !path_exists(path) {
+                return Err(anyhow!("'{}' no longer exists", path));
+            }
+            trash_path(trash_dir, path)?;
+            Ok(format!("Moved created {} to trash", file_name(path)))
+        }
+        UndoRecord::Trash { entry } => {
+            restore_trash_entry(entry)?;
+            Ok(format!("Restored {}", entry.original_path))
+        }
+        UndoRecord::Transfer {
+            mode,
+            items,
+            replaced,
+        } => {
+            let mut reverted = 0;
+            let mut errors: Vec<String> = Vec::new();
+            for item in items.iter().filter(|item| path_exists(&item.target)) {
+                let result = match mode {
+                    TransferMode::Copy => trash_path(trash_dir, &item.target).map(|_| ()),
+                    TransferMode::Move if path_exists(&item.source) => {
+                        Err(anyhow!("'{}' already exists", item.source))
+                    }
+                    TransferMode::Move => {
+                        log(
+                            format!("Undoing move: {} back to {}", item.target, item.source)
+                                .as_str(),
+                        );
+                        move_path(Path::new(&item.target), Path::new(&item.source)).with_context(
+                            || {
+                                format!(
+                                    "failed to move '{}' back to '{}'",
+                                    item.target, item.source
+                                )
+                            },
+                        )
+                    }
+                };
+                match result {
+                    Ok(_) => reverted += 1,
+                    Err(e) => errors.push(format!("{:#}", e)),
+                }
+            }
+            // the replaced entries get their places back once the pasted ones are gone
+            for entry in replaced {
+                if let Err(e) = restore_trash_entry(entry) {
+                    errors.push(format!("{:#}", e));
+                }
+            }
+            if !errors.is_empty() {
+                return Err(anyhow!(errors.join("\n")));
+            }
+            Ok(match mode {
+                TransferMode::Copy => format!("Moved {} pasted copies to trash", reverted),
+                TransferMode::Move => format!("Moved {} entries back", reverted),
+            })
+        }
+    }
+}
+
+fn file_name(abs_path: &str) -> &str {
+    abs_path.rsplit('/').next().unwrap_or(abs_path)
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::testing::{path_string, TempDir};
+    use crate::transfer::{TransferItem, TransferMode};
+    use crate::trash::{list_trash, trash_path};
+    use crate::undo::{first_missing_ancestor, revert_change, UndoRecord};
+
+    #[test]
+    fn test_first_missing_ancestor() {
+        let dir = TempDir::new("undo-ancestor");
+        assert_eq!(
+            first_missing_ancestor(&path_string(&dir.join("a/b/c.txt"))),
+            Some(path_string(&dir.join("a")))
+        );
+        fs::create_dir_all(dir.join("a")).unwrap();
+        assert_eq!(
+            first_missing_ancestor(&path_string(&dir.join("a/b/c.txt"))),
+            Some(path_string(&dir.join("a/b")))
+        );
+        assert_eq!(first_missing_ancestor(&path_string(&dir.join("a"))), None);
+    }
+
+    #[test]
+    fn test_revert_rename_and_create() {
+        let dir = TempDir::new("undo-rename");
+        let trash_dir = dir.join("Trash");
+        fs::write(dir.join("new.txt"), "content").unwrap();
+        let rename = UndoRecord::Rename {
+            from: path_string(&dir.join("old.txt")),
+            to: path_string(&dir.join("new.txt")),
+        };
+        let message = revert_change(&rename, &trash_dir).unwrap();
+        assert_eq!(message, "Renamed new.txt back to old.txt");
+        assert_eq!(fs::read_to_string(dir.join("old.txt")).unwrap(), "content");
+        assert!(!dir.join("new.txt").exists());
+        // the old name is taken now
+        fs::write(dir.join("new.txt"), "").unwrap();
+        assert!(revert_change(&rename, &trash_dir).is_err());
+
+        fs::create_dir_all(dir.join("created/sub")).unwrap();
+        let create = UndoRecord::Create {
+            path: path_string(&dir.join("created")),
+        };
+        revert_change(&create, &trash_dir).unwrap();
+        assert!(!dir.join("created").exists());
+        assert!(trash_dir.join("files/created/sub").exists());
+        assert!(revert_change(&create, &trash_dir).is_err());
+    }
+
+    #[test]
+    fn test_revert_trash() {
+        let dir = TempDir::new("undo-trash");
+        let trash_dir = dir.join("Trash");
+        fs::write(dir.join("a.txt"), "a").unwrap();
+        let entry = trash_path(&trash_dir, &path_string(&dir.join("a.txt"))).unwrap();
+        revert_change(&UndoRecord::Trash { entry }, &trash_dir).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("a.txt")).unwrap(), "a");
+        assert!(list_trash(&trash_dir).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_revert_transfer() {
+        let dir = TempDir::new("undo-transfer");
+        let trash_dir = dir.join("Trash");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::create_dir_all(dir.join("dst")).unwrap();
+        fs::write(dir.join("dst/moved.txt"), "moved").unwrap();
+        fs::write(dir.join("dst/copy.txt"), "copy").unwrap();
+        fs::write(dir.join("src/copy.txt"), "copy").unwrap();
+        let item = |name: &str| TransferItem {
+            source: path_string(&dir.join("src").join(name)),
+            target: path_string(&dir.join("dst").join(name)),
+            overwrite: false,
+        };
+
+        let moved = UndoRecord::Transfer {
+            mode: TransferMode::Move,
+            items: vec![item("moved.txt"), item("missing.txt")],
+            replaced: vec![],
+        };
+        let message = revert_change(&moved, &trash_dir).unwrap();
+        assert_eq!(message, "Moved 1 entries back");
+        assert_eq!(
+            fs::read_to_string(dir.join("src/moved.txt")).unwrap(),
+            "moved"
+        );
+        assert!(!dir.join("dst/moved.txt").exists());
+
+        let copied = UndoRecord::Transfer {
+            mode: TransferMode::Copy,
+            items: vec![item("copy.txt")],
+            replaced: vec![],
+        };
+        revert_change(&copied, &trash_dir).unwrap();
+        assert!(!dir.join("dst/copy.txt").exists());
+        assert!(dir.join("src/copy.txt").exists());
+        assert_eq!(list_trash(&trash_dir).unwrap().len(), 1);
+    }
+}
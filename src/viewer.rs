@@ -0,0 +1,367 @@
+use anyhow::{Context, Result};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use std::fs::{self, File};
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+
+use crate::highlight::{ChunkHighlighter, HighlightSettings};
+use crate::preview::{hex_dump_at, is_binary};
+
+pub const VIEWER_CHUNK_SIZE: u64 = 64 * 1024; // multiple of 16 to keep hex dump rows aligned
+
+/// Reads a file in chunks on demand, splitting it into text lines
+/// or hex dump rows when the beginning of the file looks binary.
+#[derive(Debug)]
+pub struct ViewerReader {
+    path: String,
+    file: File,
+    size: u64,
+    max_bytes: u64,
+    offset: u64,        // bytes read so far
+    remainder: Vec<u8>, // incomplete last line of the previous chunk
+    binary: Option<bool>,
+    highlight_settings: HighlightSettings,
+    highlighter: Option<ChunkHighlighter>,
+    eof: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewerChunk {
+    pub lines: Vec<String>,
+    pub highlighted: Option<Vec<Line<'static>>>,
+    pub binary: bool,
+    pub eof: bool,
+    pub truncated: bool, // reading stopped at the size limit
+}
+
+impl ViewerReader {
+    pub fn open(path: &str, max_bytes: u64, highlight_settings: HighlightSettings) -> Result<Self> {
+        let size = fs::metadata(path)
+            .context("failed to read file metadata")?
+            .len();
+        let file = File::open(path).context("failed to open file")?;
+        Ok(ViewerReader {
+            path: path.to_string(),
+            file,
+            size,
+            max_bytes,
+            offset: 0,
+            remainder: Vec::new(),
+            binary: None,
+            highlight_settings,
+            highlighter: None,
+            eof: false,
+        })
+    }
+
+    pub fn read_chunk(&mut self) -> Result<ViewerChunk> {
+        let start_offset = self.offset;
+        let mut buffer: Vec<u8> = Vec::new();
+        if !self.eof {
+            let limit = VIEWER_CHUNK_SIZE.min(self.max_bytes.saturating_sub(self.offset));
+            (&mut self.file)
+                .take(limit)
+                .read_to_end(&mut buffer)
+                .context("failed to read file")?;
+            self.offset += buffer.len() as u64;
+            self.eof = (buffer.len() as u64) < limit || self.offset >= self.max_bytes;
+        }
+        let truncated = self.eof && self.offset >= self.max_bytes && self.size > self.offset;
+        let binary = *self.binary.get_or_insert_with(|| is_binary(&buffer));
+        if binary {
+            return Ok(ViewerChunk {
+                lines: hex_dump_at(&buffer, start_offset as usize),
+                highlighted: None,
+                binary,
+                eof: self.eof,
+                truncated,
+            });
+        }
+
+        self.remainder.extend_from_slice(&buffer);
+        let complete_len = match self.eof {
+            true => self.remainder.len(),
+            false => match self.remainder.iter().rposition(|b| *b == b'\n') {
+                Some(newline) => newline + 1,
+                None => 0,
+            },
+        };
+        let complete: Vec<u8> = self.remainder.drain(..complete_len).collect();
+        let lines: Vec<String> = String::from_utf8_lossy(&complete)
+            .lines()
+            .map(|line| line.replace('\t', "    "))
+            .collect();
+        if start_offset == 0 {
+            let first_line: &str = lines.first().map(|it| it.as_str()).unwrap_or("");
+            self.highlighter =
+                ChunkHighlighter::new(&self.path, first_line, &self.highlight_settings);
+        }
+        let highlighted = match self.highlighter.as_mut() {
+            Some(highlighter) => highlighter.highlight(&lines),
+            None => None,
+        };
+        if highlighted.is_none() {
+            self.highlighter = None; // the rest stays plain once the grammar fails
+        }
+        Ok(ViewerChunk {
+            lines,
+            highlighted,
+            binary,
+            eof: self.eof,
+            truncated,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewerPrompt {
+    Search,
+    GotoLine,
+}
+
+/// Jump that has to wait for further chunks of the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingJump {
+    Line(usize),
+    Search { from: usize },
+}
+
+/// State of the full-screen file viewer.
+#[derive(Debug)]
+pub struct FileViewer {
+    pub path: String,
+    pub reader: Arc<Mutex<ViewerReader>>,
+    pub lines: Vec<String>,
+    pub highlighted: Vec<Line<'static>>, // colored versions of the first lines, may be shorter
+    pub binary: bool,
+    pub eof: bool,
+    pub truncated: bool,
+    pub loading: bool,
+    pub scroll_y: usize,
+    pub scroll_x: usize,
+    pub page_height: usize, // updated on every render
+    pub prompt: Option<ViewerPrompt>,
+    pub prompt_text: String,
+    pub search_query: String,
+    pub current_match: Option<usize>, // line of the last found match
+    pub pending_jump: Option<PendingJump>,
+    pub message: Option<String>,
+}
+
+impl FileViewer {
+    pub fn new(path: String, reader: ViewerReader) -> Self {
+        FileViewer {
+            path,
+            reader: Arc::new(Mutex::new(reader)),
+            lines: Vec::new(),
+            highlighted: Vec::new(),
+            binary: false,
+            eof: false,
+            truncated: false,
+            loading: false,
+            scroll_y: 0,
+            scroll_x: 0,
+            page_height: 1,
+            prompt: None,
+            prompt_text: String::new(),
+            search_query: String::new(),
+            current_match: None,
+            pending_jump: None,
+            message: None,
+        }
+    }
+
+    pub fn append_chunk(&mut self, chunk: ViewerChunk) {
+        if let Some(highlighted) = chunk.highlighted {
+            if self.highlighted.len() == self.lines.len() {
+                self.highlighted.extend(highlighted);
+            }
+        }
+        self.lines.extend(chunk.lines);
+        self.binary = chunk.binary;
+        self.eof = chunk.eof;
+        self.truncated = chunk.truncated;
+        self.loading = false;
+        self.resolve_pending_jump();
+    }
+
+    /// Whether another chunk should be read to fill the screen or to finish a jump.
+    pub fn needs_more_lines(&self) -> bool {
+        if self.eof || self.loading {
+            return false;
+        }
+        self.pending_jump.is_some() || self.scroll_y + 2 * self.page_height >= self.lines.len()
+    }
+
+    pub fn max_scroll_y(&self) -> usize {
+        self.lines.len().saturating_sub(self.page_height)
+    }
+
+    pub fn scroll_by(&mut self, delta: i64) {
+        let target = (self.scroll_y as i64 + delta).max(0) as usize;
+        self.scroll_y = target.min(self.max_scroll_y());
+    }
+
+    pub fn scroll_x_by(&mut self, delta: i64) {
+        self.scroll_x = (self.scroll_x as i64 + delta).max(0) as usize;
+    }
+
+    /// Scrolls to the line (counted from 0), waiting for more chunks if it isn't loaded yet.
+    pub fn go_to_line(&mut self, line: usize) {
+        self.pending_jump = Some(PendingJump::Line(line));
+        self.resolve_pending_jump();
+    }
+
+    pub fn search(&mut self, query: String) {
+        self.search_query = query;
+        self.current_match = None;
+        self.search_next(true);
+    }
+
+    /// Looks for the next match below (or above) the last one or the top of the screen.
+    /// Searching forward continues in chunks that aren't loaded yet.
+    pub fn search_next(&mut self, forward: bool) {
+        if self.search_query.is_empty() {
+            return;
+        }
+        let current: usize = self.current_match.unwrap_or(self.scroll_y);
+        match forward {
+            true => {
+                let from = match self.current_match {
+                    Some(line) => line + 1,
+                    None => current,
+                };
+                self.pending_jump = Some(PendingJump::Search { from });
+                self.resolve_pending_jump();
+            }
+            false => {
+                let found = (0..current)
+                    .rev()
+                    .find(|i| !find_in_line(&self.lines[*i], &self.search_query).is_empty());
+                match found {
+                    Some(line) => self.show_match(line),
+                    None => {
+                        self.message = Some(format!("Pattern not found: {}", self.search_query))
+                    }
+                }
+            }
+        }
+    }
+
+    fn resolve_pending_jump(&mut self) {
+        match self.pending_jump {
+            Some(PendingJump::Line(line)) if line < self.lines.len() || self.eof => {
+                self.pending_jump = None;
+                self.scroll_y = line.min(self.lines.len().saturating_sub(1));
+                self.scroll_y = self.scroll_y.min(self.max_scroll_y());
+            }
+            Some(PendingJump::Line(_)) => {} // waiting for the line to be read
+            Some(PendingJump::Search { from }) => {
+                let found = (from..self.lines.len())
+                    .find(|i| !find_in_line(&self.lines[*i], &self.search_query).is_empty());
+                match found {
+                    Some(line) => {
+                        self.pending_jump = None;
+                        self.show_match(line);
+                    }
+                    None if self.eof => {
+                        self.pending_jump = None;
+                        self.message = Some(format!("Pattern not found: {}", self.search_query));
+                    }
+                    None => {
+                        // continue with the lines of the next chunk
+                        self.pending_jump = Some(PendingJump::Search {
+                            from: self.lines.len(),
+                        });
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    fn show_match(&mut self, line: usize) {
+        self.current_match = Some(line);
+        self.message = None;
+        if line < self.scroll_y || line >= self.scroll_y + self.page_height {
+            self.scroll_y = line
+                .saturating_sub(self.page_height / 3)
+                .min(self.max_scroll_y());
+        }
+    }
+
+    /// Line with syntax colors when available.
+    pub fn display_line(&self, index: usize) -> Line<'static> {
+        match self.highlighted.get(index) {
+            Some(line) => line.clone(),
+            None => Line::raw(self.lines[index].clone()),
+        }
+    }
+}
+
+/// Finds occurrences of the query in the line, as ranges of char positions.
+/// The search is case-insensitive unless the query contains uppercase letters.
+pub fn find_in_line(line: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return vec![];
+    }
+    let case_sensitive = query.chars().any(|c| c.is_uppercase());
+    let fold = |c: char| match case_sensitive {
+        true => c,
+        false => c.to_lowercase().next().unwrap_or(c),
+    };
+    let line_chars: Vec<char> = line.chars().map(fold).collect();
+    let query_chars: Vec<char> = query.chars().map(fold).collect();
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    let mut i = 0;
+    while i + query_chars.len() <= line_chars.len() {
+        if line_chars[i..i + query_chars.len()] == query_chars[..] {
+            ranges.push((i, i + query_chars.len()));
+            i += query_chars.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+/// Restyles the parts of the line matching the query, keeping the styles of the rest.
+pub fn mark_matches(line: Line<'static>, query: &str, match_style: Style) -> Line<'static> {
+    let ranges = find_in_line(&line.to_string(), query);
+    if ranges.is_empty() {
+        return line;
+    }
+    let is_matched = |pos: usize| {
+        ranges
+            .iter()
+            .any(|(start, end)| pos >= *start && pos < *end)
+    };
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut pos = 0;
+    for span in line.spans {
+        let mut chunk = String::new();
+        let mut chunk_matched = false;
+        for c in span.content.chars() {
+            let matched = is_matched(pos);
+            if matched != chunk_matched && !chunk.is_empty() {
+                let style = match chunk_matched {
+                    true => span.style.patch(match_style),
+                    false => span.style,
+                };
+                spans.push(Span::styled(std::mem::take(&mut chunk), style));
+            }
+            chunk_matched = matched;
+            chunk.push(c);
+            pos += 1;
+        }
+        if !chunk.is_empty() {
+            let style = match chunk_matched {
+                true => span.style.patch(match_style),
+                false => span.style,
+            };
+            spans.push(Span::styled(chunk, style));
+        }
+    }
+    Line::from(spans)
+}
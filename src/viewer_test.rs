@@ -0,0 +1,202 @@
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::style::{Color, Style, Stylize};
+    use ratatui::text::{Line, Span};
+    use ratatui::Terminal;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use crate::app::App;
+    use crate::appdata::WindowFocus;
+
+    use crate::highlight::HighlightSettings;
+    use crate::testing::TempDir;
+    use crate::ui::render;
+    use crate::viewer::{
+        find_in_line, mark_matches, FileViewer, PendingJump, ViewerReader, VIEWER_CHUNK_SIZE,
+    };
+
+    /// Writes the file to its own directory, removed with the returned `TempDir`.
+    fn temp_file(name: &str, content: &[u8]) -> (TempDir, PathBuf) {
+        let dir = TempDir::new(&format!("viewer-{}", name));
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        (dir, path)
+    }
+
+    fn numbered_lines(count: usize) -> String {
+        (1..=count).map(|i| format!("line {}\n", i)).collect()
+    }
+
+    fn open_viewer(path: &Path, max_bytes: u64) -> FileViewer {
+        let path = path.to_string_lossy().to_string();
+        let reader = ViewerReader::open(&path, max_bytes, HighlightSettings::default()).unwrap();
+        let mut viewer = FileViewer::new(path, reader);
+        viewer.page_height = 10;
+        viewer
+    }
+
+    fn load_next_chunk(viewer: &mut FileViewer) {
+        let chunk = viewer.reader.lock().unwrap().read_chunk().unwrap();
+        viewer.append_chunk(chunk);
+    }
+
+    #[test]
+    fn test_read_text_in_chunks() {
+        let content = numbered_lines(20000);
+        assert!(content.len() as u64 > VIEWER_CHUNK_SIZE);
+        let (_dir, path) = temp_file("chunks.txt", content.as_bytes());
+        let mut viewer = open_viewer(&path, u64::MAX);
+
+        load_next_chunk(&mut viewer);
+        assert!(!viewer.eof);
+        assert!(viewer.lines.len() < 20000);
+        assert_eq!(viewer.lines[0], "line 1");
+        assert!(!viewer.needs_more_lines());
+        while !viewer.eof {
+            load_next_chunk(&mut viewer);
+        }
+        assert_eq!(viewer.lines.len(), 20000);
+        assert_eq!(viewer.lines.join("\n") + "\n", content);
+        assert!(!viewer.truncated);
+        assert!(viewer.highlighted.is_empty()); // plain text
+    }
+
+    #[test]
+    fn test_read_truncated() {
+        let (_dir, path) = temp_file("truncated.txt", numbered_lines(100).as_bytes());
+        let mut viewer = open_viewer(&path, 20);
+        load_next_chunk(&mut viewer);
+        assert!(viewer.eof);
+        assert!(viewer.truncated);
+        assert_eq!(viewer.lines, vec!["line 1", "line 2", "line 3"]);
+    }
+
+    #[test]
+    fn test_read_binary() {
+        let bytes: Vec<u8> = (0..40u8).collect();
+        let (_dir, path) = temp_file("binary.bin", &bytes);
+        let mut viewer = open_viewer(&path, u64::MAX);
+        load_next_chunk(&mut viewer);
+        assert!(viewer.binary);
+        assert!(viewer.eof);
+        assert_eq!(viewer.lines.len(), 3);
+        assert!(viewer.lines[2].starts_with("00000020  20 21 22"));
+    }
+
+    #[test]
+    fn test_read_highlighted() {
+        let (_dir, path) = temp_file("code.rs", b"/* block\ncomment */\nfn main() {}\n");
+        let mut viewer = open_viewer(&path, u64::MAX);
+        load_next_chunk(&mut viewer);
+        assert_eq!(viewer.highlighted.len(), 3);
+        assert_eq!(viewer.display_line(1).to_string(), "comment */");
+        // the comment color continues on the second line
+        assert_eq!(
+            viewer.highlighted[0].spans[0].style.fg,
+            viewer.highlighted[1].spans[0].style.fg
+        );
+    }
+
+    #[test]
+    fn test_search_across_chunks() {
+        let (_dir, path) = temp_file("search.txt", numbered_lines(20000).as_bytes());
+        let mut viewer = open_viewer(&path, u64::MAX);
+        load_next_chunk(&mut viewer);
+
+        viewer.search("LINE 19999".to_string());
+        assert!(matches!(
+            viewer.pending_jump,
+            Some(PendingJump::Search { .. })
+        ));
+        assert!(viewer.needs_more_lines());
+        while viewer.pending_jump.is_some() {
+            load_next_chunk(&mut viewer);
+        }
+        assert_eq!(viewer.current_match, None); // case-sensitive because of uppercase letters
+        assert!(viewer.message.is_some());
+
+        viewer.search("line 1999".to_string());
+        assert_eq!(viewer.current_match, Some(1998));
+        assert!(viewer.scroll_y <= 1998 && 1998 < viewer.scroll_y + viewer.page_height);
+        viewer.search_next(true);
+        assert_eq!(viewer.current_match, Some(19989)); // "line 19990"
+        viewer.search_next(false);
+        assert_eq!(viewer.current_match, Some(1998));
+        viewer.search_next(false);
+        assert_eq!(viewer.current_match, Some(1998));
+        assert!(viewer.message.is_some());
+    }
+
+    #[test]
+    fn test_go_to_line() {
+        let (_dir, path) = temp_file("goto.txt", numbered_lines(20000).as_bytes());
+        let mut viewer = open_viewer(&path, u64::MAX);
+        load_next_chunk(&mut viewer);
+
+        viewer.go_to_line(99);
+        assert_eq!(viewer.scroll_y, 99);
+        viewer.go_to_line(usize::MAX);
+        assert!(viewer.pending_jump.is_some());
+        while viewer.pending_jump.is_some() {
+            load_next_chunk(&mut viewer);
+        }
+        assert_eq!(viewer.scroll_y, 20000 - 10);
+        viewer.scroll_by(5);
+        assert_eq!(viewer.scroll_y, 20000 - 10);
+        viewer.scroll_by(-30000);
+        assert_eq!(viewer.scroll_y, 0);
+    }
+
+    #[test]
+    fn test_find_in_line() {
+        assert_eq!(
+            find_in_line("Foo foo FOO", "foo"),
+            vec![(0, 3), (4, 7), (8, 11)]
+        );
+        assert_eq!(find_in_line("Foo foo FOO", "Foo"), vec![(0, 3)]);
+        assert_eq!(find_in_line("zażółć gęślą", "ŁĆ"), vec![]);
+        assert_eq!(find_in_line("ZAŻÓŁĆ gęślą", "łć"), vec![(4, 6)]);
+        assert_eq!(find_in_line("aaaa", "aa"), vec![(0, 2), (2, 4)]);
+        assert_eq!(find_in_line("abc", ""), vec![]);
+    }
+
+    #[test]
+    fn test_mark_matches() {
+        let red = Style::default().fg(Color::Red);
+        let line = Line::from(vec![Span::styled("let ab", red), Span::raw("c = 1;")]);
+        let marked = mark_matches(line, "abc", Style::default().reversed());
+        let texts: Vec<&str> = marked.spans.iter().map(|it| it.content.as_ref()).collect();
+        assert_eq!(texts, vec!["let ", "ab", "c", " = 1;"]);
+        assert_eq!(marked.spans[0].style, red);
+        assert_eq!(marked.spans[1].style, red.reversed());
+        assert_eq!(marked.spans[2].style, Style::default().reversed());
+        assert_eq!(marked.spans[3].style, Style::default());
+    }
+
+    fn render_screen(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(|frame| render(app, frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_binary_viewer() {
+        let bytes: Vec<u8> = (0..40u8).collect();
+        let (_dir, path) = temp_file("render.bin", &bytes);
+        let mut viewer = open_viewer(&path, u64::MAX);
+        load_next_chunk(&mut viewer);
+        let mut app = App::new();
+        app.viewer = Some(viewer);
+        app.window_focus = WindowFocus::Viewer;
+
+        let screen = render_screen(&mut app, 80, 8);
+        assert!(screen[0].contains("(binary)"));
+        assert!(screen[1].starts_with("│00000000  00 01 02"));
+        assert!(screen[3].starts_with("│00000020  20 21 22"));
+    }
+}
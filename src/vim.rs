@@ -0,0 +1,103 @@
+use crate::keymap::Command;
+
+/// Mode of the tree window when vim-style navigation is enabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VimMode {
+    #[default]
+    Normal, // characters are motions and operators
+    Insert, // characters are typed into the search
+}
+
+impl VimMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VimMode::Normal => "NORMAL",
+            VimMode::Insert => "INSERT",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VimState {
+    pub mode: VimMode,
+    pub pending_keys: String, // count and beginning of a sequence typed so far
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimAction {
+    Run(Command),
+    Insert, // switches to the insert mode, keeping the search text
+    Search, // clears the search text and switches to the insert mode
+}
+
+/// Key sequences of the normal mode.
+pub const VIM_SEQUENCES: &[(&str, VimAction)] = &[
+    ("j", VimAction::Run(Command::MoveDown)),
+    ("k", VimAction::Run(Command::MoveUp)),
+    ("h", VimAction::Run(Command::GoUp)),
+    ("l", VimAction::Run(Command::GoInto)),
+    ("gg", VimAction::Run(Command::MoveTop)),
+    ("G", VimAction::Run(Command::MoveBottom)),
+    ("H", VimAction::Run(Command::GoBack)),
+    ("L", VimAction::Run(Command::GoForward)),
+    ("o", VimAction::Run(Command::Confirm)),
+    ("q", VimAction::Run(Command::Cancel)),
+    ("i", VimAction::Insert),
+    ("/", VimAction::Search),
+    ("yy", VimAction::Run(Command::Yank)),
+    ("dd", VimAction::Run(Command::Cut)),
+    ("dD", VimAction::Run(Command::MoveToTrash)),
+    ("p", VimAction::Run(Command::Paste)),
+    ("u", VimAction::Run(Command::Undo)),
+    ("cw", VimAction::Run(Command::Rename)),
+    (" ", VimAction::Run(Command::ToggleMark)),
+    ("zh", VimAction::Run(Command::ToggleHidden)),
+    ("zi", VimAction::Run(Command::ToggleIgnored)),
+    ("m", VimAction::Run(Command::AddBookmark)),
+    ("'", VimAction::Run(Command::ShowBookmarks)),
+    ("?", VimAction::Run(Command::ShowHelp)),
+];
+
+/// Counts are capped so that they still fit in cursor offsets.
+pub const MAX_VIM_COUNT: usize = i32::MAX as usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimKeys {
+    Pending, // beginning of a sequence, wait for more keys
+    Unknown,
+    Complete {
+        count: Option<usize>,
+        action: VimAction,
+    },
+}
+
+/// Parses the keys typed in the normal mode: an optional count followed by a sequence, e.g. `5j`.
+pub fn parse_vim_keys(keys: &str) -> VimKeys {
+    let sequence = keys.trim_start_matches(|c: char| c.is_ascii_digit());
+    let count_digits = &keys[..keys.len() - sequence.len()];
+    if count_digits.starts_with('0') {
+        return VimKeys::Unknown;
+    }
+    if sequence.is_empty() {
+        return VimKeys::Pending;
+    }
+    if let Some((_, action)) = VIM_SEQUENCES.iter().find(|(it, _)| *it == sequence) {
+        let count: Option<usize> = match count_digits.is_empty() {
+            true => None,
+            false => Some(
+                count_digits
+                    .parse::<usize>()
+                    .unwrap_or(MAX_VIM_COUNT)
+                    .min(MAX_VIM_COUNT),
+            ),
+        };
+        return VimKeys::Complete {
+            count,
+            action: *action,
+        };
+    }
+    match VIM_SEQUENCES.iter().any(|(it, _)| it.starts_with(sequence)) {
+        true => VimKeys::Pending,
+        false => VimKeys::Unknown,
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::app::App;
+    use crate::filesystem::get_path_file_nodes;
+    use crate::keyboard::on_vim_action;
+    use crate::keymap::Command;
+    use crate::testing::{path_string, TempDir};
+    use crate::vim::{parse_vim_keys, VimAction, VimKeys, VimMode, MAX_VIM_COUNT};
+
+    fn complete(count: Option<usize>, action: VimAction) -> VimKeys {
+        VimKeys::Complete { count, action }
+    }
+
+    #[test]
+    fn test_parse_vim_keys() {
+        assert_eq!(
+            parse_vim_keys("j"),
+            complete(None, VimAction::Run(Command::MoveDown))
+        );
+        assert_eq!(
+            parse_vim_keys("5j"),
+            complete(Some(5), VimAction::Run(Command::MoveDown))
+        );
+        assert_eq!(
+            parse_vim_keys("12gg"),
+            complete(Some(12), VimAction::Run(Command::MoveTop))
+        );
+        assert_eq!(parse_vim_keys("1"), VimKeys::Pending);
+        assert_eq!(parse_vim_keys("g"), VimKeys::Pending);
+        assert_eq!(parse_vim_keys("3d"), VimKeys::Pending);
+        assert_eq!(
+            parse_vim_keys("dd"),
+            complete(None, VimAction::Run(Command::Cut))
+        );
+        assert_eq!(parse_vim_keys("/"), complete(None, VimAction::Search));
+        assert_eq!(parse_vim_keys("dx"), VimKeys::Unknown);
+        assert_eq!(parse_vim_keys("0j"), VimKeys::Unknown);
+        assert_eq!(parse_vim_keys("x"), VimKeys::Unknown);
+        assert_eq!(
+            parse_vim_keys("99999999999j"),
+            complete(Some(MAX_VIM_COUNT), VimAction::Run(Command::MoveDown))
+        );
+        assert_eq!(
+            parse_vim_keys("999999999999999999999999h"),
+            complete(Some(MAX_VIM_COUNT), VimAction::Run(Command::GoUp))
+        );
+    }
+
+    #[test]
+    fn test_huge_counts() {
+        let dir = TempDir::new("vim-counts");
+        fs::create_dir_all(dir.join("a/b")).unwrap();
+        for name in ["x", "y", "z"] {
+            fs::write(dir.join("a/b").join(name), "").unwrap();
+        }
+        let mut app = App::new();
+        app.parent_file_nodes = get_path_file_nodes(&path_string(&dir.join("a/b"))).unwrap();
+        app.populate_current_child_nodes();
+
+        on_vim_action(
+            &mut app,
+            Some(MAX_VIM_COUNT),
+            VimAction::Run(Command::MoveDown),
+        );
+        assert_eq!(app.get_selected_tree_node().unwrap().name(), "z");
+        on_vim_action(
+            &mut app,
+            Some(MAX_VIM_COUNT),
+            VimAction::Run(Command::MoveUp),
+        );
+        assert_eq!(app.get_selected_tree_node().unwrap().name(), ".");
+        on_vim_action(&mut app, Some(MAX_VIM_COUNT), VimAction::Run(Command::GoUp));
+        assert_eq!(app.get_current_string_path(), "/");
+    }
+
+    #[test]
+    fn test_pending_keys() {
+        let mut app = App::new();
+        app.vim_mode = true;
+        assert_eq!(app.get_vim_mode_indicator().unwrap(), "-- NORMAL --");
+        assert_eq!(app.push_vim_key('2'), VimKeys::Pending);
+        assert_eq!(app.push_vim_key('y'), VimKeys::Pending);
+        assert_eq!(app.get_vim_mode_indicator().unwrap(), "-- NORMAL -- 2y");
+        assert_eq!(
+            app.push_vim_key('y'),
+            complete(Some(2), VimAction::Run(Command::Yank))
+        );
+        assert!(!app.has_vim_pending_keys());
+        app.push_vim_key('g');
+        assert_eq!(app.push_vim_key('x'), VimKeys::Unknown);
+        assert!(!app.has_vim_pending_keys());
+
+        app.enter_vim_insert_mode();
+        assert_eq!(app.vim.mode, VimMode::Insert);
+        assert!(!app.is_vim_normal_mode());
+        app.vim_mode = false;
+        assert_eq!(app.get_vim_mode_indicator(), None);
+    }
+}